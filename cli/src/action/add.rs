@@ -6,7 +6,7 @@ use thiserror::Error;
 use crate::cmd::matcher::{MainMatcher, Matcher, add::AddMatcher};
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 use crate::util::tomb;
-use crate::util::{cli, edit, error, stdin, sync};
+use crate::util::{cli, edit, error, search, stdin, sync};
 
 /// Add secret action.
 pub struct Add<'a> {
@@ -82,11 +82,13 @@ impl<'a> Add<'a> {
         }
 
         // Encrypt and write changed plaintext
-        // TODO: select proper recipients (use from current file?)
-        let recipients = store.recipients()?;
+        let recipients = store.recipients_for(&secret)?;
         crate::crypto::context(&matcher_main)?
-            .encrypt_file(&recipients, plaintext, &path)
+            .encrypt_file(&recipients, plaintext.clone(), &path)
             .map_err(Err::Write)?;
+        search::update(&store, &matcher_main, |index| {
+            index.update(&secret, &plaintext).map(|_| true)
+        });
 
         // Finalize sync
         if !matcher_add.no_sync() {
@@ -0,0 +1,118 @@
+use std::fs::File;
+use std::io::{self, Read};
+
+use anyhow::Result;
+use clap::ArgMatches;
+use prs_lib::{Plaintext, Secret, Store, crypto::prelude::*, types::BINARY_MARKER};
+use thiserror::Error;
+
+use crate::cmd::matcher::{MainMatcher, Matcher, attach::AttachMatcher};
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+use crate::util::{cli, error, search, sync};
+
+/// Attach file as binary secret action.
+pub struct Attach<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> Attach<'a> {
+    /// Construct a new attach action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the attach action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let matcher_attach = AttachMatcher::with(self.cmd_matches).unwrap();
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+        let sync = store.sync();
+
+        // Open file to attach early, to fail before preparing anything
+        let input: Box<dyn Read> = match matcher_attach.file() {
+            Some(path) => Box::new(File::open(path).map_err(Err::Open)?),
+            None => Box::new(io::stdin().lock()),
+        };
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        // Prepare sync
+        sync::ensure_ready(&sync, matcher_attach.allow_dirty());
+        if !matcher_attach.no_sync() {
+            sync.prepare()?;
+        }
+
+        // Normalize destination path
+        let path = store
+            .normalize_secret_path(matcher_attach.name(), None, true)
+            .map_err(Err::NormalizePath)?;
+        let secret = Secret::from(&store, path.clone());
+
+        // Check if destination already exists if not forcing
+        if !matcher_main.force() && path.is_file() {
+            eprintln!("A secret at '{}' already exists", path.display(),);
+            if !cli::prompt_yes("Overwrite?", Some(true), &matcher_main) {
+                if matcher_main.verbose() {
+                    eprintln!("Attaching cancelled");
+                }
+                error::quit();
+            }
+        }
+
+        // Stream file through encryption into secret, mark it as binary
+        let recipients = store.recipients_for(&secret)?;
+        crate::crypto::context(&matcher_main)?
+            .encrypt_file_stream(&recipients, &mut BINARY_MARKER.chain(input), &path)
+            .map_err(Err::Write)?;
+        search::update(&store, &matcher_main, |index| {
+            index
+                .update(&secret, &Plaintext::from(BINARY_MARKER.to_vec()))
+                .map(|_| true)
+        });
+
+        // Finalize sync
+        if !matcher_attach.no_sync() {
+            sync.finalize(format!("Attach file to {}", secret.name))?;
+        }
+
+        // Finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, true).map_err(Err::Tomb)?;
+
+        if !matcher_main.quiet() {
+            eprintln!("File attached");
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("failed to open file to attach")]
+    Open(#[source] io::Error),
+
+    #[error("failed to normalize destination path")]
+    NormalizePath(#[source] anyhow::Error),
+
+    #[error("failed to write secret")]
+    Write(#[source] anyhow::Error),
+}
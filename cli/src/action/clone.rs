@@ -7,7 +7,7 @@ use prs_lib::{Store, crypto};
 use thiserror::Error;
 
 use crate::cmd::matcher::{MainMatcher, Matcher, clone::CloneMatcher};
-use crate::util::{self, cli, style};
+use crate::util::{self, style};
 
 /// Clone store action.
 pub struct Clone<'a> {
@@ -39,15 +39,7 @@ impl<'a> Clone<'a> {
             .map_err(Err::Clone)?;
 
         // Import repo recipients missing in keychain
-        let confirm_callback = |fingerprint| {
-            matcher_main.force()
-                || cli::prompt_yes(
-                    &format!("Import recipient key {fingerprint} into keychain?"),
-                    Some(true),
-                    &matcher_main,
-                )
-        };
-        crypto::store::import_missing_keys_from_store(&store, confirm_callback)
+        crate::action::housekeeping::sync_keys::import_missing_keys(&store, &matcher_main)
             .map_err(Err::ImportRecipients)?;
 
         // Run housekeeping
@@ -0,0 +1,234 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::Result;
+use clap::ArgMatches;
+use prs_lib::{
+    Ciphertext, Plaintext, Secret, Store,
+    crypto::{
+        Context,
+        prelude::*,
+        shamir::{self, Share},
+    },
+};
+use thiserror::Error;
+
+use crate::cmd::matcher::{MainMatcher, Matcher, combine::CombineMatcher};
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+use crate::util::{
+    error::{self, ErrorHintsBuilder, quit_error},
+    secret, select, stdin, style,
+};
+
+/// Header of an ASCII armored message.
+const ARMOR_BEGIN: &str = "-----BEGIN PGP MESSAGE-----";
+
+/// Footer of an ASCII armored message.
+const ARMOR_END: &str = "-----END PGP MESSAGE-----";
+
+/// Combine secret action.
+pub struct Combine<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> Combine<'a> {
+    /// Construct a new combine action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the combine action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let matcher_combine = CombineMatcher::with(self.cmd_matches).unwrap();
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        let secret = select::store_select_secret(&store, matcher_combine.query(), &matcher_main)
+            .ok_or(Err::NoneSelected)?;
+        secret::print_name(
+            matcher_combine.query(),
+            &secret,
+            &store,
+            matcher_main.quiet(),
+        );
+
+        // Collect shares we can decrypt from the store, and shares given to us
+        let mut context = crate::crypto::context(&matcher_main)?;
+        let mut shares = store_shares(&mut context, &secret, &matcher_main)?;
+        let files = matcher_combine.shares();
+        for path in &files {
+            let data = if path.as_os_str() == "-" {
+                stdin::read_plaintext(!matcher_main.quiet())?
+            } else {
+                fs::read(path).map_err(Err::ReadShare)?.into()
+            };
+            shares.extend(parse_shares(&mut context, data)?);
+        }
+
+        // Without given shares, read more from stdin if we don't have enough
+        if files.is_empty() && !enough(&shares) {
+            if !matcher_main.quiet() {
+                eprintln!("Not enough shares in store to reconstruct secret, provide more shares");
+            }
+            let data = stdin::read_plaintext(!matcher_main.quiet())?;
+            shares.extend(parse_shares(&mut context, data)?);
+        }
+
+        let plaintext = match shamir::combine(&shares) {
+            Ok(plaintext) => plaintext,
+            Err(err) => quit_error(
+                err.context("failed to reconstruct secret"),
+                ErrorHintsBuilder::from_matcher(&matcher_main)
+                    .add_info(format!(
+                        "Ask share holders to decrypt their share with '{}' and pass it to '{}'",
+                        style::highlight("gpg --decrypt <SHARE_FILE>"),
+                        style::highlight("--share"),
+                    ))
+                    .build()
+                    .unwrap(),
+            ),
+        };
+
+        // Finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, false).map_err(Err::Tomb)?;
+
+        secret::print(plaintext).map_err(Err::Print)?;
+
+        Ok(())
+    }
+}
+
+/// Decrypt all shares of the given secret in the store that we have a key for.
+fn store_shares(
+    context: &mut Context,
+    secret: &Secret,
+    matcher_main: &MainMatcher,
+) -> Result<Vec<Share>> {
+    let mut shares = vec![];
+    for (index, path) in shamir::find_shares(&secret.path) {
+        if !context.can_decrypt_file(&path).unwrap_or(false) {
+            continue;
+        }
+        match decrypt_share(context, &path) {
+            Ok(share) => {
+                if matcher_main.verbose() {
+                    eprintln!("Decrypted share {index} from store");
+                }
+                shares.push(share);
+            }
+            Err(err) => error::print_error(err.context(format!(
+                "failed to decrypt share {index} from store, ignoring"
+            ))),
+        }
+    }
+    Ok(shares)
+}
+
+/// Decrypt and parse the share file at the given path.
+fn decrypt_share(context: &mut Context, path: &Path) -> Result<Share> {
+    Share::from_plaintext(&context.decrypt_file(path).map_err(Err::Decrypt)?)
+}
+
+/// Parse shares from the given data.
+///
+/// The data may hold plaintext shares separated by empty lines, and ASCII armored messages with
+/// encrypted shares. Other data is decrypted as binary ciphertext.
+fn parse_shares(context: &mut Context, data: Plaintext) -> Result<Vec<Share>> {
+    let Ok(mut rest) = data.unsecure_to_str() else {
+        let plaintext = context
+            .decrypt(Ciphertext::from(data.unsecure_ref().to_vec()))
+            .map_err(Err::Decrypt)?;
+        return parse_plaintext_shares(&plaintext);
+    };
+
+    let mut shares = vec![];
+    while let Some(start) = rest.find(ARMOR_BEGIN) {
+        let end = rest[start..]
+            .find(ARMOR_END)
+            .map(|end| start + end + ARMOR_END.len())
+            .ok_or(Err::Armor)?;
+        shares.extend(parse_plaintext_shares(&rest[..start].into())?);
+        let plaintext = context
+            .decrypt(Ciphertext::from(rest.as_bytes()[start..end].to_vec()))
+            .map_err(Err::Decrypt)?;
+        shares.extend(parse_plaintext_shares(&plaintext)?);
+        rest = &rest[end..];
+    }
+    shares.extend(parse_plaintext_shares(&rest.into())?);
+    Ok(shares)
+}
+
+/// Parse plaintext shares, separated by empty lines.
+fn parse_plaintext_shares(plaintext: &Plaintext) -> Result<Vec<Share>> {
+    let text = plaintext.unsecure_to_str().map_err(|_| Err::Parse)?;
+    let mut shares = vec![];
+    let mut block: Vec<&str> = vec![];
+    for line in text.lines().chain([""]) {
+        if !line.trim().is_empty() {
+            block.push(line);
+            continue;
+        }
+        if !block.is_empty() {
+            let share = Plaintext::from(block.join("\n"));
+            shares.push(Share::from_plaintext(&share).map_err(Err::Share)?);
+            block.clear();
+        }
+    }
+    Ok(shares)
+}
+
+/// Check whether there are enough distinct shares to reconstruct.
+fn enough(shares: &[Share]) -> bool {
+    let Some(first) = shares.first() else {
+        return false;
+    };
+    let mut indices: Vec<u8> = shares.iter().map(|share| share.index).collect();
+    indices.sort_unstable();
+    indices.dedup();
+    indices.len() >= first.threshold as usize
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("no secret selected")]
+    NoneSelected,
+
+    #[error("failed to read share file")]
+    ReadShare(#[source] std::io::Error),
+
+    #[error("failed to decrypt share")]
+    Decrypt(#[source] anyhow::Error),
+
+    #[error("unterminated ASCII armored message")]
+    Armor,
+
+    #[error("failed to parse shares, data is not UTF-8")]
+    Parse,
+
+    #[error("failed to parse share")]
+    Share(#[source] anyhow::Error),
+
+    #[error("failed to print reconstructed secret to stdout")]
+    Print(#[source] std::io::Error),
+}
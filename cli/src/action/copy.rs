@@ -6,7 +6,7 @@ use thiserror::Error;
 use crate::cmd::matcher::{MainMatcher, Matcher, copy::CopyMatcher};
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 use crate::util::tomb;
-use crate::util::{clipboard, secret, select};
+use crate::util::{clipboard, expiry, secret, select};
 
 /// Copy secret to clipboard action.
 pub struct Copy<'a> {
@@ -37,7 +37,7 @@ impl<'a> Copy<'a> {
         #[cfg(all(feature = "tomb", target_os = "linux"))]
         tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
 
-        let secret = select::store_select_secret(&store, matcher_copy.query(), &matcher_main)
+        let secret = select::store_select_secret_read(&store, matcher_copy.query(), &matcher_main)
             .ok_or(Err::NoneSelected)?;
 
         secret::print_name(matcher_copy.query(), &secret, &store, matcher_main.quiet());
@@ -46,6 +46,9 @@ impl<'a> Copy<'a> {
             .decrypt_file(&secret.path)
             .map_err(Err::Read)?;
 
+        // Warn if secret is expired
+        expiry::warn_expired(&store, &secret, &plaintext, &matcher_main);
+
         // Trim plaintext to property or first line
         if let Some(property) = matcher_copy.property() {
             plaintext = plaintext.property(property).map_err(Err::Property)?;
@@ -8,7 +8,7 @@ use thiserror::Error;
 use crate::cmd::matcher::{MainMatcher, Matcher, duplicate::DuplicateMatcher};
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 use crate::util::tomb;
-use crate::util::{cli, error, select, sync};
+use crate::util::{cli, error, search, select, sync};
 
 /// Duplicate secret action.
 pub struct Duplicate<'a> {
@@ -72,6 +72,23 @@ impl<'a> Duplicate<'a> {
         // Copy secret
         fs::copy(&secret.path, path).map_err(Err::Copy)?;
 
+        // Copy secret tags and search index entry along
+        let mut tags = store.tags().map_err(Err::Tags)?;
+        if tags.copy_secret(&secret, &new_secret) {
+            tags.save().map_err(Err::Tags)?;
+        }
+        search::update(&store, &matcher_main, |index| {
+            Ok(index.copy(&secret, &new_secret))
+        });
+
+        // Re-encrypt if copied to a directory with other recipients
+        crate::action::housekeeping::recrypt::recrypt_moved(
+            &store,
+            &secret.path,
+            &new_secret,
+            &matcher_main,
+        )?;
+
         // Finalize sync
         if !matcher_duplicate.no_sync() {
             sync.finalize(format!(
@@ -109,4 +126,7 @@ pub enum Err {
 
     #[error("failed to copy secret file")]
     Copy(#[source] std::io::Error),
+
+    #[error("failed to update secret tags")]
+    Tags(#[source] anyhow::Error),
 }
@@ -6,7 +6,7 @@ use thiserror::Error;
 use crate::cmd::matcher::{MainMatcher, Matcher, edit::EditMatcher};
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 use crate::util::tomb;
-use crate::util::{cli, edit, error, secret, select, stdin, sync};
+use crate::util::{cli, edit, error, search, secret, select, stdin, sync};
 
 /// Edit secret plaintext action.
 pub struct Edit<'a> {
@@ -55,6 +55,9 @@ impl<'a> Edit<'a> {
         if matcher_edit.stdin() {
             plaintext = stdin::read_plaintext(!matcher_main.quiet())?;
         } else {
+            if plaintext.is_binary() {
+                return Err(Err::Binary.into());
+            }
             plaintext = match edit::edit(&plaintext).map_err(Err::Edit)? {
                 Some(changed) => changed,
                 None => {
@@ -78,11 +81,13 @@ impl<'a> Edit<'a> {
         }
 
         // Encrypt and write changed plaintext
-        // TODO: select proper recipients (use from current file?)
-        let recipients = store.recipients()?;
+        let recipients = store.recipients_for(&secret)?;
         context
-            .encrypt_file(&recipients, plaintext, &secret.path)
+            .encrypt_file(&recipients, plaintext.clone(), &secret.path)
             .map_err(Err::Write)?;
+        search::update(&store, &matcher_main, |index| {
+            index.update(&secret, &plaintext).map(|_| true)
+        });
 
         // Finalize sync
         if !matcher_edit.no_sync() {
@@ -116,6 +121,9 @@ pub enum Err {
     #[error("failed to read secret")]
     Read(#[source] anyhow::Error),
 
+    #[error("cannot edit binary secret in editor, use attach or extract instead")]
+    Binary,
+
     #[error("failed to edit secret in editor")]
     Edit(#[source] anyhow::Error),
 
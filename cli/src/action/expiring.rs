@@ -0,0 +1,222 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use anyhow::Result;
+use chrono::{DateTime, Local};
+use clap::ArgMatches;
+use prs_lib::{
+    Secret, Store,
+    crypto::{self, prelude::*},
+};
+use thiserror::Error;
+
+use crate::cmd::matcher::{MainMatcher, Matcher, expiring::ExpiringMatcher};
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+use crate::util::{
+    error::{self, ErrorHints, ErrorHintsBuilder},
+    expiry,
+    output::{self, ExpiringOutput, SecretOutput},
+    progress::{self, ProgressBarExt},
+    style, time,
+};
+
+/// Maximum number of failures without forcing.
+const MAX_FAIL: usize = 4;
+
+/// List expiring secrets action.
+pub struct Expiring<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> Expiring<'a> {
+    /// Construct a new expiring action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the expiring action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let matcher_expiring = ExpiringMatcher::with(self.cmd_matches).unwrap();
+
+        let within = matcher_expiring.within().map_err(Err::Within)?;
+        let until = expiry::add_duration(Local::now(), within);
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        // Select secrets based on filters, sort the list
+        let mut secrets: Vec<Secret> = store
+            .secret_iter()
+            .filter_name(matcher_expiring.query())
+            .collect();
+        secrets.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+
+        // Find expiring secrets, sort by expiry
+        let changed = store.sync().last_commit_times(None).unwrap_or_default();
+        let mut expiring = find_expiring(
+            &secrets,
+            &changed,
+            &matcher_main,
+            matcher_expiring.jobs(&matcher_main),
+        )?;
+        expiring.retain(|(_, expires)| until.is_none_or(|until| *expires <= until));
+        expiring.sort_by_key(|(_, expires)| *expires);
+
+        let now = Local::now();
+        if matcher_main.json() {
+            let expiring: Vec<_> = expiring
+                .iter()
+                .map(|(secret, expires)| ExpiringOutput {
+                    secret: SecretOutput::new(&store, secret),
+                    expires: expires.to_rfc3339(),
+                    expired: *expires <= now,
+                })
+                .collect();
+            output::print_json(&expiring).map_err(Err::Output)?;
+        } else if expiring.is_empty() {
+            if !matcher_main.quiet() {
+                eprintln!(
+                    "No secrets expiring within {}",
+                    time::format_duration(within.try_into().unwrap_or(u32::MAX))
+                );
+            }
+        } else {
+            let width = expiring.iter().map(|(s, _)| s.name.len()).max().unwrap();
+            for (secret, expires) in &expiring {
+                let date = expires.format(expiry::DATE_FORMAT);
+                if *expires <= now {
+                    println!(
+                        "{:width$}  {}",
+                        secret.name,
+                        style::highlight_error(format!("expired on {date}")),
+                    );
+                } else {
+                    println!("{:width$}  due on {date}", secret.name);
+                }
+            }
+        }
+
+        // Finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, false).map_err(Err::Tomb)?;
+
+        Ok(())
+    }
+}
+
+/// Find all secrets with an expiry, along with the expiry time.
+///
+/// `changed` maps secret paths to the time they were last committed. Secrets without commit
+/// fall back to their file modification time. Secrets are decrypted in parallel using the given
+/// number of jobs.
+fn find_expiring<'a>(
+    secrets: &'a [Secret],
+    changed: &HashMap<PathBuf, SystemTime>,
+    matcher_main: &MainMatcher,
+    jobs: usize,
+) -> Result<Vec<(&'a Secret, DateTime<Local>)>> {
+    let mut expiring = vec![];
+    let mut failed = 0;
+
+    // Progress bar
+    let pb = progress::progress_bar(secrets.len() as u64, matcher_main.quiet());
+
+    crypto::parallel(
+        &crate::crypto::config(matcher_main),
+        jobs,
+        secrets,
+        |context, secret| context.decrypt_file(&secret.path),
+        |secret, plaintext| {
+            pb.set_message_trunc(&secret.name);
+
+            let result = plaintext.map(|plaintext| {
+                // Skip binary secrets and secrets without expiry
+                if plaintext.is_binary() || !expiry::has_expiry(&plaintext) {
+                    return None;
+                }
+
+                // Report invalid expiry properties, but don't fail on them
+                let changed = || {
+                    changed
+                        .get(&secret.path)
+                        .copied()
+                        .or_else(|| secret.path.metadata().and_then(|m| m.modified()).ok())
+                };
+                expiry::expiry(&plaintext, changed).unwrap_or_else(|err| {
+                    pb.suspend(|| {
+                        error::print_warning(format!("invalid expiry in {}: {err}", secret.name))
+                    });
+                    None
+                })
+            });
+
+            match result {
+                Ok(Some(expires)) => expiring.push((secret, expires)),
+                Ok(None) => {}
+                Err(err) => {
+                    error::print_error(
+                        anyhow::Error::from(Err::Read(err))
+                            .context(format!("failed to check: {}", secret.name)),
+                    );
+                    failed += 1;
+                }
+            }
+
+            pb.inc(1);
+
+            // Stop after many failures
+            if failed > MAX_FAIL && !matcher_main.force() {
+                error::quit_error_msg(
+                    format!("stopped after {failed} failures"),
+                    ErrorHintsBuilder::from_matcher(matcher_main)
+                        .force(true)
+                        .build()
+                        .unwrap(),
+                );
+            }
+        },
+    );
+
+    pb.finish_and_clear();
+
+    if failed > 0 {
+        error::quit_error_msg(
+            format!("Failed to check {} of {} secrets", failed, secrets.len()),
+            ErrorHints::default(),
+        );
+    }
+
+    Ok(expiring)
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("invalid duration for --within")]
+    Within(#[source] time::ParseDurationError),
+
+    #[error("failed to read secret")]
+    Read(#[source] anyhow::Error),
+
+    #[error("failed to print secrets as JSON")]
+    Output(#[source] std::io::Error),
+}
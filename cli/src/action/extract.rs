@@ -0,0 +1,173 @@
+use std::fs;
+use std::io::{self, Write};
+
+use anyhow::Result;
+use clap::ArgMatches;
+use prs_lib::{Store, crypto::prelude::*, types::BINARY_MARKER};
+use thiserror::Error;
+
+use crate::cmd::matcher::{MainMatcher, Matcher, extract::ExtractMatcher};
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+use crate::util::{self, cli, error, secret, select};
+
+/// Extract binary secret action.
+pub struct Extract<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> Extract<'a> {
+    /// Construct a new extract action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the extract action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let matcher_extract = ExtractMatcher::with(self.cmd_matches).unwrap();
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        let secret =
+            select::store_select_secret_read(&store, matcher_extract.query(), &matcher_main)
+                .ok_or(Err::NoneSelected)?;
+
+        secret::print_name(
+            matcher_extract.query(),
+            &secret,
+            &store,
+            matcher_main.quiet(),
+        );
+
+        let mut context = crate::crypto::context(&matcher_main)?;
+        match matcher_extract.out() {
+            Some(path) => {
+                // Check if destination already exists if not forcing
+                let mut overwrite = matcher_main.force();
+                if !overwrite && path.exists() {
+                    eprintln!("A file at '{}' already exists", path.display());
+                    if !cli::prompt_yes("Overwrite?", Some(true), &matcher_main) {
+                        if matcher_main.verbose() {
+                            eprintln!("Extracting cancelled");
+                        }
+                        error::quit();
+                    }
+                    overwrite = true;
+                }
+
+                // Secret may be sensitive, only make it readable by the current user
+                let file = util::fs::create_private(path, overwrite).map_err(Err::Create)?;
+                let mut output = StripMarker::new(file);
+                if let Err(err) = context
+                    .decrypt_file_stream(&secret.path, &mut output)
+                    .and_then(|_| output.finish().map_err(Into::into))
+                {
+                    drop(output);
+                    let _ = fs::remove_file(path);
+                    return Err(Err::Read(err).into());
+                }
+
+                if !matcher_main.quiet() {
+                    eprintln!("Secret written to {}", path.display());
+                }
+            }
+            None => {
+                let mut stdout = StripMarker::new(io::stdout().lock());
+                context
+                    .decrypt_file_stream(&secret.path, &mut stdout)
+                    .map_err(Err::Read)?;
+                let _ = stdout.finish();
+            }
+        }
+
+        // Finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, false).map_err(Err::Tomb)?;
+
+        Ok(())
+    }
+}
+
+/// Writer that strips the binary marker from the start of a secret.
+///
+/// Secrets without marker are written as-is.
+struct StripMarker<W: Write> {
+    inner: W,
+
+    /// Buffered start of the secret, `None` once the marker has been checked.
+    head: Option<Vec<u8>>,
+}
+
+impl<W: Write> StripMarker<W> {
+    /// Construct a new writer wrapping the given writer.
+    fn new(inner: W) -> Self {
+        Self {
+            inner,
+            head: Some(Vec::with_capacity(BINARY_MARKER.len())),
+        }
+    }
+
+    /// Write out anything buffered and flush.
+    ///
+    /// Must be called when done, secrets shorter than the marker are buffered until now.
+    fn finish(&mut self) -> io::Result<()> {
+        if let Some(head) = self.head.take() {
+            self.inner.write_all(&head)?;
+        }
+        self.inner.flush()
+    }
+}
+
+impl<W: Write> Write for StripMarker<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let Some(head) = &mut self.head else {
+            return self.inner.write(buf);
+        };
+
+        // Buffer until we can compare with the marker
+        let len = buf.len().min(BINARY_MARKER.len() - head.len());
+        head.extend_from_slice(&buf[..len]);
+        if head.len() == BINARY_MARKER.len() {
+            if head != BINARY_MARKER {
+                self.inner.write_all(head)?;
+            }
+            self.head = None;
+        }
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("no secret selected")]
+    NoneSelected,
+
+    #[error("failed to create output file")]
+    Create(#[source] io::Error),
+
+    #[error("failed to read secret")]
+    Read(#[source] anyhow::Error),
+}
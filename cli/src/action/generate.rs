@@ -11,7 +11,7 @@ use crate::cmd::matcher::{MainMatcher, Matcher, generate::GenerateMatcher};
 use crate::util::clipboard;
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 use crate::util::tomb;
-use crate::util::{cli, edit, error, pass, secret, select, stdin, sync};
+use crate::util::{cli, edit, error, pass, search, secret, select, stdin, sync};
 
 /// Generate secret action.
 pub struct Generate<'a> {
@@ -150,11 +150,13 @@ impl<'a> Generate<'a> {
 
         // Encrypt and write changed plaintext if we need to store
         if let Some(dest) = &dest {
-            // TODO: select proper recipients (use from current file?)
-            let recipients = store.recipients()?;
+            let recipients = store.recipients_for(&dest.1)?;
             context
                 .encrypt_file(&recipients, plaintext.clone(), &dest.0)
                 .map_err(Err::Write)?;
+            search::update(&store, &matcher_main, |index| {
+                index.update(&dest.1, &plaintext).map(|_| true)
+            });
         }
 
         // Copy to clipboard after editing
@@ -2,7 +2,8 @@ use anyhow::Result;
 use clap::ArgMatches;
 use prs_lib::{
     Plaintext, Secret, Store,
-    crypto::{Context, prelude::*},
+    crypto::{self, prelude::*},
+    search::SearchIndex,
     store::SecretIterConfig,
 };
 use regex::Regex;
@@ -14,6 +15,7 @@ use crate::util::tomb;
 use crate::util::{
     error::{self, ErrorHints, ErrorHintsBuilder},
     progress::{self, ProgressBarExt},
+    search,
 };
 
 /// Maximum number of failures without forcing.
@@ -53,9 +55,12 @@ impl<'a> Grep<'a> {
             find_files: true,
             find_symlink_files: matcher_grep.with_aliases(),
         };
+        let tags = store.tags().map_err(Err::Tags)?;
+        let filter_tags = matcher_grep.tags();
         let mut secrets: Vec<Secret> = store
             .secret_iter_config(config)
             .filter_name(matcher_grep.query())
+            .filter(|secret| tags.has_all(secret, &filter_tags))
             .collect();
         secrets.sort_unstable_by(|a, b| a.name.cmp(&b.name));
 
@@ -64,12 +69,13 @@ impl<'a> Grep<'a> {
             return Err(Err::NoSecret.into());
         }
 
-        grep(
-            &secrets,
-            &matcher_grep.pattern(),
-            &matcher_main,
-            &matcher_grep,
-        )?;
+        // Without pattern, list tagged secrets without decrypting
+        match matcher_grep.pattern() {
+            Some(pattern) => grep(&store, &secrets, &pattern, &matcher_main, &matcher_grep)?,
+            None => secrets
+                .iter()
+                .for_each(|secret| println!("{}", secret.name)),
+        }
 
         // Finalize tomb
         #[cfg(all(feature = "tomb", target_os = "linux"))]
@@ -80,14 +86,20 @@ impl<'a> Grep<'a> {
 }
 
 /// Grep the given secrets.
+///
+/// Uses the search index if available, secrets that are not indexed or are outdated are
+/// decrypted in parallel and updated in the index.
 fn grep(
+    store: &Store,
     secrets: &[Secret],
     pattern: &str,
     matcher_main: &MainMatcher,
     matcher_grep: &GrepMatcher,
 ) -> Result<()> {
+    let jobs = matcher_grep.jobs(matcher_main);
     let mut context = crate::crypto::context(matcher_main)?;
-    let (mut found, mut failed) = (0, 0);
+    let mut found: Vec<String> = Vec::new();
+    let mut failed = 0;
 
     // Parse regex if enabled
     let regex = if matcher_grep.regex() {
@@ -96,24 +108,35 @@ fn grep(
         None
     };
 
+    // Load search index, fall back to decrypting all secrets
+    let mut index = None;
+    let mut index_changed = false;
+    if !matcher_grep.no_index() && SearchIndex::exists(store) {
+        match SearchIndex::load(store, &mut context) {
+            Ok(loaded) => index = Some(loaded),
+            Err(err) => {
+                error::print_error(err.context("failed to load search index, decrypting secrets"));
+                search::print_rebuild_hint(matcher_main);
+            }
+        }
+    }
+
     // Progress bar
     let pb = progress::progress_bar(secrets.len() as u64, matcher_main.quiet());
 
-    for secret in secrets.iter() {
+    // Grep plaintext of a single secret, show matches, count failures
+    let mut handle = |secret: &Secret, plaintext: Result<Option<Plaintext>>| {
         pb.set_message_trunc(&secret.name);
 
         // Parse normally or with regex
-        let result = match &regex {
-            Some(re) => grep_single_regex(&mut context, secret, re),
-            None => grep_single(&mut context, secret, pattern),
-        };
+        let result = plaintext.and_then(|plaintext| match (plaintext, &regex) {
+            (None, _) => Ok(false),
+            (Some(plaintext), Some(re)) => grep_single_regex(&plaintext, re),
+            (Some(plaintext), None) => grep_single(&plaintext, pattern),
+        });
 
-        // Grep single secret
         match result {
-            Ok(true) => {
-                pb.println_always(&secret.name);
-                found += 1;
-            }
+            Ok(true) => found.push(secret.name.clone()),
             Ok(false) => {}
             Err(err) => {
                 error::print_error(err.context(format!("failed to grep: {}", secret.name)));
@@ -133,14 +156,55 @@ fn grep(
                     .unwrap(),
             );
         }
+    };
+
+    // Grep secrets with up-to-date index entries, collect the rest to decrypt
+    let mut pending = Vec::new();
+    for secret in secrets {
+        match index.as_ref().and_then(|index| index.get(secret)) {
+            Some(entry) => handle(secret, Ok((!entry.binary).then(|| entry.plaintext.clone()))),
+            None => pending.push(secret.clone()),
+        }
     }
 
+    // Decrypt remaining secrets in parallel, update index along
+    crypto::parallel(
+        &crate::crypto::config(matcher_main),
+        jobs,
+        &pending,
+        |context, secret| Ok(context.decrypt_file(&secret.path).map_err(Err::Read)?),
+        |secret, plaintext| {
+            let plaintext = plaintext.and_then(|plaintext| {
+                if let Some(index) = &mut index {
+                    index.update(secret, &plaintext)?;
+                    index_changed = true;
+                }
+
+                // Skip binary secrets
+                Ok((!plaintext.is_binary()).then_some(plaintext))
+            });
+            handle(secret, plaintext);
+        },
+    );
+
     pb.finish_and_clear();
 
+    // Save search index with updated entries
+    if let Some(index) = index
+        && index_changed
+        && let Err(err) = index.save(store, &mut context)
+    {
+        error::print_error(err.context("failed to update search index, ignoring"));
+    }
+
+    // Show matches sorted by name, secrets complete in arbitrary order
+    found.sort_unstable();
+    found.iter().for_each(|name| println!("{name}"));
+
     if !matcher_main.quiet() {
-        if found > 0 {
+        if !found.is_empty() {
             eprintln!();
-            eprintln!("Found {} of {} matches", found, secrets.len());
+            eprintln!("Found {} of {} matches", found.len(), secrets.len());
         } else {
             eprintln!("No matches in {} secrets", secrets.len());
         }
@@ -157,10 +221,8 @@ fn grep(
 }
 
 /// Grep a single secret.
-fn grep_single(context: &mut Context, secret: &Secret, pattern: &str) -> Result<bool> {
-    let plaintext: Plaintext = context
-        .decrypt_file(&secret.path)
-        .map_err(Err::Read)?
+fn grep_single(plaintext: &Plaintext, pattern: &str) -> Result<bool> {
+    let plaintext: Plaintext = plaintext
         .unsecure_to_str()
         .map_err(Err::Utf8)?
         .to_uppercase()
@@ -173,9 +235,7 @@ fn grep_single(context: &mut Context, secret: &Secret, pattern: &str) -> Result<
 }
 
 /// Grep a single secret using a regular expression.
-fn grep_single_regex(context: &mut Context, secret: &Secret, pattern: &Regex) -> Result<bool> {
-    let plaintext = context.decrypt_file(&secret.path).map_err(Err::Read)?;
-
+fn grep_single_regex(plaintext: &Plaintext, pattern: &Regex) -> Result<bool> {
     Ok(pattern.is_match(plaintext.unsecure_to_str().map_err(Err::Utf8)?))
 }
 
@@ -187,6 +247,9 @@ pub enum Err {
     #[error("no secret to grep")]
     NoSecret,
 
+    #[error("failed to load secret tags")]
+    Tags(#[source] anyhow::Error),
+
     #[cfg(all(feature = "tomb", target_os = "linux"))]
     #[error("failed to prepare password store tomb for usage")]
     Tomb(#[source] anyhow::Error),
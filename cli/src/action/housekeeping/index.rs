@@ -0,0 +1,194 @@
+use anyhow::Result;
+use clap::ArgMatches;
+use prs_lib::{
+    Secret, Store,
+    crypto::{self, prelude::*},
+    search::SearchIndex,
+};
+use thiserror::Error;
+
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+use crate::{
+    cmd::matcher::{
+        MainMatcher, Matcher,
+        housekeeping::{HousekeepingMatcher, index::IndexMatcher},
+    },
+    util::{
+        error::{self, ErrorHints, ErrorHintsBuilder},
+        progress::{self, ProgressBarExt},
+        search,
+    },
+};
+
+/// Maximum number of failures without forcing.
+const MAX_FAIL: usize = 4;
+
+/// A housekeeping index action.
+pub struct Index<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> Index<'a> {
+    /// Construct a new index action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the index action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let _matcher_housekeeping = HousekeepingMatcher::with(self.cmd_matches).unwrap();
+        let matcher_index = IndexMatcher::with(self.cmd_matches).unwrap();
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        if matcher_index.remove() {
+            SearchIndex::delete(&store).map_err(Err::Remove)?;
+            if !matcher_main.quiet() {
+                eprintln!("Search index removed");
+            }
+        } else if matcher_index.check() {
+            check(&store, &matcher_main)?;
+        } else {
+            build(&store, &matcher_main, matcher_index.jobs(&matcher_main))?;
+        }
+
+        // Finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, false).map_err(Err::Tomb)?;
+
+        Ok(())
+    }
+}
+
+/// Check integrity of the search index.
+///
+/// Fails if the index cannot be decrypted, is corrupt, or doesn't match the store secrets.
+fn check(store: &Store, matcher_main: &MainMatcher) -> Result<()> {
+    if !SearchIndex::exists(store) {
+        return Err(Err::NoIndex.into());
+    }
+
+    let mut context = crate::crypto::context(matcher_main)?;
+    let index = match SearchIndex::load(store, &mut context) {
+        Ok(index) => index,
+        Err(err) => {
+            error::print_error(err);
+            search::print_rebuild_hint(matcher_main);
+            error::exit(1);
+        }
+    };
+
+    let problems = index.verify(store);
+    if problems.is_empty() {
+        if !matcher_main.quiet() {
+            eprintln!("Search index is up-to-date with {} secrets", index.len());
+        }
+        return Ok(());
+    }
+
+    for problem in &problems {
+        println!("{problem}");
+    }
+    error::print_error_msg(format!("Search index has {} problems", problems.len()));
+    search::print_rebuild_hint(matcher_main);
+    error::exit(1);
+}
+
+/// Build the search index from scratch.
+///
+/// Secrets are decrypted in parallel using the given number of jobs.
+fn build(store: &Store, matcher_main: &MainMatcher, jobs: usize) -> Result<()> {
+    let mut context = crate::crypto::context(matcher_main)?;
+    let mut secrets: Vec<Secret> = store
+        .secret_iter()
+        .filter(|secret| SearchIndex::indexes(store, secret))
+        .collect();
+    secrets.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+
+    let mut index = SearchIndex::new();
+    let mut failed = 0;
+
+    // Progress bar
+    let pb = progress::progress_bar(secrets.len() as u64, matcher_main.quiet());
+
+    crypto::parallel(
+        &crate::crypto::config(matcher_main),
+        jobs,
+        &secrets,
+        |context, secret| Ok(context.decrypt_file(&secret.path).map_err(Err::Read)?),
+        |secret, plaintext| {
+            pb.set_message_trunc(&secret.name);
+
+            let result = plaintext.and_then(|plaintext| index.update(secret, &plaintext));
+            if let Err(err) = result {
+                error::print_error(err.context(format!("failed to index: {}", secret.name)));
+                failed += 1;
+            }
+
+            pb.inc(1);
+
+            // Stop after many failures
+            if failed > MAX_FAIL && !matcher_main.force() {
+                error::quit_error_msg(
+                    format!("stopped after {failed} failures"),
+                    ErrorHintsBuilder::from_matcher(matcher_main)
+                        .force(true)
+                        .build()
+                        .unwrap(),
+                );
+            }
+        },
+    );
+
+    pb.finish_and_clear();
+
+    index.save(store, &mut context).map_err(Err::Write)?;
+
+    if !matcher_main.quiet() {
+        eprintln!("Indexed {} of {} secrets", index.len(), secrets.len());
+    }
+
+    if failed > 0 {
+        error::quit_error_msg(
+            format!("Failed to index {} of {} secrets", failed, secrets.len()),
+            ErrorHints::default(),
+        );
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("store has no search index, build it first")]
+    NoIndex,
+
+    #[error("failed to read secret")]
+    Read(#[source] anyhow::Error),
+
+    #[error("failed to write search index")]
+    Write(#[source] anyhow::Error),
+
+    #[error("failed to remove search index")]
+    Remove(#[source] anyhow::Error),
+}
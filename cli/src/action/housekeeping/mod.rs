@@ -1,3 +1,4 @@
+pub mod index;
 pub mod recrypt;
 pub mod run;
 pub mod sync_keys;
@@ -23,6 +24,10 @@ impl<'a> Housekeeping<'a> {
         // Create the command matcher
         let matcher_housekeeping = HousekeepingMatcher::with(self.cmd_matches).unwrap();
 
+        if matcher_housekeeping.index().is_some() {
+            return index::Index::new(self.cmd_matches).invoke();
+        }
+
         if matcher_housekeeping.recrypt().is_some() {
             return recrypt::Recrypt::new(self.cmd_matches).invoke();
         }
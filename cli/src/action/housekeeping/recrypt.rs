@@ -1,24 +1,31 @@
+use std::collections::{HashMap, HashSet, hash_map::Entry};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::slice;
+
 use anyhow::Result;
 use clap::ArgMatches;
 use thiserror::Error;
 
 use prs_lib::{
-    Recipients, Secret, Store,
-    crypto::{self, Context, prelude::*},
+    Plaintext, Recipients, Secret, Store,
+    crypto::{self, Context, prelude::*, recipients, shamir},
+    search::SearchIndex,
 };
 
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 use crate::util::tomb;
 use crate::{
+    cmd::arg::ArgJobs,
     cmd::matcher::{
         MainMatcher, Matcher,
         housekeeping::{HousekeepingMatcher, recrypt::RecryptMatcher},
     },
     util::{
-        self, cli,
+        self,
         error::{self, ErrorHintsBuilder},
         progress::{self, ProgressBarExt},
-        style, sync,
+        search, style, sync,
     },
 };
 
@@ -56,6 +63,34 @@ impl<'a> Recrypt<'a> {
         #[cfg(all(feature = "tomb", target_os = "linux"))]
         tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
 
+        let secrets = store.secrets(matcher_recrypt.query());
+        let jobs = matcher_recrypt.jobs(&matcher_main);
+
+        // Show secrets that would be re-encrypted
+        if matcher_recrypt.dry_run() {
+            let changed = if matcher_recrypt.full() {
+                secrets.clone()
+            } else {
+                find_changed(&store, &secrets, &matcher_main, jobs)?
+            };
+            changed
+                .iter()
+                .for_each(|secret| println!("{}", secret.name));
+            if !matcher_main.quiet() {
+                eprintln!(
+                    "Would re-encrypt {} of {} secrets",
+                    changed.len(),
+                    secrets.len()
+                );
+            }
+
+            // Finalize tomb
+            #[cfg(all(feature = "tomb", target_os = "linux"))]
+            tomb::finalize_tomb(&mut tomb, &matcher_main, false).map_err(Err::Tomb)?;
+
+            return Ok(());
+        }
+
         // Prepare sync
         sync::ensure_ready(&sync, matcher_recrypt.allow_dirty());
         if !matcher_recrypt.no_sync() {
@@ -63,20 +98,14 @@ impl<'a> Recrypt<'a> {
         }
 
         // Import new keys
-        let confirm_callback = |fingerprint| {
-            matcher_main.force()
-                || cli::prompt_yes(
-                    &format!("Import recipient key {fingerprint} into keychain?"),
-                    Some(true),
-                    &matcher_main,
-                )
-        };
-        crypto::store::import_missing_keys_from_store(&store, confirm_callback)
+        crate::action::housekeeping::sync_keys::import_missing_keys(&store, &matcher_main)
             .map_err(Err::ImportRecipients)?;
 
-        let secrets = store.secrets(matcher_recrypt.query());
-
-        recrypt(&store, &secrets, &matcher_main)?;
+        if matcher_recrypt.full() {
+            recrypt(&store, &secrets, &matcher_main, jobs)?;
+        } else {
+            recrypt_changed(&store, &secrets, &matcher_main, jobs)?;
+        }
 
         // Finalize sync
         if !matcher_recrypt.no_sync() {
@@ -92,45 +121,237 @@ impl<'a> Recrypt<'a> {
 }
 
 /// Re-encrypt all secrets in the given store.
+///
+/// Secrets already encrypted for their current recipients are skipped.
 pub fn recrypt_all(store: &Store, matcher_main: &MainMatcher) -> Result<()> {
-    recrypt(store, &store.secrets(None), matcher_main)
+    recrypt_changed(
+        store,
+        &store.secrets(None),
+        matcher_main,
+        ArgJobs::default_jobs(matcher_main),
+    )
 }
 
-/// Re-encrypt all given secrets.
-pub fn recrypt(store: &Store, secrets: &[Secret], matcher_main: &MainMatcher) -> Result<()> {
+/// Re-encrypt the given secrets that are not encrypted for their current recipients.
+///
+/// See [`find_changed`].
+pub fn recrypt_changed(
+    store: &Store,
+    secrets: &[Secret],
+    matcher_main: &MainMatcher,
+    jobs: usize,
+) -> Result<()> {
+    let secrets = &without_shares(secrets);
+    let changed = find_changed(store, secrets, matcher_main, jobs)?;
+    if !changed.is_empty() {
+        recrypt(store, &changed, matcher_main, jobs)?;
+    }
+
+    if !matcher_main.quiet() && changed.len() < secrets.len() {
+        eprintln!(
+            "Skipped {} secrets already encrypted for current recipients",
+            secrets.len() - changed.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Find secrets that are not encrypted for their current recipients.
+///
+/// Compares the recipient key IDs of each secret against the key IDs for the recipients of the
+/// GPG IDs file that applies to it, in parallel. This does not decrypt secrets. Secrets are
+/// included if their recipients cannot be determined.
+pub fn find_changed(
+    store: &Store,
+    secrets: &[Secret],
+    matcher_main: &MainMatcher,
+    jobs: usize,
+) -> Result<Vec<Secret>> {
+    let secrets = &without_shares(secrets);
     let mut context = crate::crypto::context(matcher_main)?;
-    let recipients = store.recipients().map_err(Err::Store)?;
 
-    let mut failed = Vec::new();
+    // Find key IDs for the recipients of each GPG IDs file, none if any key is not usable
+    let mut expected: HashMap<PathBuf, Option<Vec<String>>> = HashMap::new();
+    for secret in secrets {
+        if let Entry::Vacant(entry) =
+            expected.entry(crypto::store::store_gpg_ids_file_for(store, &secret.path))
+        {
+            let recipients = crypto::store::store_load_recipients_for(store, &secret.path)
+                .map_err(Err::Store)?;
+            entry.insert(recipients::recipients_key_ids(&mut context, &recipients).ok());
+        }
+    }
 
-    // Progress bar
     let pb = progress::progress_bar(secrets.len() as u64, matcher_main.quiet());
+    let mut changed = HashSet::new();
+    crypto::parallel(
+        &crate::crypto::config(matcher_main),
+        jobs,
+        secrets,
+        |context, secret| context.recipient_key_ids_file(&secret.path),
+        |secret, result| {
+            pb.set_message_trunc(&secret.name);
+            pb.inc(1);
+
+            let expected = &expected[&crypto::store::store_gpg_ids_file_for(store, &secret.path)];
+            let unchanged = match (expected, result) {
+                (Some(expected), Ok(mut actual)) => {
+                    actual.sort_unstable();
+                    actual.dedup();
+                    &actual == expected
+                }
+                _ => false,
+            };
+            if !unchanged {
+                changed.insert(&secret.path);
+            }
+        },
+    );
+    pb.finish_and_clear();
 
-    for secret in secrets.iter() {
-        pb.set_message_trunc(&secret.name);
+    Ok(secrets
+        .iter()
+        .filter(|secret| changed.contains(&secret.path))
+        .cloned()
+        .collect())
+}
+
+/// Re-encrypt a secret moved or copied from `from`, if it has different recipients now.
+///
+/// Recipients differ if the secret is governed by another GPG IDs file at its new location.
+/// Aliases are skipped, these share the file of their target.
+pub fn recrypt_moved(
+    store: &Store,
+    from: &Path,
+    secret: &Secret,
+    matcher_main: &MainMatcher,
+) -> Result<()> {
+    let alias = secret
+        .path
+        .symlink_metadata()
+        .is_ok_and(|meta| meta.file_type().is_symlink());
+    if alias
+        || crypto::store::store_gpg_ids_file_for(store, from)
+            == crypto::store::store_gpg_ids_file_for(store, &secret.path)
+    {
+        return Ok(());
+    }
+    recrypt(store, slice::from_ref(secret), matcher_main, 1)
+}
+
+/// Re-encrypt all given secrets.
+///
+/// Secrets are re-encrypted in parallel using the given number of jobs. Shares of split secrets
+/// are skipped.
+pub fn recrypt(
+    store: &Store,
+    secrets: &[Secret],
+    matcher_main: &MainMatcher,
+    jobs: usize,
+) -> Result<()> {
+    let secrets = &without_shares(secrets);
+    let mut context = crate::crypto::context(matcher_main)?;
+
+    let mut failed: Vec<&Secret> = Vec::new();
 
-        // Recrypt secret, show status, remember errors
-        if let Err(err) = recrypt_single(&mut context, secret, &recipients) {
-            error::print_error(err.context(format!("recrypting failed: {}", secret.name)));
-            failed.push(secret);
+    // Load search index to update along, re-encrypting invalidates its entries
+    let mut index = if SearchIndex::exists(store) {
+        match SearchIndex::load(store, &mut context) {
+            Ok(index) => Some(index),
+            Err(err) => {
+                error::print_error(err.context("failed to load search index, not updating it"));
+                None
+            }
         }
+    } else {
+        None
+    };
+
+    // Progress bar
+    let pb = progress::progress_bar(secrets.len() as u64, matcher_main.quiet());
 
-        pb.inc(1);
+    // Aliases share a file with their target, re-encrypt each file once to prevent concurrent writes
+    let mut files: Vec<Vec<&Secret>> = Vec::new();
+    let mut file_index: HashMap<PathBuf, usize> = HashMap::new();
+    for secret in secrets {
+        let path = fs::canonicalize(&secret.path).unwrap_or_else(|_| secret.path.clone());
+        match file_index.entry(path) {
+            Entry::Occupied(entry) => files[*entry.get()].push(secret),
+            Entry::Vacant(entry) => {
+                entry.insert(files.len());
+                files.push(vec![secret]);
+            }
+        }
+    }
 
-        // Stop after many failures
-        if failed.len() > MAX_FAIL && !matcher_main.force() {
-            error::quit_error_msg(
-                format!("stopped after {} failures", failed.len()),
-                ErrorHintsBuilder::from_matcher(matcher_main)
-                    .force(true)
-                    .build()
-                    .unwrap(),
+    // Load recipients for each GPG IDs file governing the secrets
+    let mut recipients: HashMap<PathBuf, Recipients> = HashMap::new();
+    for file in &files {
+        let gpg_ids = crypto::store::store_gpg_ids_file_for(store, &file[0].path);
+        if let Entry::Vacant(entry) = recipients.entry(gpg_ids) {
+            entry.insert(
+                crypto::store::store_load_recipients_for(store, &file[0].path)
+                    .map_err(Err::Store)?,
             );
         }
     }
 
+    crypto::parallel(
+        &crate::crypto::config(matcher_main),
+        jobs,
+        &files,
+        |context, file| {
+            let gpg_ids = crypto::store::store_gpg_ids_file_for(store, &file[0].path);
+            recrypt_single(context, file[0], &recipients[&gpg_ids])
+        },
+        |file, result| {
+            pb.set_message_trunc(&file[0].name);
+
+            // Show status, remember errors
+            match result {
+                Ok(plaintext) => {
+                    for secret in file {
+                        if let Some(index) = &mut index
+                            && let Err(err) = index.update(secret, &plaintext)
+                        {
+                            error::print_error(
+                                err.context("failed to update search index, ignoring"),
+                            );
+                        }
+                    }
+                }
+                Err(err) => {
+                    error::print_error(err.context(format!("recrypting failed: {}", file[0].name)));
+                    failed.extend(file);
+                }
+            }
+
+            pb.inc(file.len() as u64);
+
+            // Stop after many failures
+            if failed.len() > MAX_FAIL && !matcher_main.force() {
+                error::quit_error_msg(
+                    format!("stopped after {} failures", failed.len()),
+                    ErrorHintsBuilder::from_matcher(matcher_main)
+                        .force(true)
+                        .build()
+                        .unwrap(),
+                );
+            }
+        },
+    );
+
     pb.finish_and_clear();
 
+    // Save search index, also re-encrypts it to the current recipients
+    if let Some(index) = index
+        && let Err(err) = index.save(store, &mut context)
+    {
+        error::print_error(err.context("failed to save search index, ignoring"));
+        search::print_rebuild_hint(matcher_main);
+    }
+
     // Show success message if any is recrypted
     let recrypted = secrets.len() - failed.len();
     if !matcher_main.quiet() && recrypted > 0 {
@@ -172,14 +393,29 @@ pub fn recrypt(store: &Store, secrets: &[Secret], matcher_main: &MainMatcher) ->
 }
 
 /// Recrypt a single secret.
-fn recrypt_single(context: &mut Context, secret: &Secret, recipients: &Recipients) -> Result<()> {
+///
+/// Returns the plaintext of the secret.
+fn recrypt_single(
+    context: &mut Context,
+    secret: &Secret,
+    recipients: &Recipients,
+) -> Result<Plaintext> {
     let path = &secret.path;
     let plaintext = context.decrypt_file(path).map_err(Err::Read)?;
     context
-        .encrypt_file(recipients, plaintext, path)
+        .encrypt_file(recipients, plaintext.clone(), path)
         .map_err(Err::Write)?;
 
-    Ok(())
+    Ok(plaintext)
+}
+
+/// Leave out shares of split secrets, these are encrypted for a single recipient on purpose.
+fn without_shares(secrets: &[Secret]) -> Vec<Secret> {
+    secrets
+        .iter()
+        .filter(|secret| !shamir::is_share(&secret.path))
+        .cloned()
+        .collect()
 }
 
 #[derive(Debug, Error)]
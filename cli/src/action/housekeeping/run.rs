@@ -78,6 +78,7 @@ pub(crate) fn housekeeping(store: &Store, allow_dirty: bool, no_sync: bool) -> R
     }
 
     set_store_permissions(store).map_err(Err::Perms)?;
+    prune_tags(store).map_err(Err::Tags)?;
 
     if sync.is_init() {
         set_git_ignore(store).map_err(Err::GitAttributes)?;
@@ -111,6 +112,15 @@ fn set_store_permissions(_store: &Store) -> Result<(), std::io::Error> {
     Ok(())
 }
 
+/// Remove tags of secrets that don't exist anymore from the tags index.
+fn prune_tags(store: &Store) -> Result<()> {
+    let mut tags = store.tags()?;
+    if !tags.prune(store).is_empty() {
+        tags.save()?;
+    }
+    Ok(())
+}
+
 /// Set up the git ignore file.
 fn set_git_ignore(store: &Store) -> Result<(), std::io::Error> {
     const ENTRIES: [&str; 6] = [".host", ".last", ".tty", ".uid", ".timer", "lost+found"];
@@ -177,6 +187,9 @@ pub enum Err {
     #[error("failed to set password store permissions")]
     Perms(#[source] std::io::Error),
 
+    #[error("failed to prune secret tags index")]
+    Tags(#[source] anyhow::Error),
+
     #[error("failed to set default .gitattributes")]
     GitAttributes(#[source] std::io::Error),
 }
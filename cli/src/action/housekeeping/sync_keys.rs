@@ -1,8 +1,12 @@
 use anyhow::Result;
+use chrono::{DateTime, Local};
 use clap::ArgMatches;
 use prs_lib::{
     Store,
-    crypto::{self, store::ImportResult},
+    crypto::{
+        self,
+        store::{ImportResult, KeyImport},
+    },
 };
 use thiserror::Error;
 
@@ -13,7 +17,7 @@ use crate::{
         MainMatcher, Matcher,
         housekeeping::{HousekeepingMatcher, sync_keys::SyncKeysMatcher},
     },
-    util::{cli, sync},
+    util::{self, cli, error, error::ErrorHintsBuilder, expiry::DATE_FORMAT, style, sync},
 };
 
 /// A housekeeping sync-keys action.
@@ -84,19 +88,26 @@ impl<'a> SyncKeys<'a> {
 }
 
 /// Import missing keys from store to keychain.
-fn import_missing_keys(store: &Store, matcher_main: &MainMatcher<'_>) -> Result<()> {
+///
+/// Shows details of each key and asks for confirmation before importing it, unless forced or
+/// pinned as trusted. Key files that changed since they were trusted are never imported, not even
+/// when forced, the user must trust the recipient again.
+pub fn import_missing_keys(store: &Store, matcher_main: &MainMatcher<'_>) -> Result<()> {
     if matcher_main.verbose() {
         eprintln!("Importing missing public keys from recipients...");
     }
 
     // Import keys, report results
-    let confirm_callback = |fingerprint| {
-        matcher_main.force()
-            || cli::prompt_yes(
-                &format!("Import recipient key {fingerprint} into keychain?"),
-                Some(true),
-                matcher_main,
-            )
+    let confirm_callback = |import: &KeyImport| {
+        if matcher_main.force() {
+            return true;
+        }
+        print_key_import(import);
+        cli::prompt_yes(
+            &format!("Import recipient key {} into keychain?", import.fingerprint),
+            Some(true),
+            matcher_main,
+        )
     };
     for result in crypto::store::import_missing_keys_from_store(store, confirm_callback)? {
         match result {
@@ -111,12 +122,57 @@ fn import_missing_keys(store: &Store, matcher_main: &MainMatcher<'_>) -> Result<
             ImportResult::Rejected(fingerprint) => {
                 eprintln!("Did not import missing public key, rejected by user: {fingerprint}",)
             }
+            ImportResult::Invalid(fingerprint) => error::print_warning(format!(
+                "did not import missing public key, key file is invalid or holds a different key: {fingerprint}",
+            )),
+            ImportResult::Changed(fingerprint) => {
+                error::print_warning(format!(
+                    "key file of trusted recipient {fingerprint} changed since it was trusted, it may have been tampered with",
+                ));
+                ErrorHintsBuilder::from_matcher(matcher_main)
+                    .add_info(format!(
+                        "verify the key with its owner, then trust it again with '{}'",
+                        style::highlight(format!(
+                            "{} recipients trust {fingerprint}",
+                            util::bin_name()
+                        )),
+                    ))
+                    .verbose(false)
+                    .help(false)
+                    .build()
+                    .unwrap()
+                    .print(false);
+            }
         }
     }
 
     Ok(())
 }
 
+/// Print details of a key to import, so the user can verify it.
+fn print_key_import(import: &KeyImport) {
+    let date = |time| DateTime::<Local>::from(time).format(DATE_FORMAT);
+
+    eprintln!("Recipient key from store is missing in keychain:");
+    eprintln!("    fingerprint: {}", import.fingerprint);
+    if let Some(key) = import.key() {
+        for user_id in key.user_ids() {
+            eprintln!("    user ID:     {user_id}");
+        }
+        if let Some(created) = key.meta().created {
+            eprintln!("    created:     {}", date(created));
+        }
+    }
+    if let Some(commit) = &import.added_by {
+        eprintln!(
+            "    added in:    commit {} by {} on {}",
+            commit.id,
+            commit.author,
+            date(commit.time),
+        );
+    }
+}
+
 #[derive(Debug, Error)]
 pub enum Err {
     #[error("failed to access password store")]
@@ -7,6 +7,7 @@ use text_trees::{FormatCharacters, StringTreeNode, TreeFormatting};
 use thiserror::Error;
 
 use crate::cmd::matcher::{MainMatcher, Matcher, list::ListMatcher};
+use crate::util::output::{self, SecretOutput};
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 use crate::util::tomb;
 
@@ -44,19 +45,26 @@ impl<'a> List<'a> {
             find_files: !matcher_list.only_aliases(),
             find_symlink_files: !matcher_list.only_non_aliases(),
         };
+        let tags = store.tags().map_err(Err::Tags)?;
+        let filter_tags = matcher_list.tags();
         let mut secrets: Vec<Secret> = store
             .secret_iter_config(config)
             .filter_name(matcher_list.query())
+            .filter(|secret| tags.has_all(secret, &filter_tags))
             .collect();
         secrets.sort_unstable_by(|a, b| a.name.cmp(&b.name));
 
-        // Return nothing if we have an empty list
-        if secrets.is_empty() {
+        // Output JSON, always emit a list even if empty
+        if matcher_main.json() {
+            let secrets: Vec<_> = secrets
+                .iter()
+                .map(|secret| SecretOutput::new(&store, secret))
+                .collect();
+            output::print_json(&secrets).map_err(Err::Output)?;
+        } else if secrets.is_empty() {
+            // Return nothing if we have an empty list
             return Ok(());
-        }
-
-        // Show a list or tree
-        if matcher_list.list() {
+        } else if matcher_list.list() {
             secrets.iter().for_each(|s| println!("{}", s.name));
         } else {
             display_tree(&secrets);
@@ -150,4 +158,10 @@ pub enum Err {
     #[cfg(all(feature = "tomb", target_os = "linux"))]
     #[error("failed to prepare password store tomb for usage")]
     Tomb(#[source] anyhow::Error),
+
+    #[error("failed to load secret tags")]
+    Tags(#[source] anyhow::Error),
+
+    #[error("failed to print secrets as JSON")]
+    Output(#[source] std::io::Error),
 }
@@ -1,11 +1,15 @@
 pub mod add;
 #[cfg(feature = "alias")]
 pub mod alias;
+pub mod attach;
 pub mod clone;
+pub mod combine;
 #[cfg(feature = "clipboard")]
 pub mod copy;
 pub mod duplicate;
 pub mod edit;
+pub mod expiring;
+pub mod extract;
 pub mod generate;
 pub mod git;
 pub mod grep;
@@ -16,10 +20,20 @@ pub mod list;
 pub mod r#move;
 pub mod recipients;
 pub mod remove;
+pub mod rotate;
+pub mod set;
+pub mod share;
 pub mod show;
 pub mod slam;
+pub mod split;
+#[cfg(all(feature = "ssh", unix))]
+pub mod ssh_add;
+#[cfg(all(feature = "ssh", unix))]
+pub mod ssh_agent;
 pub mod sync;
+pub mod tag;
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 pub mod tomb;
 #[cfg(feature = "totp")]
 pub mod totp;
+pub mod unset;
@@ -4,13 +4,13 @@ use std::path::Path;
 
 use anyhow::Result;
 use clap::ArgMatches;
-use prs_lib::{Secret, Store};
+use prs_lib::{Secret, Store, crypto::shamir};
 use thiserror::Error;
 
 use crate::cmd::matcher::{MainMatcher, Matcher, r#move::MoveMatcher};
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 use crate::util::tomb;
-use crate::util::{cli, error, select, sync};
+use crate::util::{cli, error, search, select, sync};
 
 /// Move secret action.
 pub struct Move<'a> {
@@ -81,13 +81,39 @@ impl<'a> Move<'a> {
             update_alias_for_secret_to(&store, &secret, &new_secret);
         }
 
-        // Move secret
+        // Move secret, replace shares of an overwritten secret with the shares of this one
         fs::rename(&secret.path, path)
             .map(|_| ())
             .map_err(Err::Move)?;
+        if new_secret.path != secret.path {
+            for (_, share) in shamir::find_shares(&new_secret.path) {
+                fs::remove_file(share).map_err(Err::Move)?;
+            }
+            for (index, share) in shamir::find_shares(&secret.path) {
+                fs::rename(share, shamir::share_path(&new_secret.path, index))
+                    .map_err(Err::Move)?;
+            }
+        }
 
         super::remove::remove_empty_secret_dir(&secret);
 
+        // Move secret tags and search index entry along
+        let mut tags = store.tags().map_err(Err::Tags)?;
+        if tags.move_secret(&secret, &new_secret) {
+            tags.save().map_err(Err::Tags)?;
+        }
+        search::update(&store, &matcher_main, |index| {
+            Ok(index.rename(&secret, &new_secret))
+        });
+
+        // Re-encrypt if moved to a directory with other recipients
+        crate::action::housekeeping::recrypt::recrypt_moved(
+            &store,
+            &secret.path,
+            &new_secret,
+            &matcher_main,
+        )?;
+
         // Optionally create alias from old location to new location
         #[cfg(feature = "alias")]
         if matcher_move.alias() {
@@ -211,6 +237,9 @@ pub enum Err {
     #[error("failed to move secret file")]
     Move(#[source] std::io::Error),
 
+    #[error("failed to update secret tags")]
+    Tags(#[source] anyhow::Error),
+
     #[cfg(feature = "alias")]
     #[error("failed to update alias")]
     UpdateAlias(#[source] std::io::Error),
@@ -1,11 +1,23 @@
-use anyhow::Result;
+use std::fs;
+
+use anyhow::{Result, ensure};
 use clap::ArgMatches;
-use prs_lib::{Recipients, Store, crypto::prelude::*};
+use prs_lib::{
+    Key, Recipients, Store,
+    crypto::{
+        Context, groups,
+        prelude::*,
+        recipients, store,
+        util::{fingerprints_equal, is_armored_public_key},
+    },
+};
 use thiserror::Error;
 
+#[cfg(feature = "keyserver")]
+use crate::util::keyserver;
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 use crate::util::tomb;
-use crate::util::{self, error, select, style, sync};
+use crate::util::{self, error, select, stdin, style, sync};
 use crate::{
     cmd::matcher::{
         MainMatcher, Matcher,
@@ -41,8 +53,9 @@ impl<'a> Add<'a> {
         );
         let sync = store.sync();
 
-        // Disallow non-interactive mode
-        if matcher_main.no_interact() {
+        // Disallow non-interactive mode, unless key is given
+        let select = matcher_add.fingerprint().is_none() && matcher_add.key_file().is_none();
+        if matcher_main.no_interact() && select {
             quit_error_msg(
                 "cannot add recipient in non interactive mode",
                 ErrorHintsBuilder::from_matcher(&matcher_main)
@@ -68,19 +81,31 @@ impl<'a> Add<'a> {
             sync.prepare()?;
         }
 
-        let mut context = crate::crypto::context(&matcher_main)?;
-        let mut recipients = store.recipients().map_err(Err::LoadRecipients)?;
-
-        // Find unused keys, select one and add to recipients
-        let mut tmp = Recipients::from(if !matcher_add.secret() {
-            context.keys_public().map_err(Err::LoadPublic)?
-        } else {
-            context.keys_private().map_err(Err::LoadPrivate)?
-        });
-        tmp.remove_all(recipients.keys());
-        let key = select::select_key(tmp.keys(), None).ok_or(Err::NoneSelected)?;
-        recipients.add(key.clone());
-        recipients.save(&store)?;
+        // Add recipient group reference, or key
+        let group = matcher_add
+            .fingerprint()
+            .and_then(|fp| groups::reference_name(fp))
+            .map(groups::normalize);
+        let (id, recipient) = match &group {
+            Some(name) => {
+                ensure!(
+                    store::store_add_group_reference(&store, name)?,
+                    Err::AlreadyRecipient(groups::reference(name)),
+                );
+                if !matcher_main.quiet() {
+                    error::print_warning(
+                        "group references in .gpg-id files are not supported by pass and gopass, \
+                         they cannot encrypt secrets for this store",
+                    );
+                }
+                store::store_sync_public_key_files(&store, &store::store_load_all_keys(&store)?)?;
+                (groups::reference(name), groups::reference(name))
+            }
+            None => {
+                let key = add_key(&store, &matcher_main, &matcher_add, select)?;
+                (key.fingerprint(true), key.to_string())
+            }
+        };
 
         if prs_lib::store::can_decrypt(&store) {
             // Recrypt secrets
@@ -94,20 +119,126 @@ impl<'a> Add<'a> {
         }
 
         // Finalize sync
-        sync.finalize(format!("Add recipient {}", key.fingerprint(true)))?;
+        if !matcher_add.no_sync() {
+            sync.finalize(format!("Add recipient {id}"))?;
+        }
 
         // Finalize tomb
         #[cfg(all(feature = "tomb", target_os = "linux"))]
         tomb::finalize_tomb(&mut tomb, &matcher_main, true).map_err(Err::Tomb)?;
 
         if !matcher_main.quiet() {
-            eprintln!("Added recipient: {key}");
+            eprintln!("Added recipient: {recipient}");
         }
 
         Ok(())
     }
 }
 
+/// Add a key as store recipient.
+///
+/// Selects an unused key, or imports and finds the given key. Returns the added key.
+fn add_key(
+    store: &Store,
+    matcher_main: &MainMatcher,
+    matcher_add: &AddMatcher,
+    select: bool,
+) -> Result<Key> {
+    let mut context = crate::crypto::context(matcher_main)?;
+    let mut recipients = store.recipients().map_err(Err::LoadRecipients)?;
+
+    // Find unused keys and select one, or import and find given key
+    let key = if select {
+        let mut tmp = Recipients::from(keys(&mut context, matcher_add)?);
+        tmp.remove_all(recipients.keys());
+        select::select_key(tmp.keys(), None)
+            .ok_or(Err::NoneSelected)?
+            .clone()
+    } else {
+        let key = find_key(&mut context, matcher_main, matcher_add)?;
+        ensure!(
+            !recipients.has_fingerprint(&key.fingerprint(false)),
+            Err::AlreadyRecipient(key.fingerprint(true)),
+        );
+        key
+    };
+
+    // Key must be usable for encryption
+    ensure!(
+        recipients::is_usable(&mut context, &key),
+        Err::Unusable(key.fingerprint(true)),
+    );
+
+    recipients.add(key.clone());
+    recipients.save(store)?;
+
+    Ok(key)
+}
+
+/// Get public keys from keychain, or private keys if adding a secret key.
+fn keys(context: &mut Context, matcher_add: &AddMatcher) -> Result<Vec<Key>> {
+    Ok(if !matcher_add.secret() {
+        context.keys_public().map_err(Err::LoadPublic)?
+    } else {
+        context.keys_private().map_err(Err::LoadPrivate)?
+    })
+}
+
+/// Find the key to add, import it first if given as file or from keyserver.
+///
+/// The key is found by the given fingerprint. Without fingerprint, the key file must contain a
+/// single key that was not yet in the keychain.
+fn find_key(
+    context: &mut Context,
+    matcher_main: &MainMatcher,
+    matcher_add: &AddMatcher,
+) -> Result<Key> {
+    let fingerprint = matcher_add.fingerprint();
+    let before = keys(context, matcher_add)?;
+
+    // Read key from file or stdin
+    let data = match matcher_add.key_file() {
+        Some(path) if path == "-" => Some(
+            stdin::read_plaintext(!matcher_main.quiet())?
+                .unsecure_ref()
+                .to_vec(),
+        ),
+        Some(path) => Some(fs::read(path).map_err(Err::ReadKeyFile)?),
+        None => None,
+    };
+
+    // Fetch key from keyserver
+    #[cfg(feature = "keyserver")]
+    let data = match (data, matcher_add.keyserver(), fingerprint) {
+        (None, Some(keyserver), Some(fingerprint)) => {
+            Some(keyserver::fetch(keyserver, fingerprint)?)
+        }
+        (data, _, _) => data,
+    };
+
+    // Import key into keychain
+    if let Some(data) = data {
+        ensure!(is_armored_public_key(&data), Err::KeyFormat);
+        context.import_key(&data).map_err(Err::Import)?;
+    }
+    let keys = keys(context, matcher_add)?;
+
+    match fingerprint {
+        Some(fingerprint) => keys
+            .into_iter()
+            .find(|key| fingerprints_equal(key.fingerprint(false), fingerprint))
+            .ok_or_else(|| Err::UnknownKey(fingerprint.into()).into()),
+        None => {
+            let mut new: Vec<Key> = keys.into_iter().filter(|k| !before.contains(k)).collect();
+            match new.len() {
+                1 => Ok(new.remove(0)),
+                0 => Err(Err::NoNewKey.into()),
+                n => Err(Err::ManyNewKeys(n).into()),
+            }
+        }
+    }
+}
+
 /// Cannot decrypt on this machine, show recrypt hints.
 pub(crate) fn cannot_decrypt_show_recrypt_hints() {
     // TODO: only show this if adding secret key
@@ -139,6 +270,36 @@ pub enum Err {
     #[error("no key selected")]
     NoneSelected,
 
+    #[error("key '{}' not found in keychain", _0)]
+    UnknownKey(String),
+
+    #[error("key '{}' is already a recipient", _0)]
+    AlreadyRecipient(String),
+
+    #[error(
+        "key '{}' cannot be used for encryption, it may be expired or revoked",
+        _0
+    )]
+    Unusable(String),
+
+    #[error("failed to read key file")]
+    ReadKeyFile(#[source] std::io::Error),
+
+    #[error("key file must contain an ASCII armored public key")]
+    KeyFormat,
+
+    #[error("failed to import key into keychain")]
+    Import(#[source] anyhow::Error),
+
+    #[error("key file has no keys new to keychain, specify fingerprint of key to add")]
+    NoNewKey,
+
+    #[error(
+        "key file has {} keys new to keychain, specify fingerprint of key to add",
+        _0
+    )]
+    ManyNewKeys(usize),
+
     #[error("failed to load recipients from keychain")]
     LoadRecipients(#[source] anyhow::Error),
 
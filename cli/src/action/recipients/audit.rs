@@ -0,0 +1,313 @@
+use std::collections::{HashMap, hash_map::Entry};
+use std::path::PathBuf;
+
+use anyhow::Result;
+use clap::ArgMatches;
+use prs_lib::{
+    Key, Secret, Store,
+    crypto::{self, Context, prelude::*, recipients, store},
+};
+use thiserror::Error;
+
+use crate::cmd::matcher::{
+    MainMatcher, Matcher,
+    recipients::{RecipientsMatcher, audit::AuditMatcher},
+};
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+use crate::util::{
+    self, error,
+    output::{self, AuditOutput, KeyOutput, SecretOutput},
+    progress::{self, ProgressBarExt},
+    style, sync,
+};
+
+/// A recipients audit action.
+pub struct Audit<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> Audit<'a> {
+    /// Construct a new audit action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the audit action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let _matcher_recipients = RecipientsMatcher::with(self.cmd_matches).unwrap();
+        let matcher_audit = AuditMatcher::with(self.cmd_matches).unwrap();
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+        let sync = store.sync();
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        // Prepare sync when fixing
+        let fix = matcher_audit.fix();
+        if fix {
+            sync::ensure_ready(&sync, matcher_audit.allow_dirty());
+            if !matcher_audit.no_sync() {
+                sync.prepare()?;
+            }
+        }
+
+        // Aliases share the file of their target, only audit the target
+        let secrets: Vec<Secret> = store
+            .secrets(matcher_audit.query())
+            .into_iter()
+            .filter(|secret| {
+                !secret
+                    .path
+                    .symlink_metadata()
+                    .is_ok_and(|meta| meta.file_type().is_symlink())
+            })
+            .collect();
+
+        let (drifted, failed) = audit(
+            &store,
+            &secrets,
+            &matcher_main,
+            matcher_audit.jobs(&matcher_main),
+        )?;
+
+        // Report secrets with different recipients
+        if matcher_main.json() {
+            let list: Vec<AuditOutput> = drifted
+                .iter()
+                .map(|drift| AuditOutput {
+                    secret: SecretOutput::new(&store, drift.secret),
+                    missing: drift.missing.iter().map(KeyOutput::from).collect(),
+                    unexpected: drift.unexpected.clone(),
+                })
+                .collect();
+            output::print_json(&list).map_err(Err::Output)?;
+        } else if matcher_main.quiet() {
+            drifted
+                .iter()
+                .for_each(|drift| println!("{}", drift.secret.name));
+        } else {
+            let unexpected: Vec<&String> =
+                drifted.iter().flat_map(|drift| &drift.unexpected).collect();
+            let known = find_keys(&mut crate::crypto::context(&matcher_main)?, &unexpected);
+            for drift in &drifted {
+                println!("{}", drift.secret.name);
+                for key in &drift.missing {
+                    println!("    missing:    {key}");
+                }
+                for key_id in &drift.unexpected {
+                    match known.get(key_id) {
+                        Some(key) => println!("    unexpected: {key} ({key_id})"),
+                        None => println!("    unexpected: {key_id} (unknown key)"),
+                    }
+                }
+            }
+        }
+
+        // Re-encrypt secrets with different recipients
+        if fix && !drifted.is_empty() {
+            let secrets: Vec<Secret> = drifted.iter().map(|drift| drift.secret.clone()).collect();
+            crate::action::housekeeping::recrypt::recrypt(
+                &store,
+                &secrets,
+                &matcher_main,
+                matcher_audit.jobs(&matcher_main),
+            )
+            .map_err(Err::Recrypt)?;
+        }
+
+        // Finalize sync
+        if fix && !matcher_audit.no_sync() {
+            sync.finalize("Re-encrypt secrets with changed recipients")?;
+        }
+
+        // Finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, fix).map_err(Err::Tomb)?;
+
+        // Secrets that failed to audit are unknown, never report all is fine then
+        if drifted.is_empty() && failed == 0 {
+            if !matcher_main.quiet() {
+                eprintln!(
+                    "All {} secrets are encrypted for current recipients",
+                    secrets.len()
+                );
+            }
+        } else if !fix && !drifted.is_empty() {
+            if !matcher_main.quiet() && !matcher_main.json() {
+                eprintln!();
+                eprintln!(
+                    "{} of {} secrets have different recipients, use '{}' to re-encrypt them",
+                    drifted.len(),
+                    secrets.len(),
+                    style::highlight(format!("{} recipients audit --fix", util::bin_name())),
+                );
+            }
+            error::exit(1);
+        }
+
+        if failed > 0 {
+            error::exit(1);
+        }
+
+        Ok(())
+    }
+}
+
+/// A secret encrypted for different recipients than expected.
+struct Drift<'a> {
+    /// The secret.
+    secret: &'a Secret,
+
+    /// Expected recipient keys the secret is not encrypted for.
+    missing: Vec<Key>,
+
+    /// Key IDs the secret is encrypted for that are not expected.
+    unexpected: Vec<String>,
+}
+
+/// Audit recipients of the given secrets.
+///
+/// Lists actual recipient key IDs of each secret in parallel, and compares them against the
+/// recipients of the GPG IDs file that applies to the secret. Returns secrets that differ, and the
+/// number of secrets that failed to audit.
+fn audit<'a>(
+    store: &Store,
+    secrets: &'a [Secret],
+    matcher_main: &MainMatcher,
+    jobs: usize,
+) -> Result<(Vec<Drift<'a>>, usize)> {
+    let mut context = crate::crypto::context(matcher_main)?;
+
+    // Find expected key IDs for the recipients of each GPG IDs file
+    let mut key_ids: HashMap<String, Vec<String>> = HashMap::new();
+    let mut expected: HashMap<PathBuf, Vec<(Key, Vec<String>)>> = HashMap::new();
+    for secret in secrets {
+        let Entry::Vacant(entry) =
+            expected.entry(store::store_gpg_ids_file_for(store, &secret.path))
+        else {
+            continue;
+        };
+        let recipients =
+            store::store_load_recipients_for(store, &secret.path).map_err(Err::Load)?;
+        let mut keys = Vec::new();
+        for key in recipients.keys() {
+            let ids = match key_ids.entry(key.fingerprint(false)) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(key_ids_or_warn(&mut context, key)),
+            };
+            keys.push((key.clone(), ids.clone()));
+        }
+        entry.insert(keys);
+    }
+
+    // List actual key IDs of each secret, compare
+    let pb = progress::progress_bar(secrets.len() as u64, matcher_main.quiet());
+    let mut drifted = Vec::new();
+    let mut failed = 0;
+    crypto::parallel(
+        &crate::crypto::config(matcher_main),
+        jobs,
+        secrets,
+        |context, secret| context.recipient_key_ids_file(&secret.path),
+        |secret, result| {
+            pb.set_message_trunc(&secret.name);
+            pb.inc(1);
+
+            let actual = match result {
+                Ok(actual) => actual,
+                Err(err) => {
+                    error::print_error(err.context(format!("failed to audit: {}", secret.name)));
+                    failed += 1;
+                    return;
+                }
+            };
+            let keys = &expected[&store::store_gpg_ids_file_for(store, &secret.path)];
+            let missing: Vec<Key> = keys
+                .iter()
+                .filter(|(_, ids)| !ids.iter().any(|id| actual.contains(id)))
+                .map(|(key, _)| key.clone())
+                .collect();
+            let unexpected: Vec<String> = actual
+                .into_iter()
+                .filter(|id| !keys.iter().any(|(_, ids)| ids.contains(id)))
+                .collect();
+            if !missing.is_empty() || !unexpected.is_empty() {
+                drifted.push(Drift {
+                    secret,
+                    missing,
+                    unexpected,
+                });
+            }
+        },
+    );
+    pb.finish_and_clear();
+
+    if failed > 0 {
+        error::print_error_msg(format!("Failed to audit {failed} secrets"));
+    }
+
+    drifted.sort_by(|a, b| a.secret.name.cmp(&b.secret.name));
+    Ok((drifted, failed))
+}
+
+/// Get the key IDs ciphertext for the given key is encrypted for.
+///
+/// Shows a warning and returns no key IDs if the key is not usable.
+fn key_ids_or_warn(context: &mut Context, key: &Key) -> Vec<String> {
+    recipients::key_ids(context, key).unwrap_or_else(|err| {
+        error::print_error(err.context(format!(
+            "recipient '{}' is not usable, it may be expired or revoked",
+            key.fingerprint(true),
+        )));
+        vec![]
+    })
+}
+
+/// Find keychain keys for the given recipient key IDs.
+///
+/// Key IDs are usually of subkeys, these are matched against the key IDs of each key and its
+/// subkeys as listed in the keychain. Key IDs no key is found for are skipped.
+fn find_keys(context: &mut Context, key_ids: &[&String]) -> HashMap<String, Key> {
+    let mut found = HashMap::new();
+    if key_ids.is_empty() {
+        return found;
+    }
+    for key in context.keys_public().unwrap_or_default() {
+        for id in &key.meta().key_ids {
+            if key_ids.contains(&id) {
+                found.insert(id.clone(), key.clone());
+            }
+        }
+    }
+    found
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("failed to load store recipients")]
+    Load(#[source] anyhow::Error),
+
+    #[error("failed to print audit results as JSON")]
+    Output(#[source] std::io::Error),
+
+    #[error("failed to re-encrypt secrets in store")]
+    Recrypt(#[source] anyhow::Error),
+}
@@ -3,7 +3,7 @@ use std::io::Write;
 
 use anyhow::Result;
 use clap::ArgMatches;
-use prs_lib::{Plaintext, Store, crypto::prelude::*};
+use prs_lib::{Plaintext, Store, crypto::prelude::*, crypto::util::fingerprints_equal};
 use thiserror::Error;
 
 #[cfg(feature = "clipboard")]
@@ -46,8 +46,8 @@ impl<'a> Export<'a> {
         );
         let recipients = store.recipients().map_err(Err::Load)?;
 
-        // Disallow non-interactive mode
-        if matcher_main.no_interact() {
+        // Disallow non-interactive mode, unless recipient is given
+        if matcher_main.no_interact() && matcher_export.fingerprint().is_none() {
             quit_error_msg(
                 "cannot export recipient in non interactive mode",
                 ErrorHintsBuilder::from_matcher(&matcher_main)
@@ -67,9 +67,15 @@ impl<'a> Export<'a> {
         #[cfg(all(feature = "tomb", target_os = "linux"))]
         tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
 
-        let key = select::select_key(recipients.keys(), None)
-            .ok_or(Err::NoneSelected)?
-            .clone();
+        let key = match matcher_export.fingerprint() {
+            Some(fingerprint) => recipients
+                .keys()
+                .iter()
+                .find(|key| fingerprints_equal(key.fingerprint(false), fingerprint))
+                .ok_or_else(|| Err::UnknownRecipient(fingerprint.into()))?,
+            None => select::select_key(recipients.keys(), None).ok_or(Err::NoneSelected)?,
+        }
+        .clone();
 
         // Export public key
         let data = Plaintext::from(crate::crypto::context(&matcher_main)?.export_key(key)?);
@@ -122,6 +128,9 @@ pub enum Err {
     #[error("no key selected")]
     NoneSelected,
 
+    #[error("recipient '{}' is not in store", _0)]
+    UnknownRecipient(String),
+
     #[error("failed to write key to file")]
     Output(#[source] std::io::Error),
 
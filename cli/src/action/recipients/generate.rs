@@ -1,6 +1,12 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+
 use anyhow::Result;
 use clap::ArgMatches;
-use prs_lib::{Recipients, Store, crypto::prelude::*};
+use prs_lib::{Plaintext, Store, crypto::prelude::*};
 use thiserror::Error;
 
 use crate::cmd::matcher::{
@@ -12,15 +18,9 @@ use crate::util::tomb;
 use crate::util::{
     self, cli,
     error::{self, ErrorHintsBuilder},
-    style, sync,
+    style, sync, time,
 };
 
-/// Binary name.
-#[cfg(not(windows))]
-const BIN_NAME: &str = "gpg";
-#[cfg(windows)]
-const BIN_NAME: &str = "gpg.exe";
-
 /// A recipients generate action.
 pub struct Generate<'a> {
     cmd_matches: &'a ArgMatches,
@@ -58,20 +58,44 @@ impl<'a> Generate<'a> {
             sync.prepare()?;
         }
 
-        // Generating recipient in no-interact mode is not supported
-        if matcher_main.no_interact() && !matcher_main.force() {
+        // Backends that cannot keep the secret key require a file to write it to
+        let mut context = crate::crypto::context(&matcher_main)?;
+        let keeps_secret_key = context.keeps_secret_keys();
+        let secret_key_file = matcher_generate.secret_key_file();
+        if !keeps_secret_key && secret_key_file.is_none() {
             error::quit_error_msg(
-                "generating recipient with --no-interact is not supported",
+                "crypto backend cannot keep the secret key, a file to write it to is required",
                 ErrorHintsBuilder::from_matcher(&matcher_main)
-                    .force(true)
+                    .add_info(format!(
+                        "Use '{}' to specify it",
+                        style::highlight("--secret-key-file <FILE>"),
+                    ))
                     .build()
                     .unwrap(),
-            )
+            );
         }
 
-        // Show warning to user
+        // Build user ID, prompt for missing details
+        let user_id = user_id(&matcher_main, &matcher_generate);
+        let expire = matcher_generate.expire();
+
+        // Show key details to user
         if !matcher_main.force() {
-            eprintln!("This will start a key pair generation wizard through 'gpg'");
+            match expire {
+                Some(expire) => eprintln!(
+                    "This will generate an Ed25519/Cv25519 key pair for '{user_id}', expiring in {}",
+                    time::format_duration(expire.as_secs() as u32),
+                ),
+                None => eprintln!(
+                    "This will generate an Ed25519/Cv25519 key pair for '{user_id}', never expiring"
+                ),
+            }
+            if let Some(path) = secret_key_file.filter(|_| !keeps_secret_key) {
+                eprintln!(
+                    "The secret key will be written to '{}', the key will not be added to the store",
+                    path.display(),
+                );
+            }
             if !cli::prompt_yes("Continue?", Some(true), &matcher_main) {
                 if matcher_main.verbose() {
                     eprintln!("Generation cancelled");
@@ -80,29 +104,48 @@ impl<'a> Generate<'a> {
             }
         }
 
-        // Generate new key through GPG
-        let new = gpg_generate(&matcher_main)?;
-        let new_keys = new.keys();
-
-        if !matcher_generate.no_add() {
-            if new.keys().is_empty() {
-                error::quit_error_msg(
-                    "not adding recipient to store because no new keys are found",
-                    ErrorHintsBuilder::from_matcher(&matcher_main)
-                        .add_info(format!(
-                            "Use '{}' to add a recipient",
-                            style::highlight("prs recipients add")
-                        ))
-                        .build()
-                        .unwrap(),
-                );
-            }
+        // Generate new key, store secret key if the backend does not keep it
+        let passphrase = if keeps_secret_key {
+            None
+        } else {
+            Some(prompt_passphrase(&matcher_main)?)
+        };
+        let generated = context
+            .generate_key(&user_id, expire, passphrase)
+            .map_err(Err::Generate)?;
+        if let (Some(secret_key), Some(path)) = (&generated.secret_key, secret_key_file) {
+            write_secret_key(path, secret_key.unsecure_ref())
+                .map_err(|err| Err::WriteSecretKey(path.clone(), err))?;
+            error::print_warning(format!(
+                "secret key is not kept by the crypto backend, it was written to: {}",
+                path.display(),
+            ));
+            ErrorHintsBuilder::from_matcher(&matcher_main)
+                .add_info(format!(
+                    "move it to your OpenPGP card or keychain and remove the file, then add it to the store with '{}'",
+                    style::highlight(format!(
+                        "{} recipients add {}",
+                        util::bin_name(),
+                        generated.key.fingerprint(false),
+                    )),
+                ))
+                .verbose(false)
+                .help(false)
+                .build()
+                .unwrap()
+                .print(false);
+        }
+        let key = generated.key;
 
-            // Add new keys to store
-            let mut recipients = store.recipients().map_err(Err::Load)?;
-            for key in new_keys {
-                recipients.add(key.clone());
+        // A secret key that is not kept cannot decrypt yet, do not add it as recipient
+        if matcher_generate.no_add() || !keeps_secret_key {
+            if !matcher_main.quiet() {
+                eprintln!("Generated key: {key}");
             }
+        } else {
+            // Add new key to store
+            let mut recipients = store.recipients().map_err(Err::Load)?;
+            recipients.add(key.clone());
             recipients.save(&store)?;
 
             if prs_lib::store::can_decrypt(&store) {
@@ -120,18 +163,12 @@ impl<'a> Generate<'a> {
             if !matcher_generate.no_sync() {
                 sync.finalize(format!(
                     "Generate and add recipient {}",
-                    new_keys
-                        .iter()
-                        .map(|k| k.fingerprint(true))
-                        .collect::<Vec<_>>()
-                        .join(", "),
+                    key.fingerprint(true)
                 ))?;
             }
 
             if !matcher_main.quiet() {
-                for key in new_keys {
-                    eprintln!("Added recipient: {key}");
-                }
+                eprintln!("Added recipient: {key}");
             }
         }
 
@@ -143,32 +180,70 @@ impl<'a> Generate<'a> {
     }
 }
 
-/// Invoke GPG generate command.
+/// Build the user ID for the new key from its name and email address.
 ///
-/// Return new keys as recipients.
-pub fn gpg_generate(matcher_main: &MainMatcher) -> Result<Recipients> {
-    // List recipients before
-    let mut context = crate::crypto::context(matcher_main)?;
-    let before = Recipients::from(context.keys_private()?);
-
-    // Generate key through GPG
-    if !matcher_main.quiet() {
-        eprintln!("===== GPG START =====");
+/// Prompts for a name and email address if not given, unless in no-interact mode.
+fn user_id(matcher_main: &MainMatcher, matcher_generate: &GenerateMatcher) -> String {
+    let prompt = |value: Option<&String>, msg| match value {
+        Some(value) => value.trim().to_string(),
+        None if !matcher_main.no_interact() => cli::prompt(msg, matcher_main),
+        None => String::new(),
+    };
+    let name = prompt(matcher_generate.name(), "Name");
+    let email = prompt(matcher_generate.email(), "Email");
+
+    match (name.is_empty(), email.is_empty()) {
+        (false, false) => format!("{name} <{email}>"),
+        (false, true) => name,
+        (true, false) => format!("<{email}>"),
+        (true, true) => error::quit_error_msg(
+            "cannot generate key pair without name or email",
+            ErrorHintsBuilder::from_matcher(matcher_main)
+                .add_info(format!(
+                    "Use '{}' or '{}' to specify them",
+                    style::highlight("--name"),
+                    style::highlight("--email"),
+                ))
+                .build()
+                .unwrap(),
+        ),
+    }
+}
+
+/// Prompt for a passphrase to protect the generated secret key with.
+fn prompt_passphrase(matcher_main: &MainMatcher) -> Result<Plaintext> {
+    if matcher_main.no_interact() {
+        error::quit_error_msg(
+            "secret key passphrase required, cannot prompt in non-interactive mode",
+            ErrorHintsBuilder::from_matcher(matcher_main)
+                .build()
+                .unwrap(),
+        );
+    }
+
+    let passphrase: Plaintext = rpassword::prompt_password("Secret key passphrase: ")
+        .map_err(Err::Passphrase)?
+        .into();
+    if passphrase.is_empty() {
+        return Err(Err::EmptyPassphrase.into());
     }
-    util::invoke_cmd(
-        &format!("{BIN_NAME} --full-generate-key"),
-        None,
-        matcher_main.verbose(),
-    )
-    .map_err(Err::Invoke)?;
-    if !matcher_main.quiet() {
-        eprintln!("===== GPG END =====");
+    let confirm: Plaintext = rpassword::prompt_password("Confirm secret key passphrase: ")
+        .map_err(Err::Passphrase)?
+        .into();
+    if passphrase != confirm {
+        return Err(Err::PassphraseMismatch.into());
     }
 
-    // List recipients after, keep new keys
-    let mut diff = Recipients::from(context.keys_private()?);
-    diff.remove_all(before.keys());
-    Ok(diff)
+    Ok(passphrase)
+}
+
+/// Write secret key to a new file, only readable by the current user.
+fn write_secret_key(path: &Path, secret_key: &[u8]) -> std::io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    options.open(path)?.write_all(secret_key)
 }
 
 #[derive(Debug, Error)]
@@ -183,8 +258,20 @@ pub enum Err {
     #[error("failed to load recipients from keychain")]
     Load(#[source] anyhow::Error),
 
-    #[error("failed to invoke gpg command")]
-    Invoke(#[source] std::io::Error),
+    #[error("failed to generate key pair")]
+    Generate(#[source] anyhow::Error),
+
+    #[error("failed to read secret key passphrase")]
+    Passphrase(#[source] std::io::Error),
+
+    #[error("secret key passphrase must not be empty")]
+    EmptyPassphrase,
+
+    #[error("secret key passphrases do not match")]
+    PassphraseMismatch,
+
+    #[error("failed to write secret key to file: {}", _0.display())]
+    WriteSecretKey(PathBuf, #[source] std::io::Error),
 
     #[error("failed to re-encrypt secrets in store")]
     Recrypt(#[source] anyhow::Error),
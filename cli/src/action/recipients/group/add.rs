@@ -0,0 +1,144 @@
+use anyhow::{Result, ensure};
+use clap::ArgMatches;
+use prs_lib::{
+    Key, Store,
+    crypto::{
+        groups::{self, Groups},
+        prelude::*,
+        recipients,
+    },
+};
+use thiserror::Error;
+
+use crate::cmd::matcher::{
+    MainMatcher, Matcher,
+    recipients::group::{GroupMatcher, add::AddMatcher},
+};
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+use crate::util::{error, sync};
+
+/// A recipient group add action.
+pub struct Add<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> Add<'a> {
+    /// Construct a new add action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the add action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let _matcher_group = GroupMatcher::with(self.cmd_matches).unwrap();
+        let matcher_add = AddMatcher::with(self.cmd_matches).unwrap();
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+        let sync = store.sync();
+        let name = groups::normalize(matcher_add.group());
+        groups::validate(&name)?;
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        // Prepare sync
+        sync::ensure_ready(&sync, matcher_add.allow_dirty());
+        if !matcher_add.no_sync() {
+            sync.prepare()?;
+        }
+
+        // Find keys, each must be usable for encryption
+        let mut context = crate::crypto::context(&matcher_main)?;
+        let mut groups = Groups::load(&store).map_err(Err::Load)?;
+        let mut added: Vec<Key> = Vec::new();
+        for fingerprint in matcher_add.fingerprints() {
+            let key = context
+                .get_public_key(fingerprint)
+                .map_err(|_| Err::UnknownKey(fingerprint.into()))?;
+            ensure!(
+                recipients::is_usable(&mut context, &key),
+                Err::Unusable(key.fingerprint(true)),
+            );
+            if groups.add(&name, &key.fingerprint(false))? {
+                added.push(key);
+            } else if !matcher_main.quiet() {
+                error::print_warning(format!(
+                    "key '{}' is already a member of group, skipping",
+                    key.fingerprint(true),
+                ));
+            }
+        }
+        if added.is_empty() {
+            if !matcher_main.quiet() {
+                eprintln!("No members added");
+            }
+            error::quit();
+        }
+        groups.save().map_err(Err::Save)?;
+
+        super::update_store(&store, &matcher_main, &name, !matcher_add.no_recrypt())
+            .map_err(Err::Update)?;
+
+        // Finalize sync
+        if !matcher_add.no_sync() {
+            let members = match added.as_slice() {
+                [key] => key.fingerprint(true),
+                keys => format!("{} members", keys.len()),
+            };
+            sync.finalize(format!(
+                "Add {members} to recipient group {}",
+                groups::reference(&name),
+            ))?;
+        }
+
+        // Finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, true).map_err(Err::Tomb)?;
+
+        if !matcher_main.quiet() {
+            for key in added {
+                eprintln!("Added to {}: {key}", groups::reference(&name));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("failed to load recipient groups")]
+    Load(#[source] anyhow::Error),
+
+    #[error("failed to save recipient groups")]
+    Save(#[source] anyhow::Error),
+
+    #[error("key '{}' not found in keychain", _0)]
+    UnknownKey(String),
+
+    #[error(
+        "key '{}' cannot be used for encryption, it may be expired or revoked",
+        _0
+    )]
+    Unusable(String),
+
+    #[error("failed to update store for changed recipient group")]
+    Update(#[source] anyhow::Error),
+}
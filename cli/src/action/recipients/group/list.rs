@@ -0,0 +1,169 @@
+use anyhow::Result;
+use clap::ArgMatches;
+use prs_lib::{
+    Key, Store,
+    crypto::{
+        Proto,
+        groups::{self, Groups},
+        prelude::*,
+        store,
+    },
+};
+use thiserror::Error;
+
+use crate::action::recipients;
+use crate::cmd::matcher::{
+    MainMatcher, Matcher,
+    recipients::group::{GroupMatcher, list::ListMatcher},
+};
+use crate::util::output::{self, GroupOutput, KeyOutput};
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+
+/// A recipient group list action.
+pub struct List<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> List<'a> {
+    /// Construct a new list action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the list action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let _matcher_group = GroupMatcher::with(self.cmd_matches).unwrap();
+        let matcher_list = ListMatcher::with(self.cmd_matches).unwrap();
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        let groups = Groups::load(&store).map_err(Err::Load)?;
+        let names: Vec<String> = match matcher_list.group() {
+            Some(name) => {
+                let name = groups::normalize(name);
+                if groups.members(&name).is_none() {
+                    return Err(groups::Err::UnknownGroup(name).into());
+                }
+                vec![name]
+            }
+            None => groups.names().map(|name| name.to_string()).collect(),
+        };
+
+        // Collect members and GPG IDs files using each group
+        let mut context = crate::crypto::context(&matcher_main)?;
+        let mut list = Vec::new();
+        for name in names {
+            let members = groups
+                .members(&name)
+                .unwrap_or_default()
+                .iter()
+                .map(|fingerprint| context.get_public_key(fingerprint).map_err(|_| fingerprint))
+                .collect::<Vec<_>>();
+            let used_by: Vec<String> = store::store_gpg_ids_files_with_group(&store, &name)
+                .map_err(Err::Load)?
+                .iter()
+                .map(|path| {
+                    path.strip_prefix(&store.root)
+                        .unwrap_or(path)
+                        .display()
+                        .to_string()
+                })
+                .collect();
+            list.push((name, members, used_by));
+        }
+
+        if matcher_main.json() {
+            let list: Vec<GroupOutput> = list
+                .into_iter()
+                .map(|(name, members, used_by)| GroupOutput {
+                    name,
+                    members: members
+                        .iter()
+                        .map(|member| match member {
+                            Ok(key) => KeyOutput::from(key),
+                            Err(fingerprint) => {
+                                KeyOutput::missing(Proto::Gpg, fingerprint.to_string())
+                            }
+                        })
+                        .collect(),
+                    used_by,
+                })
+                .collect();
+            output::print_json(&list).map_err(Err::Output)?;
+        } else if matcher_main.quiet() {
+            // Print group names, or member fingerprints when listing a single group
+            for (name, members, _) in list {
+                if matcher_list.group().is_none() {
+                    println!("{}", groups::reference(&name));
+                    continue;
+                }
+                members
+                    .iter()
+                    .map(|member| match member {
+                        Ok(key) => key.fingerprint(false),
+                        Err(fingerprint) => fingerprint.to_string(),
+                    })
+                    .for_each(|fingerprint| println!("{fingerprint}"));
+            }
+        } else {
+            for (i, (name, members, used_by)) in list.into_iter().enumerate() {
+                if i > 0 {
+                    println!();
+                }
+                match used_by.as_slice() {
+                    [] => println!("{} (unused)", groups::reference(&name)),
+                    files => println!(
+                        "{} (used by {})",
+                        groups::reference(&name),
+                        files.join(", ")
+                    ),
+                }
+                members
+                    .iter()
+                    .map(|member| match member {
+                        Ok(key) => match recipients::key_flag(key) {
+                            Some(flag) => format!("{key} ({flag})"),
+                            None => Key::to_string(key),
+                        },
+                        Err(fingerprint) => format!("{fingerprint} (not in keychain)"),
+                    })
+                    .for_each(|member| println!("    {member}"));
+            }
+        }
+
+        // Finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, false).map_err(Err::Tomb)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("failed to load recipient groups")]
+    Load(#[source] anyhow::Error),
+
+    #[error("failed to print recipient groups as JSON")]
+    Output(#[source] std::io::Error),
+}
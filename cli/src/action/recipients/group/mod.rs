@@ -0,0 +1,89 @@
+pub mod add;
+pub mod list;
+pub mod remove;
+
+use anyhow::Result;
+use clap::ArgMatches;
+use prs_lib::{Store, crypto::store};
+
+use crate::cmd::arg::ArgJobs;
+use crate::cmd::matcher::{MainMatcher, Matcher, recipients::group::GroupMatcher};
+use crate::util::{self, error, style};
+
+/// A recipient group action.
+pub struct Group<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> Group<'a> {
+    /// Construct a new group action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the group action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matcher
+        let matcher_group = GroupMatcher::with(self.cmd_matches).unwrap();
+
+        if matcher_group.cmd_add().is_some() {
+            return add::Add::new(self.cmd_matches).invoke();
+        }
+
+        if matcher_group.cmd_list().is_some() {
+            return list::List::new(self.cmd_matches).invoke();
+        }
+
+        if matcher_group.cmd_remove().is_some() {
+            return remove::Remove::new(self.cmd_matches).invoke();
+        }
+
+        // Unreachable, clap will print help for missing sub command instead
+        unreachable!()
+    }
+}
+
+/// Update the store after changing members of the given group.
+///
+/// Syncs the public key files in the store, and re-encrypts the secrets of the group unless
+/// `recrypt` is `false`.
+fn update_store(
+    store: &Store,
+    matcher_main: &MainMatcher,
+    name: &str,
+    recrypt: bool,
+) -> Result<()> {
+    store::store_sync_public_key_files(store, &store::store_load_all_keys(store)?)?;
+
+    let secrets = store::store_secrets_with_group(store, name)?;
+    if store::store_gpg_ids_files_with_group(store, name)?.is_empty() {
+        if !matcher_main.quiet() {
+            error::print_warning(format!(
+                "recipient group is not used, add it to the store with '{}'",
+                style::highlight(format!(
+                    "{} recipients add {}",
+                    util::bin_name(),
+                    prs_lib::crypto::groups::reference(name),
+                )),
+            ));
+        }
+        return Ok(());
+    }
+    if !recrypt || secrets.is_empty() {
+        return Ok(());
+    }
+
+    if prs_lib::store::can_decrypt(store) {
+        crate::action::housekeeping::recrypt::recrypt_changed(
+            store,
+            &secrets,
+            matcher_main,
+            ArgJobs::default_jobs(matcher_main),
+        )
+    } else {
+        if !matcher_main.quiet() {
+            super::add::cannot_decrypt_show_recrypt_hints();
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,157 @@
+use anyhow::Result;
+use clap::ArgMatches;
+use prs_lib::{
+    Store,
+    crypto::{
+        groups::{self, Groups},
+        store,
+        util::fingerprints_equal,
+    },
+};
+use thiserror::Error;
+
+use crate::cmd::matcher::{
+    MainMatcher, Matcher,
+    recipients::group::{GroupMatcher, remove::RemoveMatcher},
+};
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+use crate::util::{
+    self,
+    error::{self, ErrorHintsBuilder},
+    style, sync,
+};
+
+/// A recipient group remove action.
+pub struct Remove<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> Remove<'a> {
+    /// Construct a new remove action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the remove action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let _matcher_group = GroupMatcher::with(self.cmd_matches).unwrap();
+        let matcher_remove = RemoveMatcher::with(self.cmd_matches).unwrap();
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+        let sync = store.sync();
+        let name = groups::normalize(matcher_remove.group());
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        // Prepare sync
+        sync::ensure_ready(&sync, matcher_remove.allow_dirty());
+        if !matcher_remove.no_sync() {
+            sync.prepare()?;
+        }
+
+        // Find members to remove
+        let mut groups = Groups::load(&store).map_err(Err::Load)?;
+        let members = groups
+            .members(&name)
+            .ok_or_else(|| groups::Err::UnknownGroup(name.clone()))?;
+        let removed = matcher_remove
+            .fingerprints()
+            .into_iter()
+            .map(|fingerprint| {
+                members
+                    .iter()
+                    .find(|member| fingerprints_equal(member, fingerprint))
+                    .cloned()
+                    .ok_or_else(|| Err::NotMember(fingerprint.into()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Do not allow emptying a group that is still used
+        let gpg_ids_files = store::store_gpg_ids_files_with_group(&store, &name)?;
+        if removed.len() >= members.len() && !gpg_ids_files.is_empty() {
+            error::print_error_msg(format!(
+                "cannot remove all members from recipient group {}, it is still used",
+                groups::reference(&name),
+            ));
+            ErrorHintsBuilder::from_matcher(&matcher_main)
+                .add_info(format!(
+                    "remove it from the store first with '{}'",
+                    style::highlight(format!(
+                        "{} recipients remove {}",
+                        util::bin_name(),
+                        groups::reference(&name),
+                    )),
+                ))
+                .verbose(false)
+                .build()
+                .unwrap()
+                .print(false);
+            error::quit();
+        }
+
+        for fingerprint in &removed {
+            groups.remove(&name, fingerprint);
+        }
+        groups.save().map_err(Err::Save)?;
+
+        super::update_store(&store, &matcher_main, &name, !matcher_remove.no_recrypt())
+            .map_err(Err::Update)?;
+
+        // Finalize sync
+        if !matcher_remove.no_sync() {
+            let members = match removed.as_slice() {
+                [fingerprint] => fingerprint.clone(),
+                fingerprints => format!("{} members", fingerprints.len()),
+            };
+            sync.finalize(format!(
+                "Remove {members} from recipient group {}",
+                groups::reference(&name),
+            ))?;
+        }
+
+        // Finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, true).map_err(Err::Tomb)?;
+
+        if !matcher_main.quiet() {
+            for fingerprint in removed {
+                eprintln!("Removed from {}: {fingerprint}", groups::reference(&name));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("failed to load recipient groups")]
+    Load(#[source] anyhow::Error),
+
+    #[error("failed to save recipient groups")]
+    Save(#[source] anyhow::Error),
+
+    #[error("key '{}' is not a member of recipient group", _0)]
+    NotMember(String),
+
+    #[error("failed to update store for changed recipient group")]
+    Update(#[source] anyhow::Error),
+}
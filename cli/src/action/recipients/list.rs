@@ -6,6 +6,10 @@ use thiserror::Error;
 use crate::cmd::matcher::{MainMatcher, Matcher, recipients::RecipientsMatcher};
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 use crate::util::tomb;
+use crate::util::{
+    error,
+    output::{self, KeyOutput},
+};
 
 /// A recipients list action.
 pub struct List<'a> {
@@ -37,17 +41,34 @@ impl<'a> List<'a> {
         #[cfg(all(feature = "tomb", target_os = "linux"))]
         tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
 
-        recipients
-            .keys()
-            .iter()
-            .map(|key| {
-                if !matcher_main.quiet() {
-                    key.to_string()
-                } else {
-                    key.fingerprint(false)
+        if matcher_main.json() {
+            let keys: Vec<KeyOutput> = recipients.keys().iter().map(KeyOutput::from).collect();
+            output::print_json(&keys).map_err(Err::Output)?;
+        } else if matcher_main.quiet() {
+            recipients
+                .keys()
+                .iter()
+                .for_each(|key| println!("{}", key.fingerprint(false)));
+        } else {
+            for key in recipients.keys() {
+                match super::key_flag(key) {
+                    Some(flag) => println!("{key} ({flag})"),
+                    None => println!("{key}"),
                 }
-            })
-            .for_each(|key| println!("{key}"));
+            }
+
+            // Warn about recipients that cannot be encrypted for
+            let unusable = recipients
+                .keys()
+                .iter()
+                .filter(|key| !key.meta().status().is_usable())
+                .count();
+            if unusable > 0 {
+                error::print_warning(format!(
+                    "{unusable} recipient key(s) cannot be used for encryption, renew or remove them"
+                ));
+            }
+        }
 
         // Finalize tomb
         #[cfg(all(feature = "tomb", target_os = "linux"))]
@@ -68,4 +89,7 @@ pub enum Err {
 
     #[error("failed to list store recipients")]
     List(#[source] anyhow::Error),
+
+    #[error("failed to print recipients as JSON")]
+    Output(#[source] std::io::Error),
 }
@@ -1,11 +1,19 @@
 pub mod add;
+pub mod audit;
 pub mod export;
 pub mod generate;
+pub mod group;
 pub mod list;
 pub mod remove;
+pub mod rotate;
+pub mod trust;
+
+use std::time::{Duration, SystemTime};
 
 use anyhow::Result;
+use chrono::{DateTime, Local};
 use clap::ArgMatches;
+use prs_lib::{Key, crypto::KeyStatus};
 
 use crate::cmd::matcher::{Matcher, RecipientsMatcher};
 
@@ -29,6 +37,10 @@ impl<'a> Recipients<'a> {
             return add::Add::new(self.cmd_matches).invoke();
         }
 
+        if matcher_recipients.cmd_audit().is_some() {
+            return audit::Audit::new(self.cmd_matches).invoke();
+        }
+
         if matcher_recipients.cmd_export().is_some() {
             return export::Export::new(self.cmd_matches).invoke();
         }
@@ -37,6 +49,10 @@ impl<'a> Recipients<'a> {
             return generate::Generate::new(self.cmd_matches).invoke();
         }
 
+        if matcher_recipients.cmd_group().is_some() {
+            return group::Group::new(self.cmd_matches).invoke();
+        }
+
         if matcher_recipients.cmd_list().is_some() {
             return list::List::new(self.cmd_matches).invoke();
         }
@@ -45,7 +61,47 @@ impl<'a> Recipients<'a> {
             return remove::Remove::new(self.cmd_matches).invoke();
         }
 
+        if matcher_recipients.cmd_rotate().is_some() {
+            return rotate::Rotate::new(self.cmd_matches).invoke();
+        }
+
+        if matcher_recipients.cmd_trust().is_some() {
+            return trust::Trust::new(self.cmd_matches).invoke();
+        }
+
         // Unreachable, clap will print help for missing sub command instead
         unreachable!()
     }
 }
+
+/// Flag recipient keys that expire within this duration.
+pub const KEY_EXPIRING_WITHIN: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Get the status name of a recipient key.
+///
+/// Usable keys that expire soon are `expiring`.
+pub fn key_status(key: &Key) -> &'static str {
+    let meta = key.meta();
+    match meta.status() {
+        KeyStatus::Usable if meta.expires_within(KEY_EXPIRING_WITHIN) => "expiring",
+        status => status.name(),
+    }
+}
+
+/// Describe why a recipient key needs attention, such as it being expired.
+///
+/// Returns `None` if the key is usable and does not expire soon.
+pub fn key_flag(key: &Key) -> Option<String> {
+    let meta = key.meta();
+    let date = |time: SystemTime| DateTime::<Local>::from(time).format("%Y-%m-%d");
+    match (meta.status(), meta.expires) {
+        (KeyStatus::Revoked, _) => Some("revoked".into()),
+        (KeyStatus::Expired, Some(expires)) => Some(format!("expired on {}", date(expires))),
+        (KeyStatus::Expired, None) => Some("expired".into()),
+        (KeyStatus::NoEncrypt, _) => Some("no usable encryption key".into()),
+        (KeyStatus::Usable, Some(expires)) if meta.expires_within(KEY_EXPIRING_WITHIN) => {
+            Some(format!("expires on {}", date(expires)))
+        }
+        (KeyStatus::Usable, _) => None,
+    }
+}
@@ -1,11 +1,19 @@
-use anyhow::Result;
+use anyhow::{Result, ensure};
 use clap::ArgMatches;
-use prs_lib::{Store, crypto::prelude::*};
+use prs_lib::{
+    Key, Store,
+    crypto::{
+        groups::{self, Groups},
+        prelude::*,
+        store,
+        util::fingerprints_equal,
+    },
+};
 use thiserror::Error;
 
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 use crate::util::tomb;
-use crate::util::{cli, error, select, sync};
+use crate::util::{self, cli, error, select, style, sync};
 use crate::{
     cmd::matcher::{
         MainMatcher, Matcher,
@@ -41,8 +49,8 @@ impl<'a> Remove<'a> {
         );
         let sync = store.sync();
 
-        // Disallow non-interactive mode
-        if matcher_main.no_interact() {
+        // Disallow non-interactive mode, unless recipient is given
+        if matcher_main.no_interact() && matcher_remove.fingerprint().is_none() {
             quit_error_msg(
                 "cannot remove recipient in non interactive mode",
                 ErrorHintsBuilder::from_matcher(&matcher_main)
@@ -68,44 +76,21 @@ impl<'a> Remove<'a> {
             sync.prepare()?;
         }
 
-        let mut recipients = store.recipients().map_err(Err::Load)?;
-
-        // Select key to remove
-        let key = select::select_key(recipients.keys(), None)
-            .ok_or(Err::NoneSelected)?
-            .clone();
-
-        // Do not allow removing last recipient unless forcing
-        if recipients.keys().len() == 1 && !matcher_main.force() {
-            error::print_error_msg(
-                "cannot remove last recipient from store, you will permanently loose access to it",
-            );
-            error::ErrorHintsBuilder::from_matcher(&matcher_main)
-                .force(true)
-                .verbose(false)
-                .build()
-                .unwrap()
-                .print(false);
-            error::quit();
-        }
-
-        // Confirm removal
-        if !matcher_main.force() {
-            eprintln!("{key}");
-            if !cli::prompt_yes(
-                &format!("Remove '{}'?", key.fingerprint(true),),
-                Some(true),
-                &matcher_main,
-            ) {
-                if matcher_main.verbose() {
-                    eprintln!("Removal cancelled");
-                }
-                error::quit();
+        // Remove recipient group reference, or key
+        let group = matcher_remove
+            .fingerprint()
+            .and_then(|fp| groups::reference_name(fp))
+            .map(groups::normalize);
+        let (id, recipient) = match &group {
+            Some(name) => {
+                remove_group(&store, &matcher_main, name)?;
+                (groups::reference(name), groups::reference(name))
             }
-        }
-
-        recipients.remove(&key);
-        recipients.save(&store)?;
+            None => {
+                let key = remove_key(&store, &matcher_main, &matcher_remove)?;
+                (key.fingerprint(true), key.to_string())
+            }
+        };
 
         // Recrypt secrets
         if matcher_remove.recrypt() {
@@ -115,7 +100,7 @@ impl<'a> Remove<'a> {
 
         // Finalize sync
         if !matcher_remove.no_sync() {
-            sync.finalize(format!("Remove recipient {}", key.fingerprint(true)))?;
+            sync.finalize(format!("Remove recipient {id}"))?;
         }
 
         // Finalize tomb
@@ -123,13 +108,130 @@ impl<'a> Remove<'a> {
         tomb::finalize_tomb(&mut tomb, &matcher_main, true).map_err(Err::Tomb)?;
 
         if !matcher_main.quiet() {
-            eprintln!("Removed recipient: {key}");
+            eprintln!("Removed recipient: {recipient}");
         }
 
         Ok(())
     }
 }
 
+/// Remove a key as store recipient.
+///
+/// Selects a recipient key, or finds the given key. Returns the removed key.
+fn remove_key(
+    store: &Store,
+    matcher_main: &MainMatcher,
+    matcher_remove: &RemoveMatcher,
+) -> Result<Key> {
+    let mut recipients = store.recipients().map_err(Err::Load)?;
+
+    // Select key to remove
+    let key = match matcher_remove.fingerprint() {
+        Some(fingerprint) => recipients
+            .keys()
+            .iter()
+            .find(|key| fingerprints_equal(key.fingerprint(false), fingerprint))
+            .ok_or_else(|| Err::UnknownRecipient(fingerprint.into()))?,
+        None => select::select_key(recipients.keys(), None).ok_or(Err::NoneSelected)?,
+    }
+    .clone();
+
+    // Keys added through a recipient group must be removed from the group
+    let fingerprint = key.fingerprint(false);
+    if !store::store_read_gpg_ids(&store::store_gpg_ids_file(store))?
+        .iter()
+        .any(|entry| fingerprints_equal(entry, &fingerprint))
+    {
+        let groups = Groups::load(store).map_err(Err::Load)?;
+        let names: Vec<String> = groups
+            .groups_with(&fingerprint)
+            .into_iter()
+            .map(groups::reference)
+            .collect();
+        error::print_error_msg(format!(
+            "cannot remove recipient '{}', it is added through recipient group {}",
+            key.fingerprint(true),
+            names.join(", "),
+        ));
+        ErrorHintsBuilder::from_matcher(matcher_main)
+            .add_info(format!(
+                "remove it from the group with '{}'",
+                style::highlight(format!(
+                    "{} recipients group remove {} {}",
+                    util::bin_name(),
+                    names.first().map(|name| name.as_str()).unwrap_or("GROUP"),
+                    key.fingerprint(false),
+                )),
+            ))
+            .verbose(false)
+            .build()
+            .unwrap()
+            .print(false);
+        error::quit();
+    }
+
+    // Do not allow removing last recipient unless forcing
+    if recipients.keys().len() == 1 && !matcher_main.force() {
+        quit_last_recipient(matcher_main);
+    }
+
+    // Confirm removal
+    if !matcher_main.force() {
+        eprintln!("{key}");
+        if !cli::prompt_yes(
+            &format!("Remove '{}'?", key.fingerprint(true),),
+            Some(true),
+            matcher_main,
+        ) {
+            if matcher_main.verbose() {
+                eprintln!("Removal cancelled");
+            }
+            error::quit();
+        }
+    }
+
+    recipients.remove(&key);
+    recipients.save(store)?;
+
+    Ok(key)
+}
+
+/// Remove a recipient group reference from the store.
+///
+/// Members of the group stay in the group.
+fn remove_group(store: &Store, matcher_main: &MainMatcher, name: &str) -> Result<()> {
+    let entries = store::store_read_gpg_ids(&store::store_gpg_ids_file(store))?;
+    let reference = groups::reference(name);
+    ensure!(
+        entries.contains(&reference),
+        Err::UnknownRecipient(reference)
+    );
+
+    // Do not allow removing last recipient unless forcing
+    if entries.len() == 1 && !matcher_main.force() {
+        quit_last_recipient(matcher_main);
+    }
+
+    store::store_remove_group_reference(store, name)?;
+    store::store_sync_public_key_files(store, &store::store_load_all_keys(store)?)?;
+
+    Ok(())
+}
+
+/// Quit because the last recipient is removed, show force hint.
+fn quit_last_recipient(matcher_main: &MainMatcher) -> ! {
+    error::print_error_msg(
+        "cannot remove last recipient from store, you will permanently loose access to it",
+    );
+    error::ErrorHintsBuilder::from_matcher(matcher_main)
+        .force(true)
+        .verbose(false)
+        .build()
+        .unwrap()
+        .print(false);
+    error::quit();
+}
+
 #[derive(Debug, Error)]
 pub enum Err {
     #[error("failed to access password store")]
@@ -139,6 +241,9 @@ pub enum Err {
     #[error("failed to prepare password store tomb for usage")]
     Tomb(#[source] anyhow::Error),
 
+    #[error("recipient '{}' is not in store", _0)]
+    UnknownRecipient(String),
+
     #[error("no key selected")]
     NoneSelected,
 
@@ -0,0 +1,259 @@
+use anyhow::{Result, ensure};
+use clap::ArgMatches;
+use prs_lib::{
+    Key, Plaintext, Recipients, Store,
+    crypto::{Context, KeyStatus, prelude::*, store, util::fingerprints_equal},
+};
+use thiserror::Error;
+
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+use crate::util::{cli, error, select, sync};
+use crate::{
+    cmd::matcher::{
+        MainMatcher, Matcher,
+        recipients::{RecipientsMatcher, rotate::RotateMatcher},
+    },
+    util::error::{ErrorHintsBuilder, quit_error_msg},
+};
+
+/// Plaintext used to verify the new key can decrypt.
+const TEST_PLAINTEXT: &str = "prs key rotation test";
+
+/// A recipients rotate action.
+pub struct Rotate<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> Rotate<'a> {
+    /// Construct a new rotate action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the rotate action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let _matcher_recipients = RecipientsMatcher::with(self.cmd_matches).unwrap();
+        let matcher_rotate = RotateMatcher::with(self.cmd_matches).unwrap();
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+        let sync = store.sync();
+
+        // Disallow non-interactive mode, unless both keys are given
+        if matcher_main.no_interact()
+            && (matcher_rotate.from().is_none() || matcher_rotate.to().is_none())
+        {
+            quit_error_msg(
+                "cannot rotate recipient in non interactive mode without --from and --to",
+                ErrorHintsBuilder::from_matcher(&matcher_main)
+                    .add_info(format!(
+                        "remove '{}' ('{}') to enable interactive mode",
+                        crate::util::style::highlight("--no-interact"),
+                        crate::util::style::highlight("-I"),
+                    ))
+                    .verbose(false)
+                    .help(true)
+                    .build()
+                    .unwrap(),
+            );
+        }
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        // Prepare sync
+        sync::ensure_ready(&sync, matcher_rotate.allow_dirty());
+        if !matcher_rotate.no_sync() {
+            sync.prepare()?;
+        }
+
+        let mut context = crate::crypto::context(&matcher_main)?;
+        let (from, from_id) = select_from(&store, &matcher_rotate)?;
+        let to = select_to(&store, &mut context, &matcher_rotate)?;
+        ensure!(
+            !fingerprints_equal(&from, to.fingerprint(false)),
+            Err::SameKey(to.fingerprint(true)),
+        );
+
+        // New key must be usable, and must decrypt before the old key is removed
+        let status = to.meta().status();
+        ensure!(
+            status.is_usable(),
+            Err::Unusable(to.fingerprint(true), status)
+        );
+        ensure!(
+            can_decrypt(&mut context, &to),
+            Err::CannotDecrypt(to.fingerprint(true))
+        );
+
+        // Confirm rotation
+        if !matcher_main.force() {
+            eprintln!("{to}");
+            if !cli::prompt_yes(
+                &format!(
+                    "Replace recipient '{from_id}' with '{}'?",
+                    to.fingerprint(true)
+                ),
+                Some(true),
+                &matcher_main,
+            ) {
+                if matcher_main.verbose() {
+                    eprintln!("Rotation cancelled");
+                }
+                error::quit();
+            }
+        }
+
+        // Replace fingerprint everywhere, update public keys and re-encrypt once
+        let changed = store::store_replace_fingerprint(&store, &from, &to.fingerprint(false))
+            .map_err(Err::Replace)?;
+        store::store_sync_public_key_files(
+            &store,
+            &store::store_load_all_keys(&store).map_err(Err::Load)?,
+        )
+        .map_err(Err::Replace)?;
+        crate::action::housekeeping::recrypt::recrypt_changed(
+            &store,
+            &store.secrets(None),
+            &matcher_main,
+            matcher_rotate.jobs(&matcher_main),
+        )
+        .map_err(Err::Recrypt)?;
+
+        // Finalize sync
+        if !matcher_rotate.no_sync() {
+            sync.finalize(format!(
+                "Rotate recipient {from_id} to {}",
+                to.fingerprint(true)
+            ))?;
+        }
+
+        // Finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, true).map_err(Err::Tomb)?;
+
+        if !matcher_main.quiet() {
+            eprintln!(
+                "Rotated recipient {from_id} to {} in {changed} place(s)",
+                to.fingerprint(true)
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Select the recipient to replace.
+///
+/// The recipient may be in any GPG IDs file or recipient group. Returns the fingerprint, and a
+/// short ID for display.
+fn select_from(store: &Store, matcher_rotate: &RotateMatcher) -> Result<(String, String)> {
+    let keys = store::store_load_all_keys(store).map_err(Err::Load)?;
+
+    let Some(fingerprint) = matcher_rotate.from() else {
+        let key = select::select_key(&keys, Some("Select recipient to replace"))
+            .ok_or(Err::NoneSelected)?;
+        return Ok((key.fingerprint(false), key.fingerprint(true)));
+    };
+
+    // The old key may not be in the keychain anymore
+    ensure!(
+        store::store_read_all_gpg_fingerprints(store)
+            .map_err(Err::Load)?
+            .iter()
+            .any(|fp| fingerprints_equal(fp, fingerprint)),
+        Err::UnknownRecipient(fingerprint.into()),
+    );
+    let id = keys
+        .iter()
+        .find(|key| fingerprints_equal(key.fingerprint(false), fingerprint))
+        .map(|key| key.fingerprint(true))
+        .unwrap_or_else(|| fingerprint.into());
+    Ok((fingerprint.into(), id))
+}
+
+/// Select the new key.
+///
+/// Selects one of the own keys that is not a recipient yet, or finds the given key.
+fn select_to(store: &Store, context: &mut Context, matcher_rotate: &RotateMatcher) -> Result<Key> {
+    match matcher_rotate.to() {
+        Some(fingerprint) => Ok(context
+            .keys_public()
+            .map_err(Err::LoadKeys)?
+            .into_iter()
+            .find(|key| fingerprints_equal(key.fingerprint(false), fingerprint))
+            .ok_or_else(|| Err::UnknownKey(fingerprint.into()))?),
+        None => {
+            let mut keys = Recipients::from(context.keys_private().map_err(Err::LoadKeys)?);
+            keys.remove_all(&store::store_load_all_keys(store).map_err(Err::Load)?);
+            Ok(select::select_key(keys.keys(), Some("Select new key"))
+                .ok_or(Err::NoneSelected)?
+                .clone())
+        }
+    }
+}
+
+/// Check whether we can decrypt with the given key.
+///
+/// Encrypts a test message for just this key and decrypts it again.
+fn can_decrypt(context: &mut Context, key: &Key) -> bool {
+    context
+        .encrypt(
+            &Recipients::from(vec![key.clone()]),
+            Plaintext::from(TEST_PLAINTEXT),
+        )
+        .and_then(|ciphertext| context.decrypt(ciphertext))
+        .is_ok_and(|plaintext| plaintext.unsecure_ref() == TEST_PLAINTEXT.as_bytes())
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("recipient '{}' is not in store", _0)]
+    UnknownRecipient(String),
+
+    #[error("key '{}' not found in keychain", _0)]
+    UnknownKey(String),
+
+    #[error("no key selected")]
+    NoneSelected,
+
+    #[error("new key '{}' is the same as the recipient to replace", _0)]
+    SameKey(String),
+
+    #[error("new key '{}' cannot be used for encryption, key status is {}", _0, _1)]
+    Unusable(String, KeyStatus),
+
+    #[error(
+        "cannot decrypt with new key '{}', its secret key must be in your keychain",
+        _0
+    )]
+    CannotDecrypt(String),
+
+    #[error("failed to load existing keys from store")]
+    Load(#[source] anyhow::Error),
+
+    #[error("failed to load keys from keychain")]
+    LoadKeys(#[source] anyhow::Error),
+
+    #[error("failed to replace recipient in store")]
+    Replace(#[source] anyhow::Error),
+
+    #[error("failed to re-encrypt secrets in store")]
+    Recrypt(#[source] anyhow::Error),
+}
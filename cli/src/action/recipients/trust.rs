@@ -0,0 +1,139 @@
+use std::fs;
+
+use anyhow::{Result, ensure};
+use clap::ArgMatches;
+use prs_lib::{
+    Store,
+    crypto::{
+        store,
+        trust::{self, Trust as TrustedKeys},
+        util::fingerprints_equal,
+    },
+};
+use thiserror::Error;
+
+use crate::cmd::matcher::{
+    MainMatcher, Matcher,
+    recipients::{RecipientsMatcher, trust::TrustMatcher},
+};
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+
+/// A recipients trust action.
+pub struct Trust<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> Trust<'a> {
+    /// Construct a new trust action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the trust action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let _matcher_recipients = RecipientsMatcher::with(self.cmd_matches).unwrap();
+        let matcher_trust = TrustMatcher::with(self.cmd_matches).unwrap();
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        let mut trust = TrustedKeys::load(&store).map_err(Err::Load)?;
+        match matcher_trust.fingerprint() {
+            None => list(&store, &trust, &matcher_main)?,
+            Some(fingerprint) if matcher_trust.remove() => {
+                ensure!(
+                    trust.unpin(fingerprint),
+                    Err::NotTrusted(fingerprint.into())
+                );
+                trust.save().map_err(Err::Save)?;
+                if !matcher_main.quiet() {
+                    eprintln!("Removed trusted recipient key: {fingerprint}");
+                }
+            }
+            Some(fingerprint) => {
+                let fingerprint = store::store_read_all_gpg_fingerprints(&store)
+                    .map_err(Err::Load)?
+                    .into_iter()
+                    .find(|fp| fingerprints_equal(fp, fingerprint))
+                    .ok_or_else(|| Err::UnknownRecipient(fingerprint.into()))?;
+                trust.pin(&fingerprint, key_file_hash(&store, &fingerprint));
+                trust.save().map_err(Err::Save)?;
+                if !matcher_main.quiet() {
+                    eprintln!("Trusted recipient key: {fingerprint}");
+                }
+            }
+        }
+
+        // Finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, false).map_err(Err::Tomb)?;
+
+        Ok(())
+    }
+}
+
+/// List trusted keys, and whether their key file changed.
+fn list(store: &Store, trust: &TrustedKeys, matcher_main: &MainMatcher) -> Result<()> {
+    let keys = store::store_load_all_keys(store).map_err(Err::Load)?;
+    for (fingerprint, hash) in trust.keys() {
+        if matcher_main.quiet() {
+            println!("{fingerprint}");
+            continue;
+        }
+
+        let key = keys
+            .iter()
+            .find(|key| fingerprints_equal(key.fingerprint(false), fingerprint))
+            .map(|key| key.to_string())
+            .unwrap_or_else(|| fingerprint.clone());
+        match (hash, key_file_hash(store, fingerprint)) {
+            (_, None) => println!("{key} (no key file in store)"),
+            (Some(hash), Some(current)) if *hash != current => {
+                println!("{key} (key file changed since trusted)")
+            }
+            _ => println!("{key}"),
+        }
+    }
+    Ok(())
+}
+
+/// Get the hash of the public key file of a recipient in the store, if it exists.
+fn key_file_hash(store: &Store, fingerprint: &str) -> Option<String> {
+    fs::read(store::store_public_keys_dir(store).join(fingerprint))
+        .ok()
+        .map(|data| trust::hash(&data))
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("failed to load trusted recipient keys")]
+    Load(#[source] anyhow::Error),
+
+    #[error("failed to save trusted recipient keys")]
+    Save(#[source] anyhow::Error),
+
+    #[error("recipient '{}' is not in store", _0)]
+    UnknownRecipient(String),
+
+    #[error("recipient '{}' is not trusted", _0)]
+    NotTrusted(String),
+}
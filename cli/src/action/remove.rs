@@ -6,14 +6,14 @@ use anyhow::{Result, anyhow};
 use clap::ArgMatches;
 #[cfg(feature = "alias")]
 use prs_lib::store::SecretIterConfig;
-use prs_lib::{Secret, Store};
+use prs_lib::{Secret, Store, crypto::shamir};
 use thiserror::Error;
 use walkdir::WalkDir;
 
 use crate::cmd::matcher::{MainMatcher, Matcher, remove::RemoveMatcher};
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 use crate::util::tomb;
-use crate::util::{cli, error, select, sync};
+use crate::util::{cli, error, search, select, sync};
 
 /// Remove secret action.
 pub struct Remove<'a> {
@@ -61,6 +61,13 @@ impl<'a> Remove<'a> {
             error::quit();
         };
 
+        // Remove tags and search index entries of removed secrets, including linked aliases
+        let mut tags = store.tags().map_err(Err::Tags)?;
+        if !tags.prune(&store).is_empty() {
+            tags.save().map_err(Err::Tags)?;
+        }
+        search::update(&store, &matcher_main, |index| Ok(index.prune(&store)));
+
         // Finalize sync
         if !matcher_remove.no_sync() {
             sync.finalize(format!("Remove secret {}", secret.name))?;
@@ -98,10 +105,16 @@ fn remove_confirm(
     let is_alias = fs::symlink_metadata(&secret.path)?.file_type().is_symlink();
     #[cfg(not(feature = "alias"))]
     let is_alias = false;
+    let shares = shamir::find_shares(&secret.path);
     let prompt = &format!(
-        "Remove {}'{}'?",
+        "Remove {}'{}'{}?",
         if is_alias { "alias " } else { "" },
         secret.path.display(),
+        if shares.is_empty() {
+            String::new()
+        } else {
+            format!(" and its {} shares", shares.len())
+        },
     );
 
     // Confirm removal
@@ -131,10 +144,13 @@ fn remove_confirm(
         }
     }
 
-    // Remove secret, remove directories that become empty
+    // Remove secret and its shares, remove directories that become empty
     fs::remove_file(&secret.path)
         .map(|_| ())
         .map_err(Err::Remove)?;
+    for (_, share) in shares {
+        fs::remove_file(share).map_err(Err::Remove)?;
+    }
     remove_empty_secret_dir(secret);
 
     Ok(true)
@@ -239,4 +255,7 @@ pub enum Err {
 
     #[error("failed to remove secret file")]
     Remove(#[source] std::io::Error),
+
+    #[error("failed to update secret tags")]
+    Tags(#[source] anyhow::Error),
 }
@@ -0,0 +1,185 @@
+use anyhow::Result;
+use chbs::{config::BasicConfig, prelude::*};
+use chrono::Local;
+use clap::ArgMatches;
+use prs_lib::{Plaintext, Store, crypto::prelude::*};
+use thiserror::Error;
+
+use crate::cmd::matcher::{MainMatcher, Matcher, rotate::RotateMatcher};
+#[cfg(feature = "clipboard")]
+use crate::util::clipboard;
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+use crate::util::{error, expiry, pass, search, secret, select, sync};
+
+/// Rotate secret action.
+pub struct Rotate<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> Rotate<'a> {
+    /// Construct a new rotate action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the rotate action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let matcher_rotate = RotateMatcher::with(self.cmd_matches).unwrap();
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+        let sync = store.sync();
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        // Prepare store sync
+        sync::ensure_ready(&sync, matcher_rotate.allow_dirty());
+        if !matcher_rotate.no_sync() {
+            sync.prepare()?;
+        }
+
+        let secret = select::store_select_secret(&store, matcher_rotate.query(), &matcher_main)
+            .ok_or(Err::NoneSelected)?;
+
+        // Read existing secret, binary secrets have no password to rotate
+        let mut context = crate::crypto::context(&matcher_main)?;
+        let existing = context.decrypt_file(&secret.path).map_err(Err::Read)?;
+        if existing.is_binary() {
+            return Err(Err::Binary.into());
+        }
+
+        // Generate new password, keep all other lines
+        let password = generate_password(&matcher_rotate);
+        let mut plaintext = password.clone();
+        let rest = existing.except_first_line().map_err(Err::Read)?;
+        if !rest.is_empty() {
+            plaintext.append(rest, true);
+        }
+
+        // Renew expiry date, warn if it is still expired
+        match expiry::renew(&mut plaintext).map_err(Err::Expiry)? {
+            Some(expires) if matcher_main.verbose() => eprintln!(
+                "Secret now expires on {}",
+                expires.format(expiry::DATE_FORMAT)
+            ),
+            Some(_) => {}
+            None => {
+                if let Ok(Some(expires)) = expiry::expiry(&plaintext, || None)
+                    && expires <= Local::now()
+                {
+                    error::print_warning(format!(
+                        "secret still expires on {}, update its '{}' property",
+                        expires.format(expiry::DATE_FORMAT),
+                        expiry::PROPERTY_EXPIRES,
+                    ));
+                }
+            }
+        }
+
+        // Encrypt and write rotated secret
+        context
+            .encrypt_file(
+                &store.recipients_for(&secret)?,
+                plaintext.clone(),
+                &secret.path,
+            )
+            .map_err(Err::Write)?;
+        search::update(&store, &matcher_main, |index| {
+            index.update(&secret, &plaintext).map(|_| true)
+        });
+
+        // Copy new password to clipboard
+        #[cfg(feature = "clipboard")]
+        if matcher_rotate.copy() {
+            clipboard::copy_plaintext(
+                password.clone(),
+                true,
+                !matcher_main.force(),
+                matcher_main.quiet(),
+                matcher_main.verbose(),
+                matcher_rotate.timeout()?,
+            )?;
+        }
+
+        // Show in stdout
+        if matcher_rotate.show() {
+            secret::print(password).map_err(Err::Print)?;
+        }
+
+        // Finalize store sync
+        if !matcher_rotate.no_sync() {
+            sync.finalize(format!("Rotate secret {}", secret.name))?;
+        }
+
+        // Finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, true).map_err(Err::Tomb)?;
+
+        // Determine whether we outputted anything to stdout/stderr
+        #[cfg_attr(not(feature = "clipboard"), expect(unused_mut))]
+        let mut output_any = matcher_rotate.show();
+        #[cfg(feature = "clipboard")]
+        {
+            output_any = output_any || matcher_rotate.copy();
+        }
+
+        if matcher_main.verbose() || (!output_any && !matcher_main.quiet()) {
+            eprintln!("Secret rotated");
+        }
+
+        Ok(())
+    }
+}
+
+/// Generate a random password.
+///
+/// This generates a secure random password/passphrase based on user configuration.
+fn generate_password(matcher_rotate: &RotateMatcher) -> Plaintext {
+    if matcher_rotate.passphrase() {
+        let config = BasicConfig {
+            words: matcher_rotate.length() as usize,
+            ..Default::default()
+        };
+        config.to_scheme().generate().into()
+    } else {
+        pass::generate_password(matcher_rotate.length())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("no secret selected")]
+    NoneSelected,
+
+    #[error("failed to read secret")]
+    Read(#[source] anyhow::Error),
+
+    #[error("cannot rotate binary secret")]
+    Binary,
+
+    #[error("failed to renew secret expiry")]
+    Expiry(#[source] anyhow::Error),
+
+    #[error("failed to write rotated secret")]
+    Write(#[source] anyhow::Error),
+
+    #[error("failed to print password to stdout")]
+    Print(#[source] std::io::Error),
+}
@@ -0,0 +1,141 @@
+use anyhow::Result;
+use clap::ArgMatches;
+use prs_lib::{Plaintext, Store, Structured, crypto::prelude::*};
+use thiserror::Error;
+
+use crate::cmd::matcher::{MainMatcher, Matcher, set::SetMatcher};
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+use crate::util::{pass, search, secret, select, stdin, sync};
+
+/// Set secret property action.
+pub struct Set<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> Set<'a> {
+    /// Construct a new set action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the set action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let matcher_set = SetMatcher::with(self.cmd_matches).unwrap();
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+        let sync = store.sync();
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        // Prepare sync
+        sync::ensure_ready(&sync, matcher_set.allow_dirty());
+        if !matcher_set.no_sync() {
+            sync.prepare()?;
+        }
+
+        let secret = select::store_select_secret(&store, matcher_set.query(), &matcher_main)
+            .ok_or(Err::NoneSelected)?;
+
+        secret::print_name(matcher_set.query(), &secret, &store, matcher_main.quiet());
+
+        // Determine property value
+        let name = matcher_set.name();
+        let value = if matcher_set.stdin() {
+            trim_newline(stdin::read_plaintext(!matcher_main.quiet())?)
+        } else if matcher_set.generate() {
+            pass::generate_password(matcher_set.length())
+        } else {
+            Plaintext::from(matcher_set.value().unwrap().as_str())
+        };
+
+        let mut context = crate::crypto::context(&matcher_main)?;
+        let mut plaintext = context.decrypt_file(&secret.path).map_err(Err::Read)?;
+        if plaintext.is_binary() {
+            return Err(Err::Binary.into());
+        }
+
+        // Update the property or structured field
+        if matcher_set.field() {
+            let mut structured = Structured::parse_or_convert(plaintext).map_err(Err::Set)?;
+            structured.set_field(name, value).map_err(Err::Set)?;
+            plaintext = structured.into_plaintext();
+        } else {
+            plaintext.set_property(name, value).map_err(Err::Set)?;
+        }
+
+        // Encrypt and write changed plaintext
+        let recipients = store.recipients_for(&secret)?;
+        context
+            .encrypt_file(&recipients, plaintext.clone(), &secret.path)
+            .map_err(Err::Write)?;
+        search::update(&store, &matcher_main, |index| {
+            index.update(&secret, &plaintext).map(|_| true)
+        });
+
+        // Finalize sync
+        if !matcher_set.no_sync() {
+            let kind = if matcher_set.field() {
+                "field"
+            } else {
+                "property"
+            };
+            sync.finalize(format!("Set {kind} {name} in secret {}", secret.name))?;
+        }
+
+        // Finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, true).map_err(Err::Tomb)?;
+
+        if !matcher_main.quiet() {
+            eprintln!("Secret updated");
+        }
+
+        Ok(())
+    }
+}
+
+/// Trim a single trailing newline from the given plaintext.
+///
+/// Values read from stdin are usually terminated by a newline, which is not part of the value.
+fn trim_newline(plaintext: Plaintext) -> Plaintext {
+    let data = plaintext.unsecure_ref();
+    let data = data.strip_suffix(b"\n").unwrap_or(data);
+    let data = data.strip_suffix(b"\r").unwrap_or(data);
+    data.to_vec().into()
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("no secret selected")]
+    NoneSelected,
+
+    #[error("failed to read secret")]
+    Read(#[source] anyhow::Error),
+
+    #[error("cannot edit binary secret as text, use attach or extract instead")]
+    Binary,
+
+    #[error("failed to set property in secret")]
+    Set(#[source] anyhow::Error),
+
+    #[error("failed to write changed secret")]
+    Write(#[source] anyhow::Error),
+}
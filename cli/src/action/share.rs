@@ -0,0 +1,288 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+
+use anyhow::Result;
+use clap::ArgMatches;
+use prs_lib::{
+    Key, Recipients, Store,
+    crypto::{
+        Context,
+        prelude::*,
+        util::{armor_message, is_armored_public_key},
+    },
+};
+use thiserror::Error;
+
+use crate::cmd::matcher::{MainMatcher, Matcher, share::ShareMatcher};
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+use crate::util::{
+    self, age, cli,
+    error::{self, ErrorHintsBuilder, quit_error_msg},
+    secret, select, style, sync,
+};
+
+/// Share secret action.
+pub struct Share<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> Share<'a> {
+    /// Construct a new share action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the share action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let matcher_share = ShareMatcher::with(self.cmd_matches).unwrap();
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+        let sync = store.sync();
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        // Prepare sync
+        sync::ensure_ready(&sync, matcher_share.allow_dirty());
+        if !matcher_share.no_sync() {
+            sync.prepare()?;
+        }
+
+        let secret = select::store_select_secret(&store, matcher_share.query(), &matcher_main)
+            .ok_or(Err::NoneSelected)?;
+        secret::print_name(matcher_share.query(), &secret, &store, matcher_main.quiet());
+
+        let mut context = crate::crypto::context(&matcher_main)?;
+        let recipient = ShareRecipient::resolve(&mut context, matcher_share.to(), &matcher_main)?;
+
+        // Confirm sharing with recipient
+        if !matcher_main.force() {
+            eprintln!("Recipient: {recipient}");
+            if !cli::prompt_yes(
+                &format!("Share secret '{}' with this recipient?", secret.name),
+                Some(true),
+                &matcher_main,
+            ) {
+                if matcher_main.verbose() {
+                    eprintln!("Sharing cancelled");
+                }
+                error::quit();
+            }
+        }
+
+        // Check if destination already exists if not forcing
+        if let Some(path) = matcher_share.out()
+            && !matcher_main.force()
+            && path.exists()
+        {
+            eprintln!("A file at '{}' already exists", path.display());
+            if !cli::prompt_yes("Overwrite?", Some(true), &matcher_main) {
+                if matcher_main.verbose() {
+                    eprintln!("Sharing cancelled");
+                }
+                error::quit();
+            }
+        }
+
+        // Decrypt secret, select property
+        let mut plaintext = context.decrypt_file(&secret.path).map_err(Err::Read)?;
+        if let Some(property) = matcher_share.property() {
+            if plaintext.is_binary() {
+                return Err(Err::Binary.into());
+            }
+            plaintext = plaintext.property(property).map_err(Err::Property)?;
+        }
+
+        // Encrypt for recipient
+        let ciphertext = match &recipient {
+            ShareRecipient::Key(key) => {
+                let ciphertext = context
+                    .encrypt(&Recipients::from(vec![key.clone()]), plaintext)
+                    .map_err(Err::Encrypt)?;
+                armor_message(&ciphertext)
+            }
+            ShareRecipient::Age(recipient) => {
+                age::encrypt(recipient, &plaintext).map_err(Err::Encrypt)?
+            }
+        };
+        write_output(matcher_share.out(), &ciphertext)?;
+
+        // Record share in store history, no files change
+        let msg = match matcher_share.property() {
+            Some(property) => format!(
+                "Share property '{property}' of secret {} with {}",
+                secret.name,
+                recipient.id(),
+            ),
+            None => format!("Share secret {} with {}", secret.name, recipient.id()),
+        };
+        if !matcher_share.no_sync() {
+            if sync.is_init() {
+                sync.commit_empty(&msg).map_err(Err::Record)?;
+            }
+            sync.finalize_push()?;
+        }
+
+        // Finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, false).map_err(Err::Tomb)?;
+
+        if !matcher_main.quiet() {
+            match matcher_share.out() {
+                Some(path) => eprintln!(
+                    "Secret shared with {}, written to {}",
+                    recipient.id(),
+                    path.display()
+                ),
+                None => eprintln!("Secret shared with {}", recipient.id()),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A recipient to share a secret with.
+enum ShareRecipient {
+    /// A key in the keychain.
+    Key(Key),
+
+    /// An age recipient.
+    Age(String),
+}
+
+impl ShareRecipient {
+    /// Resolve the recipient to share with.
+    ///
+    /// The recipient may be an age recipient, a public key file or the fingerprint of a key in
+    /// the keychain.
+    fn resolve(context: &mut Context, to: &str, matcher_main: &MainMatcher) -> Result<Self> {
+        if age::is_recipient(to) {
+            return Ok(Self::Age(to.into()));
+        }
+        Ok(Self::Key(resolve_key(context, to, matcher_main)?))
+    }
+
+    /// Identifier to record who the secret was shared with.
+    fn id(&self) -> String {
+        match self {
+            Self::Key(key) => format!("{} ({})", key.fingerprint(false), key.display()),
+            Self::Age(recipient) => recipient.clone(),
+        }
+    }
+}
+
+impl std::fmt::Display for ShareRecipient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Key(key) => write!(f, "{key}"),
+            Self::Age(recipient) => write!(f, "[age] {recipient}"),
+        }
+    }
+}
+
+/// Resolve a public key file or the fingerprint of a key in the keychain.
+///
+/// Keys from a file are imported into the keychain, they're never added to the store.
+pub fn resolve_key(context: &mut Context, to: &str, matcher_main: &MainMatcher) -> Result<Key> {
+    let path = Path::new(to);
+    if path.is_file() {
+        let data = fs::read(path).map_err(Err::ReadKeyFile)?;
+        if !is_armored_public_key(&data) {
+            return Err(Err::InvalidKeyFile(to.into()).into());
+        }
+        let key = context
+            .read_keys(&data)
+            .map_err(Err::LoadKeys)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Err::InvalidKeyFile(to.into()))?;
+        context.import_key(&data).map_err(Err::Import)?;
+        return Ok(key);
+    }
+
+    match context.get_public_key(to) {
+        Ok(key) => Ok(key),
+        Err(_) => quit_error_msg(
+            format!("no key found in keychain for '{to}'"),
+            ErrorHintsBuilder::from_matcher(matcher_main)
+                .add_info(format!(
+                    "Pass a public key file to '{}' instead",
+                    style::highlight("--to")
+                ))
+                .build()
+                .unwrap(),
+        ),
+    }
+}
+
+/// Write shared ciphertext to the given file, or stdout if `None`.
+///
+/// The file is only readable by the current user.
+fn write_output(path: Option<&Path>, ciphertext: &[u8]) -> Result<()> {
+    match path {
+        Some(path) => util::fs::create_private(path, true)
+            .and_then(|mut file| file.write_all(ciphertext))
+            .map_err(Err::Write)?,
+        None => {
+            let mut stdout = io::stdout().lock();
+            stdout.write_all(ciphertext).map_err(Err::Write)?;
+            let _ = stdout.flush();
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("no secret selected")]
+    NoneSelected,
+
+    #[error("failed to read public key file")]
+    ReadKeyFile(#[source] io::Error),
+
+    #[error("file '{}' does not contain an ASCII armored public key", _0)]
+    InvalidKeyFile(String),
+
+    #[error("failed to load keys from keychain")]
+    LoadKeys(#[source] anyhow::Error),
+
+    #[error("failed to import key into keychain")]
+    Import(#[source] anyhow::Error),
+
+    #[error("failed to read secret")]
+    Read(#[source] anyhow::Error),
+
+    #[error("cannot select property of binary secret")]
+    Binary,
+
+    #[error("failed to select property from secret")]
+    Property(#[source] anyhow::Error),
+
+    #[error("failed to encrypt secret for recipient")]
+    Encrypt(#[source] anyhow::Error),
+
+    #[error("failed to write shared secret")]
+    Write(#[source] io::Error),
+
+    #[error("failed to record share in store history")]
+    Record(#[source] anyhow::Error),
+}
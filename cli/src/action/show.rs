@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use anyhow::Result;
 use clap::ArgMatches;
-use prs_lib::{Store, crypto::prelude::*};
+use prs_lib::{Store, Structured, crypto::prelude::*};
 use thiserror::Error;
 
 use crate::cmd::matcher::{MainMatcher, Matcher, show::ShowMatcher};
@@ -10,7 +10,11 @@ use crate::cmd::matcher::{MainMatcher, Matcher, show::ShowMatcher};
 use crate::util::clipboard;
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 use crate::util::tomb;
-use crate::util::{secret, select};
+use crate::util::{
+    expiry,
+    output::{self, SecretContentOutput, SecretOutput},
+    secret, select,
+};
 use crate::viewer;
 
 /// Show secret action.
@@ -42,18 +46,58 @@ impl<'a> Show<'a> {
         #[cfg(all(feature = "tomb", target_os = "linux"))]
         tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
 
-        let secret = select::store_select_secret(&store, matcher_show.query(), &matcher_main)
+        let secret = select::store_select_secret_read(&store, matcher_show.query(), &matcher_main)
             .ok_or(Err::NoneSelected)?;
 
         let mut plaintext = crate::crypto::context(&matcher_main)?
             .decrypt_file(&secret.path)
             .map_err(Err::Read)?;
 
+        // Summarize binary secrets, do not print them
+        if plaintext.is_binary() {
+            if matcher_show.first_line()
+                || matcher_show.property().is_some()
+                || matcher_show.field().is_some()
+            {
+                return Err(Err::Binary.into());
+            }
+
+            if matcher_main.json() {
+                output::print_json(&SecretContentOutput {
+                    secret: SecretOutput::new(&store, &secret),
+                    content: None,
+                    binary: true,
+                    size: plaintext.binary_size(),
+                })
+                .map_err(Err::Output)?;
+            } else {
+                secret::print_name(matcher_show.query(), &secret, &store, matcher_main.quiet());
+                eprintln!(
+                    "Secret is binary ({} bytes), use '{} extract' to write it to a file",
+                    plaintext.binary_size(),
+                    crate::util::bin_name(),
+                );
+            }
+
+            // Finalize tomb
+            #[cfg(all(feature = "tomb", target_os = "linux"))]
+            tomb::finalize_tomb(&mut tomb, &matcher_main, false).map_err(Err::Tomb)?;
+
+            return Ok(());
+        }
+
+        // Warn if secret is expired
+        expiry::warn_expired(&store, &secret, &plaintext, &matcher_main);
+
         // Trim plaintext to first line or property
         if matcher_show.first_line() {
             plaintext = plaintext.first_line()?;
         } else if let Some(property) = matcher_show.property() {
             plaintext = plaintext.property(property).map_err(Err::Property)?;
+        } else if let Some(field) = matcher_show.field() {
+            plaintext = Structured::parse(plaintext)
+                .and_then(|secret| secret.field(field))
+                .map_err(Err::Field)?;
         }
 
         // Copy to clipboard
@@ -82,6 +126,14 @@ impl<'a> Show<'a> {
                 matcher_show.query(),
             )
             .map_err(Err::Viewer)?;
+        } else if matcher_main.json() {
+            output::print_json(&SecretContentOutput {
+                secret: SecretOutput::new(&store, &secret),
+                content: Some(plaintext.unsecure_to_str().map_err(Err::Utf8)?),
+                binary: false,
+                size: plaintext.unsecure_ref().len(),
+            })
+            .map_err(Err::Output)?;
         } else {
             secret::print_name(matcher_show.query(), &secret, &store, matcher_main.quiet());
             secret::print(plaintext).map_err(Err::Print)?
@@ -110,12 +162,24 @@ pub enum Err {
     #[error("failed to read secret")]
     Read(#[source] anyhow::Error),
 
+    #[error("cannot select from binary secret, use extract instead")]
+    Binary,
+
     #[error("failed to select property from secret")]
     Property(#[source] anyhow::Error),
 
+    #[error("failed to select field from structured secret")]
+    Field(#[source] anyhow::Error),
+
     #[error("failed to print secret to stdout")]
     Print(#[source] std::io::Error),
 
     #[error("failed to start secret viewer")]
     Viewer(#[source] anyhow::Error),
+
+    #[error("failed to parse secret as UTF-8 for JSON output")]
+    Utf8(#[source] std::str::Utf8Error),
+
+    #[error("failed to print secret as JSON")]
+    Output(#[source] std::io::Error),
 }
@@ -0,0 +1,213 @@
+use std::fs;
+
+use anyhow::{Result, ensure};
+use clap::ArgMatches;
+use prs_lib::{
+    Key, Recipients, Store,
+    crypto::{Context, KeyStatus, prelude::*, shamir, util::fingerprints_equal},
+};
+use thiserror::Error;
+
+use crate::cmd::matcher::{MainMatcher, Matcher, split::SplitMatcher};
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+use crate::util::{cli, error, secret, select, sync};
+
+/// Split secret action.
+pub struct Split<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> Split<'a> {
+    /// Construct a new split action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the split action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let matcher_split = SplitMatcher::with(self.cmd_matches).unwrap();
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+        let sync = store.sync();
+
+        // Each recipient gets exactly one share
+        let to = matcher_split.to();
+        let shares = match matcher_split.shares() {
+            Some(shares) => shares,
+            None => u8::try_from(to.len()).map_err(|_| Err::TooManyShares)?,
+        };
+        ensure!(
+            to.len() == shares as usize,
+            Err::RecipientCount(to.len(), shares)
+        );
+        let threshold = matcher_split.threshold();
+        ensure!(threshold <= shares, Err::Threshold(threshold, shares));
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        // Prepare sync
+        sync::ensure_ready(&sync, matcher_split.allow_dirty());
+        if !matcher_split.no_sync() {
+            sync.prepare()?;
+        }
+
+        let secret = select::store_select_secret(&store, matcher_split.query(), &matcher_main)
+            .ok_or(Err::NoneSelected)?;
+        secret::print_name(matcher_split.query(), &secret, &store, matcher_main.quiet());
+
+        let mut context = crate::crypto::context(&matcher_main)?;
+        let keys = resolve_keys(&mut context, &to, &matcher_main)?;
+
+        // Confirm split
+        if !matcher_main.force() {
+            for (i, key) in keys.iter().enumerate() {
+                eprintln!("Share {}: {key}", i + 1);
+            }
+            if !cli::prompt_yes(
+                &format!(
+                    "Split secret '{}' into {shares} shares, {threshold} needed to reconstruct?",
+                    secret.name,
+                ),
+                Some(true),
+                &matcher_main,
+            ) {
+                if matcher_main.verbose() {
+                    eprintln!("Split cancelled");
+                }
+                error::quit();
+            }
+        }
+
+        // Replace shares of an earlier split, they cannot be mixed with the new shares
+        let existing = shamir::find_shares(&secret.path);
+        if !existing.is_empty() {
+            if !matcher_main.force() {
+                eprintln!("Secret '{}' is already split", secret.name);
+                if !cli::prompt_yes("Replace existing shares?", Some(true), &matcher_main) {
+                    if matcher_main.verbose() {
+                        eprintln!("Split cancelled");
+                    }
+                    error::quit();
+                }
+            }
+            for (_, path) in existing {
+                fs::remove_file(path).map_err(Err::RemoveShare)?;
+            }
+        }
+
+        // Split secret, encrypt each share for its own recipient
+        let plaintext = context.decrypt_file(&secret.path).map_err(Err::Read)?;
+        for (share, key) in shamir::split(&plaintext, shares, threshold)?
+            .into_iter()
+            .zip(keys)
+        {
+            context
+                .encrypt_file(
+                    &Recipients::from(vec![key]),
+                    share.to_plaintext(),
+                    &shamir::share_path(&secret.path, share.index),
+                )
+                .map_err(Err::Write)?;
+        }
+
+        // Finalize sync
+        if !matcher_split.no_sync() {
+            sync.finalize(format!(
+                "Split secret {} into {shares} shares with threshold {threshold}",
+                secret.name
+            ))?;
+        }
+
+        // Finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, true).map_err(Err::Tomb)?;
+
+        if !matcher_main.quiet() {
+            eprintln!("Secret split into {shares} shares, {threshold} needed to reconstruct");
+        }
+
+        Ok(())
+    }
+}
+
+/// Resolve the share recipients.
+///
+/// Each recipient must be a distinct key that can be encrypted for.
+fn resolve_keys(
+    context: &mut Context,
+    to: &[&String],
+    matcher_main: &MainMatcher,
+) -> Result<Vec<Key>> {
+    let mut keys: Vec<Key> = Vec::with_capacity(to.len());
+    for to in to {
+        let key = crate::action::share::resolve_key(context, to, matcher_main)?;
+        let status = key.meta().status();
+        ensure!(
+            status.is_usable(),
+            Err::Unusable(key.fingerprint(true), status)
+        );
+        ensure!(
+            !keys
+                .iter()
+                .any(|k| fingerprints_equal(k.fingerprint(false), key.fingerprint(false))),
+            Err::DuplicateRecipient(key.fingerprint(true)),
+        );
+        keys.push(key);
+    }
+    Ok(keys)
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("cannot split into more than 255 shares")]
+    TooManyShares,
+
+    #[error(
+        "got {} recipients for {} shares, each share needs exactly one recipient",
+        _0,
+        _1
+    )]
+    RecipientCount(usize, u8),
+
+    #[error("threshold {} is larger than the number of shares {}", _0, _1)]
+    Threshold(u8, u8),
+
+    #[error("no secret selected")]
+    NoneSelected,
+
+    #[error("key '{}' cannot be used for encryption, key status is {}", _0, _1)]
+    Unusable(String, KeyStatus),
+
+    #[error(
+        "recipient '{}' is given more than once, each share needs a different recipient",
+        _0
+    )]
+    DuplicateRecipient(String),
+
+    #[error("failed to remove existing share")]
+    RemoveShare(#[source] std::io::Error),
+
+    #[error("failed to read secret")]
+    Read(#[source] anyhow::Error),
+
+    #[error("failed to write encrypted share")]
+    Write(#[source] anyhow::Error),
+}
@@ -0,0 +1,102 @@
+use anyhow::Result;
+use clap::ArgMatches;
+use prs_lib::{Store, crypto::prelude::*};
+use ssh_key::HashAlg;
+use thiserror::Error;
+
+use crate::cmd::matcher::{MainMatcher, Matcher, ssh_add::SshAddMatcher};
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+use crate::util::{
+    select,
+    ssh::{self, Constraints},
+};
+
+/// SSH add action.
+pub struct SshAdd<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> SshAdd<'a> {
+    /// Construct a new SSH add action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the SSH add action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let matcher_ssh_add = SshAddMatcher::with(self.cmd_matches).unwrap();
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        let secret = select::store_select_secret(&store, matcher_ssh_add.query(), &matcher_main)
+            .ok_or(Err::NoneSelected)?;
+
+        let plaintext = crate::crypto::context(&matcher_main)?
+            .decrypt_file(&secret.path)
+            .map_err(Err::Read)?;
+
+        // Parse key, name it after the secret if it has no comment
+        let mut key = ssh::parse_private_key(&plaintext).map_err(Err::Key)?;
+        if key.comment().is_empty() {
+            key.set_comment(&secret.name);
+        }
+
+        ssh::agent_add_identity(
+            &key,
+            Constraints {
+                lifetime: matcher_ssh_add.lifetime(),
+                confirm: matcher_ssh_add.confirm(),
+            },
+        )
+        .map_err(Err::Agent)?;
+
+        // Finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, false).map_err(Err::Tomb)?;
+
+        if !matcher_main.quiet() {
+            eprintln!(
+                "Identity added: {} ({})",
+                key.fingerprint(HashAlg::Sha256),
+                key.comment(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("no secret selected")]
+    NoneSelected,
+
+    #[error("failed to read secret")]
+    Read(#[source] anyhow::Error),
+
+    #[error("failed to load SSH key from secret")]
+    Key(#[source] anyhow::Error),
+
+    #[error("failed to add SSH key to agent")]
+    Agent(#[source] anyhow::Error),
+}
@@ -0,0 +1,188 @@
+use std::env;
+use std::fs;
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::os::unix::net::UnixListener;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+
+use anyhow::Result;
+use clap::ArgMatches;
+use prs_lib::{Store, crypto::prelude::*};
+use ssh_key::HashAlg;
+use thiserror::Error;
+
+use crate::cmd::matcher::{MainMatcher, Matcher, ssh_agent::SshAgentMatcher};
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+use crate::util::{
+    cli, error, select,
+    ssh::{self, Agent, Identity},
+};
+
+/// Default agent socket file name.
+const SOCKET_NAME: &str = "prs-ssh-agent.sock";
+
+/// SSH agent action.
+pub struct SshAgent<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> SshAgent<'a> {
+    /// Construct a new SSH agent action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the SSH agent action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let matcher_ssh_agent = SshAgentMatcher::with(self.cmd_matches).unwrap();
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        // Select secrets, select a single one interactively if no queries are given
+        let mut queries: Vec<Option<String>> =
+            matcher_ssh_agent.queries().into_iter().map(Some).collect();
+        if queries.is_empty() {
+            queries.push(None);
+        }
+        let mut context = crate::crypto::context(&matcher_main)?;
+        let mut identities = Vec::with_capacity(queries.len());
+        for query in queries {
+            let secret = select::store_select_secret(&store, query, &matcher_main)
+                .ok_or(Err::NoneSelected)?;
+            let plaintext = context.decrypt_file(&secret.path).map_err(Err::Read)?;
+
+            let mut key = ssh::parse_private_key(&plaintext)
+                .map_err(|err| Err::Key(secret.name.clone(), err))?;
+            if key.comment().is_empty() {
+                key.set_comment(&secret.name);
+            }
+            identities.push(Identity::new(key)?);
+        }
+
+        // Keys are in memory now, finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, false).map_err(Err::Tomb)?;
+
+        let socket = matcher_ssh_agent
+            .socket()
+            .unwrap_or_else(default_socket_path);
+        let listener = bind(&socket)?;
+
+        if matcher_main.quiet() {
+            println!("{}", socket.display());
+        } else {
+            for identity in &identities {
+                eprintln!(
+                    "Serving identity: {} ({})",
+                    identity.key().fingerprint(HashAlg::Sha256),
+                    identity.key().comment(),
+                );
+            }
+            eprintln!("Agent listening, use it by setting:");
+            println!("export {}={}", ssh::SSH_AUTH_SOCK, socket.display());
+        }
+
+        // Confirm each use of a key, one prompt at a time
+        let prompt_lock = Mutex::new(());
+        let agent = Agent::new(identities, |identity: &Identity| {
+            if !matcher_ssh_agent.confirm() {
+                return true;
+            }
+            let _lock = prompt_lock.lock().unwrap();
+            cli::prompt_yes(
+                &format!("Allow use of SSH key '{}'?", identity.key().comment()),
+                Some(false),
+                &matcher_main,
+            )
+        });
+
+        // Serve each client on its own thread until we're killed
+        let agent = &agent;
+        thread::scope(|s| {
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        s.spawn(move || {
+                            if let Err(err) = agent.serve_client(stream) {
+                                error::print_error(err.context("failed to serve SSH agent client"));
+                            }
+                        });
+                    }
+                    Err(err) => error::print_error(Err::Accept(err).into()),
+                }
+            }
+        });
+
+        Ok(())
+    }
+}
+
+/// Default agent socket path.
+///
+/// Prefers the user runtime directory, falls back to a process specific path in the temporary
+/// directory.
+fn default_socket_path() -> PathBuf {
+    match env::var_os("XDG_RUNTIME_DIR").filter(|d| !d.is_empty()) {
+        Some(dir) => PathBuf::from(dir).join(SOCKET_NAME),
+        None => env::temp_dir().join(format!("prs-ssh-agent.{}.sock", std::process::id())),
+    }
+}
+
+/// Bind the agent socket at the given path, accessible by the current user only.
+///
+/// A stale socket left behind by an earlier agent is replaced.
+fn bind(path: &Path) -> Result<UnixListener> {
+    if let Ok(metadata) = fs::symlink_metadata(path) {
+        if !metadata.file_type().is_socket() {
+            return Err(Err::NotSocket(path.to_path_buf()).into());
+        }
+        fs::remove_file(path).map_err(|err| Err::Bind(path.to_path_buf(), err))?;
+    }
+
+    let listener = UnixListener::bind(path).map_err(|err| Err::Bind(path.to_path_buf(), err))?;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+        .map_err(|err| Err::Bind(path.to_path_buf(), err))?;
+    Ok(listener)
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("no secret selected")]
+    NoneSelected,
+
+    #[error("failed to read secret")]
+    Read(#[source] anyhow::Error),
+
+    #[error("failed to load SSH key from secret '{0}'")]
+    Key(String, #[source] anyhow::Error),
+
+    #[error("refusing to replace non-socket file at agent socket path: {0}")]
+    NotSocket(PathBuf),
+
+    #[error("failed to bind agent socket at: {0}")]
+    Bind(PathBuf, #[source] std::io::Error),
+
+    #[error("failed to accept SSH agent client")]
+    Accept(#[source] std::io::Error),
+}
@@ -12,14 +12,13 @@ use thiserror::Error;
 use crate::util::tomb;
 
 use prs_lib::{
-    Store, crypto,
+    Store,
     sync::{Readyness, Sync as StoreSync},
 };
 
 use crate::{
     cmd::matcher::{MainMatcher, Matcher, sync::SyncMatcher},
     util::{
-        cli,
         error::{self, ErrorHintsBuilder},
         sync,
     },
@@ -83,12 +82,10 @@ impl<'a> Sync<'a> {
                         .unwrap(),
                 );
             }
-            _ if !sync.has_remote()? => {
-                if !matcher_main.quiet() {
-                    error::print_warning(
-                        "no sync remote configured, set using: prs sync remote <GIT_URL>",
-                    );
-                }
+            _ if !sync.has_remote()? && !matcher_main.quiet() => {
+                error::print_warning(
+                    "no sync remote configured, set using: prs sync remote <GIT_URL>",
+                );
             }
             _ => {}
         }
@@ -113,15 +110,7 @@ impl<'a> Sync<'a> {
         }
 
         // Import new keys
-        let confirm_callback = |fingerprint| {
-            matcher_main.force()
-                || cli::prompt_yes(
-                    &format!("Import recipient key {fingerprint} into keychain?"),
-                    Some(true),
-                    &matcher_main,
-                )
-        };
-        crypto::store::import_missing_keys_from_store(&store, confirm_callback)
+        crate::action::housekeeping::sync_keys::import_missing_keys(&store, &matcher_main)
             .map_err(Err::ImportRecipients)?;
 
         // TODO: assert not-dirty state?
@@ -61,16 +61,31 @@ impl<'a> Remote<'a> {
             );
         }
 
-        // Get or set remote
-        let remotes = sync.tracked_remote_or_remotes()?;
+        // Get or set remote, use given remote name if set
+        let remotes = match matcher_remote.name() {
+            Some(name) => {
+                if matcher_remote.git_url().is_none() && !sync.remotes()?.contains(name) {
+                    return Err(Err::UnknownRemote(name.into()).into());
+                }
+                vec![name.into()]
+            }
+            None => sync.tracked_remote_or_remotes()?,
+        };
         match matcher_remote.git_url() {
             Some(url) => {
                 match remotes.len() {
                     0 => sync.add_remote_url(DEFAULT_GIT_REMOTE_NAME, url)?,
+                    1 if !sync.remotes()?.contains(&remotes[0]) => {
+                        sync.add_remote_url(&remotes[0], url)?
+                    }
                     1 => sync.set_remote_url(&remotes[0], url)?,
                     _ => error::quit_error_msg(
                         "multiple remotes configured, cannot set automatically",
                         ErrorHintsBuilder::from_matcher(&matcher_main)
+                            .add_info(format!(
+                                "use '{}' to select a remote",
+                                style::highlight("--name <REMOTE>")
+                            ))
                             .git(true)
                             .build()
                             .unwrap(),
@@ -100,6 +115,10 @@ impl<'a> Remote<'a> {
                 _ => error::quit_error_msg(
                     "multiple remotes configured, cannot decide automatically",
                     ErrorHintsBuilder::from_matcher(&matcher_main)
+                        .add_info(format!(
+                            "use '{}' to select a remote",
+                            style::highlight("--name <REMOTE>")
+                        ))
                         .git(true)
                         .build()
                         .unwrap(),
@@ -123,4 +142,7 @@ pub enum Err {
     #[cfg(all(feature = "tomb", target_os = "linux"))]
     #[error("failed to prepare password store tomb for usage")]
     Tomb(#[source] anyhow::Error),
+
+    #[error("git remote '{}' does not exist", _0)]
+    UnknownRemote(String),
 }
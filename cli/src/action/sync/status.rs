@@ -13,7 +13,10 @@ use crate::{
         MainMatcher, Matcher,
         sync::{SyncMatcher, status::StatusMatcher},
     },
-    util::style::highlight,
+    util::{
+        output::{self, SyncStatusOutput},
+        style::highlight,
+    },
 };
 
 /// A sync status action.
@@ -47,8 +50,21 @@ impl<'a> Status<'a> {
         #[cfg(all(feature = "tomb", target_os = "linux"))]
         tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
 
-        // Show state
         let readyness = sync.readyness()?;
+
+        // Output JSON
+        if matcher_main.json() {
+            let status = SyncStatusOutput::new(&sync, &readyness).map_err(Err::Status)?;
+            output::print_json(&status).map_err(Err::Output)?;
+
+            // Finalize tomb
+            #[cfg(all(feature = "tomb", target_os = "linux"))]
+            tomb::finalize_tomb(&mut tomb, &matcher_main, true).map_err(Err::Tomb)?;
+
+            return Ok(());
+        }
+
+        // Show state
         let state_msg = match readyness {
             Readyness::NoSync => "not enabled".into(),
             Readyness::Ready => "ok".into(),
@@ -168,4 +184,10 @@ pub enum Err {
 
     #[error("failed to list changed files")]
     ChangedFiles(#[source] anyhow::Error),
+
+    #[error("failed to query sync status")]
+    Status(#[source] anyhow::Error),
+
+    #[error("failed to print sync status as JSON")]
+    Output(#[source] std::io::Error),
 }
@@ -0,0 +1,111 @@
+use anyhow::Result;
+use clap::ArgMatches;
+use prs_lib::Store;
+use thiserror::Error;
+
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+use crate::{
+    cmd::matcher::{
+        MainMatcher, Matcher,
+        tag::{TagMatcher, add::AddMatcher},
+    },
+    util::{select, sync},
+};
+
+/// A tag add action.
+pub struct Add<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> Add<'a> {
+    /// Construct a new add action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the add action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let _matcher_tag = TagMatcher::with(self.cmd_matches).unwrap();
+        let matcher_add = AddMatcher::with(self.cmd_matches).unwrap();
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+        let sync = store.sync();
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        // Prepare sync
+        sync::ensure_ready(&sync, matcher_add.allow_dirty());
+        if !matcher_add.no_sync() {
+            sync.prepare()?;
+        }
+
+        let secret = select::store_select_secret(&store, matcher_add.query(), &matcher_main)
+            .ok_or(Err::NoneSelected)?;
+
+        // Add tags to index
+        let mut tags = store.tags().map_err(Err::Tags)?;
+        let mut added = vec![];
+        for tag in matcher_add.tags() {
+            if tags.add(&secret, tag).map_err(Err::Add)? {
+                added.push(tag.to_lowercase());
+            }
+        }
+
+        if added.is_empty() {
+            if !matcher_main.quiet() {
+                eprintln!("Secret already has all given tags");
+            }
+            return Ok(());
+        }
+        tags.save().map_err(Err::Tags)?;
+
+        // Finalize sync
+        if !matcher_add.no_sync() {
+            sync.finalize(format!(
+                "Tag secret {} with {}",
+                secret.name,
+                added.join(", ")
+            ))?;
+        }
+
+        // Finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, true).map_err(Err::Tomb)?;
+
+        if !matcher_main.quiet() {
+            eprintln!("Tags added");
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("no secret selected")]
+    NoneSelected,
+
+    #[error("failed to add tag")]
+    Add(#[source] anyhow::Error),
+
+    #[error("failed to update secret tags")]
+    Tags(#[source] anyhow::Error),
+}
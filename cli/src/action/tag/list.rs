@@ -0,0 +1,112 @@
+use anyhow::Result;
+use clap::ArgMatches;
+use prs_lib::Store;
+use thiserror::Error;
+
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+use crate::{
+    cmd::matcher::{
+        MainMatcher, Matcher,
+        tag::{TagMatcher, list::ListMatcher},
+    },
+    util::{
+        output::{self, TagOutput},
+        select,
+    },
+};
+
+/// A tag list action.
+pub struct List<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> List<'a> {
+    /// Construct a new list action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the list action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let _matcher_tag = TagMatcher::with(self.cmd_matches).unwrap();
+        let matcher_list = ListMatcher::with(self.cmd_matches).unwrap();
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        let tags = store.tags().map_err(Err::Tags)?;
+
+        // List tags of selected secret, or all tags with their secrets
+        let list: Vec<TagOutput> = match matcher_list.query() {
+            Some(query) => {
+                let secret = select::store_select_secret_read(&store, Some(query), &matcher_main)
+                    .ok_or(Err::NoneSelected)?;
+                tags.get(&secret)
+                    .into_iter()
+                    .map(|tag| TagOutput {
+                        tag: tag.into(),
+                        secrets: vec![secret.name.clone()],
+                    })
+                    .collect()
+            }
+            None => tags
+                .all()
+                .into_iter()
+                .map(|tag| TagOutput {
+                    tag: tag.into(),
+                    secrets: tags
+                        .secrets_with(tag)
+                        .into_iter()
+                        .map(|name| name.into())
+                        .collect(),
+                })
+                .collect(),
+        };
+
+        if matcher_main.json() {
+            output::print_json(&list).map_err(Err::Output)?;
+        } else if matcher_main.verbose() {
+            list.iter()
+                .for_each(|tag| println!("{}: {}", tag.tag, tag.secrets.join(", ")));
+        } else {
+            list.iter().for_each(|tag| println!("{}", tag.tag));
+        }
+
+        // Finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, false).map_err(Err::Tomb)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("no secret selected")]
+    NoneSelected,
+
+    #[error("failed to load secret tags")]
+    Tags(#[source] anyhow::Error),
+
+    #[error("failed to print tags as JSON")]
+    Output(#[source] std::io::Error),
+}
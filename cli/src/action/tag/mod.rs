@@ -0,0 +1,41 @@
+pub mod add;
+pub mod list;
+pub mod remove;
+
+use anyhow::Result;
+use clap::ArgMatches;
+
+use crate::cmd::matcher::{Matcher, tag::TagMatcher};
+
+/// Tag action.
+pub struct Tag<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> Tag<'a> {
+    /// Construct a new tag action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the tag action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_tag = TagMatcher::with(self.cmd_matches).unwrap();
+
+        if matcher_tag.cmd_add().is_some() {
+            return add::Add::new(self.cmd_matches).invoke();
+        }
+
+        if matcher_tag.cmd_list().is_some() {
+            return list::List::new(self.cmd_matches).invoke();
+        }
+
+        if matcher_tag.cmd_remove().is_some() {
+            return remove::Remove::new(self.cmd_matches).invoke();
+        }
+
+        // Unreachable, clap will print help for missing sub command instead
+        unreachable!()
+    }
+}
@@ -0,0 +1,108 @@
+use anyhow::Result;
+use clap::ArgMatches;
+use prs_lib::Store;
+use thiserror::Error;
+
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+use crate::{
+    cmd::matcher::{
+        MainMatcher, Matcher,
+        tag::{TagMatcher, remove::RemoveMatcher},
+    },
+    util::{select, sync},
+};
+
+/// A tag remove action.
+pub struct Remove<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> Remove<'a> {
+    /// Construct a new remove action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the remove action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let _matcher_tag = TagMatcher::with(self.cmd_matches).unwrap();
+        let matcher_remove = RemoveMatcher::with(self.cmd_matches).unwrap();
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+        let sync = store.sync();
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        // Prepare sync
+        sync::ensure_ready(&sync, matcher_remove.allow_dirty());
+        if !matcher_remove.no_sync() {
+            sync.prepare()?;
+        }
+
+        let secret = select::store_select_secret(&store, matcher_remove.query(), &matcher_main)
+            .ok_or(Err::NoneSelected)?;
+
+        // Remove tags from index
+        let mut tags = store.tags().map_err(Err::Tags)?;
+        let removed: Vec<&str> = matcher_remove
+            .tags()
+            .into_iter()
+            .filter(|tag| tags.remove(&secret, tag))
+            .map(|tag| tag.as_str())
+            .collect();
+
+        if removed.is_empty() {
+            if !matcher_main.quiet() {
+                eprintln!("Secret has none of the given tags");
+            }
+            return Ok(());
+        }
+        tags.save().map_err(Err::Tags)?;
+
+        // Finalize sync
+        if !matcher_remove.no_sync() {
+            sync.finalize(format!(
+                "Remove tags {} from secret {}",
+                removed.join(", "),
+                secret.name,
+            ))?;
+        }
+
+        // Finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, true).map_err(Err::Tomb)?;
+
+        if !matcher_main.quiet() {
+            eprintln!("Tags removed");
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("no secret selected")]
+    NoneSelected,
+
+    #[error("failed to update secret tags")]
+    Tags(#[source] anyhow::Error),
+}
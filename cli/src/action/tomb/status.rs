@@ -8,6 +8,7 @@ use crate::cmd::matcher::{
     MainMatcher, Matcher,
     tomb::{TombMatcher, status::StatusMatcher},
 };
+use crate::util::output::{self, TombStatusOutput};
 
 /// A tomb status action.
 pub struct Status<'a> {
@@ -36,7 +37,11 @@ impl<'a> Status<'a> {
 
         let is_tomb = tomb.is_tomb();
         if !is_tomb {
-            eprintln!("Tomb: no");
+            if matcher_main.json() {
+                output::print_json(&TombStatusOutput::default()).map_err(Err::Output)?;
+            } else {
+                eprintln!("Tomb: no");
+            }
             return Ok(());
         }
 
@@ -55,6 +60,21 @@ impl<'a> Status<'a> {
         let tomb_key_path = tomb.find_tomb_key_path().unwrap();
         let sizes = tomb.fetch_size_stats().map_err(Err::Size)?;
 
+        // Output JSON
+        if matcher_main.json() {
+            let status = TombStatusOutput {
+                tomb: true,
+                open: Some(is_open),
+                close_timer: Some(has_timer),
+                tomb_path: Some(tomb_path.display().to_string()),
+                tomb_key_path: Some(tomb_key_path.display().to_string()),
+                store_size: sizes.store,
+                tomb_file_size: sizes.tomb_file,
+            };
+            output::print_json(&status).map_err(Err::Output)?;
+            return Ok(());
+        }
+
         println!("Tomb: yes");
         println!("Open: {}", if is_open { "yes" } else { "no" });
         println!("Close timer: {}", if has_timer { "active" } else { "no" });
@@ -92,4 +112,7 @@ pub enum Err {
 
     #[error("failed to fetch password store size status")]
     Size(#[source] anyhow::Error),
+
+    #[error("failed to print tomb status as JSON")]
+    Output(#[source] std::io::Error),
 }
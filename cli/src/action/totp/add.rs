@@ -0,0 +1,163 @@
+use std::fs;
+use std::io::{self, Read};
+
+use anyhow::Result;
+use clap::ArgMatches;
+use prs_lib::{Plaintext, Secret, Store, crypto::prelude::*};
+use thiserror::Error;
+
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+use crate::{
+    cmd::matcher::{
+        MainMatcher, Matcher,
+        totp::{TotpMatcher, add::AddMatcher},
+    },
+    util::{cli, error, search, sync, totp},
+};
+
+/// Property to store TOTP URLs in.
+const PROPERTY: &str = "totp";
+
+/// A TOTP add action.
+pub struct Add<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> Add<'a> {
+    /// Construct a new add action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the add action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let _matcher_totp = TotpMatcher::with(self.cmd_matches).unwrap();
+        let matcher_add = AddMatcher::with(self.cmd_matches).unwrap();
+
+        // Read and decode QR code image before touching the store
+        let mut image = vec![];
+        if matcher_add.qr_stdin() {
+            io::stdin()
+                .read_to_end(&mut image)
+                .map_err(Err::ReadImage)?;
+        } else if let Some(path) = matcher_add.qr() {
+            image = fs::read(path).map_err(Err::ReadImage)?;
+        }
+        let url = totp::decode_qr(&image).map_err(Err::Qr)?;
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+        let sync = store.sync();
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        // Prepare sync
+        sync::ensure_ready(&sync, matcher_add.allow_dirty());
+        if !matcher_add.no_sync() {
+            sync.prepare().map_err(Err::Store)?;
+        }
+
+        // Normalize destination path
+        let path = store
+            .normalize_secret_path(matcher_add.name(), None, true)
+            .map_err(Err::NormalizePath)?;
+        let secret = Secret::from(&store, path.clone());
+        let exists = path.is_file();
+
+        let mut context = crate::crypto::context(&matcher_main)?;
+
+        // Load existing secret, confirm to replace current TOTP property
+        let mut plaintext = if exists {
+            context.decrypt_file(&path).map_err(Err::Read)?
+        } else {
+            Plaintext::empty()
+        };
+        if plaintext.is_binary() {
+            return Err(Err::Binary.into());
+        }
+        if exists
+            && !matcher_main.force()
+            && plaintext.property(PROPERTY).is_ok()
+            && !cli::prompt_yes(
+                &format!("Secret already has a {PROPERTY} property. Replace?"),
+                Some(true),
+                &matcher_main,
+            )
+        {
+            if matcher_main.verbose() {
+                eprintln!("Addition cancelled");
+            }
+            error::quit();
+        }
+
+        plaintext
+            .set_property(PROPERTY, url)
+            .map_err(Err::Property)?;
+        context
+            .encrypt_file(&store.recipients_for(&secret)?, plaintext.clone(), &path)
+            .map_err(Err::Write)?;
+        search::update(&store, &matcher_main, |index| {
+            index.update(&secret, &plaintext).map(|_| true)
+        });
+
+        // Finalize sync
+        if !matcher_add.no_sync() {
+            sync.finalize(format!("Add TOTP to secret {}", secret.name))?;
+        }
+
+        // Finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, true).map_err(Err::Tomb)?;
+
+        if !matcher_main.quiet() {
+            if exists {
+                eprintln!("TOTP added to secret");
+            } else {
+                eprintln!("Secret added with TOTP");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("failed to read QR code image")]
+    ReadImage(#[source] std::io::Error),
+
+    #[error("failed to get TOTP secret from QR code")]
+    Qr(#[source] anyhow::Error),
+
+    #[error("failed to normalize destination path")]
+    NormalizePath(#[source] anyhow::Error),
+
+    #[error("failed to read secret")]
+    Read(#[source] anyhow::Error),
+
+    #[error("cannot edit binary secret as text, use attach or extract instead")]
+    Binary,
+
+    #[error("failed to add TOTP property to secret")]
+    Property(#[source] anyhow::Error),
+
+    #[error("failed to write changed secret")]
+    Write(#[source] anyhow::Error),
+}
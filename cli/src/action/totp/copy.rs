@@ -44,7 +44,7 @@ impl<'a> Copy<'a> {
         #[cfg(all(feature = "tomb", target_os = "linux"))]
         tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
 
-        let secret = select::store_select_secret(&store, matcher_copy.query(), &matcher_main)
+        let secret = select::store_select_secret_read(&store, matcher_copy.query(), &matcher_main)
             .ok_or(Err::NoneSelected)?;
 
         secret::print_name(matcher_copy.query(), &secret, &store, matcher_main.quiet());
@@ -69,8 +69,8 @@ impl<'a> Copy<'a> {
         let mut copied = false;
 
         // Use background token recopy implementation if token changes within timeout
-        let ttl = totp.ttl().map_err(Err::Totp)?;
-        if timeout > ttl && !matcher_copy.no_recopy() {
+        if !totp.is_hotp() && timeout > totp.ttl().map_err(Err::Totp)? && !matcher_copy.no_recopy()
+        {
             match totp::spawn_process_totp_recopy(&totp, timeout) {
                 Ok(_) => {
                     if !matcher_main.quiet() {
@@ -84,8 +84,23 @@ impl<'a> Copy<'a> {
 
         // Fall back to simply copy
         if !copied {
+            // Generate token, HOTP tokens advance the counter in the secret
+            let token = if totp.is_hotp() {
+                totp::generate_hotp(
+                    &store,
+                    &secret,
+                    matcher_copy.property(),
+                    &matcher_main,
+                    matcher_copy.allow_dirty(),
+                    matcher_copy.no_sync(),
+                )
+                .map_err(Err::Totp)?
+            } else {
+                totp.generate_current().map_err(Err::Totp)?
+            };
+
             clipboard::copy_plaintext(
-                totp.generate_current().map_err(Err::Totp)?,
+                token,
                 false,
                 true,
                 matcher_main.quiet(),
@@ -99,7 +114,7 @@ impl<'a> Copy<'a> {
 
         // Finalize tomb
         #[cfg(all(feature = "tomb", target_os = "linux"))]
-        tomb::finalize_tomb(&mut tomb, &matcher_main, false).map_err(Err::Tomb)?;
+        tomb::finalize_tomb(&mut tomb, &matcher_main, totp.is_hotp()).map_err(Err::Tomb)?;
 
         Ok(())
     }
@@ -0,0 +1,669 @@
+use std::io::{self, Write};
+
+use aes_gcm::{Aes256Gcm, KeyInit, aead::Aead};
+use anyhow::Result;
+use clap::ArgMatches;
+use prs_lib::{Plaintext, Secret, Store, crypto::prelude::*};
+use rand::RngCore;
+use serde_json::{Value, json};
+use thiserror::Error;
+
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+use crate::{
+    cmd::matcher::{
+        MainMatcher, Matcher,
+        totp::{
+            TotpMatcher,
+            export::{ExportFormat, ExportMatcher},
+        },
+    },
+    util::{
+        self, base64, cli,
+        error::{self, ErrorHintsBuilder},
+        progress::{self, ProgressBarExt},
+        totp::{self, Totp},
+    },
+};
+
+/// Maximum number of secrets in a single Google Authenticator migration QR code.
+const GOOGLE_BATCH_SIZE: usize = 10;
+
+/// Aegis scrypt cost parameter, as log2 of N.
+const AEGIS_SCRYPT_LOG_N: u8 = 15;
+
+/// Aegis scrypt block size parameter.
+const AEGIS_SCRYPT_R: u32 = 8;
+
+/// Aegis scrypt parallelization parameter.
+const AEGIS_SCRYPT_P: u32 = 1;
+
+/// A TOTP export action.
+pub struct Export<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> Export<'a> {
+    /// Construct a new export action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the export action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let _matcher_totp = TotpMatcher::with(self.cmd_matches).unwrap();
+        let matcher_export = ExportMatcher::with(self.cmd_matches).unwrap();
+        let format = matcher_export.format();
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+
+        // Check if destination already exists if not forcing
+        let mut overwrite = matcher_main.force();
+        if let Some(path) = matcher_export.out()
+            && !overwrite
+            && path.exists()
+        {
+            eprintln!("A file at '{}' already exists", path.display());
+            if !cli::prompt_yes("Overwrite?", Some(true), &matcher_main) {
+                if matcher_main.verbose() {
+                    eprintln!("Export cancelled");
+                }
+                error::quit();
+            }
+            overwrite = true;
+        }
+
+        // Ask for Aegis vault password early
+        let password = match format {
+            ExportFormat::Aegis => Some(prompt_password(&matcher_main)?),
+            _ => None,
+        };
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        let mut secrets: Vec<Secret> = store.secret_iter().collect();
+        secrets.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+
+        let tokens = collect(&secrets, &matcher_main)?;
+        if tokens.is_empty() {
+            return Err(Err::NoTotp.into());
+        }
+
+        // Build export in selected format
+        let output = match format {
+            ExportFormat::Uri => export_uri(&tokens),
+            ExportFormat::Aegis => export_aegis(&tokens, &password.unwrap())?,
+            ExportFormat::AndOtp => export_andotp(&tokens, &matcher_main),
+            ExportFormat::Google => export_google(&tokens, &matcher_main),
+        };
+
+        // Write to file, or stdout with Google QR codes
+        match matcher_export.out() {
+            Some(path) => util::fs::create_private(path, overwrite)
+                .and_then(|mut file| file.write_all(output.unsecure_ref()))
+                .map_err(Err::Write)?,
+            None if format == ExportFormat::Google && !matcher_main.quiet() => {
+                let text = output.unsecure_to_str().unwrap_or_default();
+                for (i, url) in text.lines().enumerate() {
+                    println!("QR code {} of {}:", i + 1, text.lines().count());
+                    qr2term::print_qr(url).map_err(Err::Qr)?;
+                }
+            }
+            None => {
+                let mut stdout = io::stdout().lock();
+                stdout
+                    .write_all(output.unsecure_ref())
+                    .and_then(|_| stdout.flush())
+                    .map_err(Err::Write)?;
+            }
+        }
+
+        // Finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, false).map_err(Err::Tomb)?;
+
+        if !matcher_main.quiet() {
+            eprintln!("Exported {} TOTP secrets", tokens.len());
+            if matches!(format, ExportFormat::Uri | ExportFormat::AndOtp) {
+                error::print_warning("exported secrets are not encrypted, handle them with care");
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Collect TOTP secrets from all given secrets, labelled with the secret name.
+fn collect(secrets: &[Secret], matcher_main: &MainMatcher) -> Result<Vec<(String, Totp)>> {
+    let mut context = crate::crypto::context(matcher_main)?;
+    let mut tokens = vec![];
+
+    let pb = progress::progress_bar(secrets.len() as u64, matcher_main.quiet());
+    for secret in secrets {
+        pb.set_message_trunc(&secret.name);
+
+        match context
+            .decrypt_file(&secret.path)
+            .map_err(Err::Read)
+            .and_then(|plaintext| totp::find_token(&plaintext).transpose().map_err(Err::Parse))
+        {
+            Ok(Some(totp)) => tokens.push((secret.name.clone(), totp)),
+            Ok(None) => {}
+            Err(err) => {
+                pb.suspend(|| {
+                    error::print_error(
+                        anyhow::Error::from(err).context(format!("skipped: {}", secret.name)),
+                    )
+                });
+            }
+        }
+
+        pb.inc(1);
+    }
+    pb.finish_and_clear();
+
+    Ok(tokens)
+}
+
+/// Export as list of otpauth URIs.
+fn export_uri(tokens: &[(String, Totp)]) -> Plaintext {
+    let mut output = Plaintext::empty();
+    for (name, totp) in tokens {
+        output.append(totp.generate_url_with_label(name), false);
+        output.append("\n".into(), false);
+    }
+    output
+}
+
+/// Export as encrypted Aegis vault.
+///
+/// See: https://github.com/beemdevelopment/Aegis/blob/master/docs/vault.md
+fn export_aegis(tokens: &[(String, Totp)], password: &Plaintext) -> Result<Plaintext> {
+    let entries: Vec<Value> = tokens
+        .iter()
+        .filter_map(|(name, totp)| {
+            let mut info = json!({
+                "secret": totp.secret_base32()?,
+                "algo": totp.algorithm(),
+                "digits": totp.digits(),
+            });
+            match (totp.period(), totp.counter()) {
+                (_, Some(counter)) => info["counter"] = counter.into(),
+                (Some(period), _) => info["period"] = period.into(),
+                _ => {}
+            }
+            if let Some(pin) = totp.pin() {
+                info["pin"] = pin.unsecure_to_str().ok()?.into();
+            }
+
+            Some(json!({
+                "type": totp.kind(),
+                "uuid": uuid(),
+                "name": name,
+                "issuer": totp.issuer().unwrap_or_default(),
+                "note": "",
+                "favorite": false,
+                "icon": null,
+                "info": info,
+            }))
+        })
+        .collect();
+    let db = Plaintext::from(json!({"version": 2, "entries": entries}).to_string());
+
+    // Derive key from password, to encrypt random master key
+    let mut salt = [0u8; 32];
+    let mut key = [0u8; 32];
+    let mut master_key = [0u8; 32];
+    rand::rng().fill_bytes(&mut salt);
+    rand::rng().fill_bytes(&mut master_key);
+    let params = scrypt::Params::new(AEGIS_SCRYPT_LOG_N, AEGIS_SCRYPT_R, AEGIS_SCRYPT_P, 32)
+        .map_err(|_| Err::Encrypt)?;
+    scrypt::scrypt(password.unsecure_ref(), &salt, &params, &mut key).map_err(|_| Err::Encrypt)?;
+
+    let (key_ciphertext, key_nonce, key_tag) = aes_gcm_encrypt(&key, &master_key)?;
+    let (db_ciphertext, db_nonce, db_tag) = aes_gcm_encrypt(&master_key, db.unsecure_ref())?;
+
+    let vault = json!({
+        "version": 1,
+        "header": {
+            "slots": [{
+                "type": 1,
+                "uuid": uuid(),
+                "key": hex(&key_ciphertext),
+                "key_params": {
+                    "nonce": hex(&key_nonce),
+                    "tag": hex(&key_tag),
+                },
+                "n": 1u32 << AEGIS_SCRYPT_LOG_N,
+                "r": AEGIS_SCRYPT_R,
+                "p": AEGIS_SCRYPT_P,
+                "salt": hex(&salt),
+                "repaired": true,
+                "is_backup": false,
+            }],
+            "params": {
+                "nonce": hex(&db_nonce),
+                "tag": hex(&db_tag),
+            },
+        },
+        "db": base64::encode(db_ciphertext),
+    });
+
+    Ok(vault.to_string().into())
+}
+
+/// Export as plain andOTP JSON backup.
+///
+/// andOTP does not support Yandex.Key and Mobile-OTP secrets, these are skipped.
+fn export_andotp(tokens: &[(String, Totp)], matcher_main: &MainMatcher) -> Plaintext {
+    let entries: Vec<Value> = tokens
+        .iter()
+        .filter_map(|(name, totp)| {
+            let kind = match totp.kind() {
+                kind @ ("totp" | "hotp" | "steam") => kind.to_uppercase(),
+                _ => return skip(name, "andOTP", matcher_main),
+            };
+
+            let mut entry = json!({
+                "secret": totp.secret_base32()?,
+                "issuer": totp.issuer().unwrap_or_default(),
+                "label": name,
+                "digits": totp.digits(),
+                "type": kind,
+                "algorithm": totp.algorithm(),
+                "thumbnail": "Default",
+                "last_used": 0,
+                "used_frequency": 0,
+                "tags": [],
+            });
+            match (totp.period(), totp.counter()) {
+                (_, Some(counter)) => entry["counter"] = counter.into(),
+                (Some(period), _) => entry["period"] = period.into(),
+                _ => {}
+            }
+            Some(entry)
+        })
+        .collect();
+
+    Value::Array(entries).to_string().into()
+}
+
+/// Export as Google Authenticator migration URLs, one per line.
+///
+/// Google Authenticator only supports standard TOTP and HOTP secrets with 6 or 8 digits and a 30
+/// second period, other secrets are skipped.
+fn export_google(tokens: &[(String, Totp)], matcher_main: &MainMatcher) -> Plaintext {
+    let params: Vec<Vec<u8>> = tokens
+        .iter()
+        .filter_map(|(name, totp)| {
+            let supported = matches!(totp.kind(), "totp" | "hotp")
+                && matches!(totp.digits(), 6 | 8)
+                && totp.period().unwrap_or(30) == 30;
+            if !supported {
+                return skip(name, "Google Authenticator", matcher_main);
+            }
+
+            // Encode OtpParameters protobuf message
+            let mut message = vec![];
+            protobuf_bytes(&mut message, 1, &totp.secret()?);
+            protobuf_bytes(&mut message, 2, name.as_bytes());
+            protobuf_bytes(
+                &mut message,
+                3,
+                totp.issuer().unwrap_or_default().as_bytes(),
+            );
+            let algorithm = match totp.algorithm() {
+                "SHA256" => 2,
+                "SHA512" => 3,
+                _ => 1,
+            };
+            protobuf_varint_field(&mut message, 4, algorithm);
+            protobuf_varint_field(&mut message, 5, if totp.digits() == 8 { 2 } else { 1 });
+            match totp.counter() {
+                Some(counter) => {
+                    protobuf_varint_field(&mut message, 6, 1);
+                    protobuf_varint_field(&mut message, 7, counter);
+                }
+                None => protobuf_varint_field(&mut message, 6, 2),
+            }
+            Some(message)
+        })
+        .collect();
+
+    // Encode MigrationPayload protobuf messages in batches
+    let batch_id = rand::rng().next_u32() >> 1;
+    let batches = params.chunks(GOOGLE_BATCH_SIZE);
+    let batch_size = batches.len() as u64;
+    let mut output = Plaintext::empty();
+    for (i, batch) in batches.enumerate() {
+        let mut payload = vec![];
+        for message in batch {
+            protobuf_bytes(&mut payload, 1, message);
+        }
+        protobuf_varint_field(&mut payload, 2, 1);
+        protobuf_varint_field(&mut payload, 3, batch_size);
+        protobuf_varint_field(&mut payload, 4, i as u64);
+        protobuf_varint_field(&mut payload, 5, batch_id.into());
+
+        output.append(
+            format!(
+                "otpauth-migration://offline?data={}\n",
+                totp::url_encode(&base64::encode(payload))
+            )
+            .into(),
+            false,
+        );
+    }
+    output
+}
+
+/// Report a secret that is skipped because the export format does not support it.
+fn skip<T>(name: &str, format: &str, matcher_main: &MainMatcher) -> Option<T> {
+    if !matcher_main.quiet() {
+        error::print_warning(format!(
+            "skipped {name}, TOTP type not supported by {format}"
+        ));
+    }
+    None
+}
+
+/// Append a length delimited protobuf field.
+fn protobuf_bytes(buf: &mut Vec<u8>, field: u64, data: &[u8]) {
+    protobuf_varint(buf, (field << 3) | 2);
+    protobuf_varint(buf, data.len() as u64);
+    buf.extend_from_slice(data);
+}
+
+/// Append a varint protobuf field.
+fn protobuf_varint_field(buf: &mut Vec<u8>, field: u64, value: u64) {
+    protobuf_varint(buf, field << 3);
+    protobuf_varint(buf, value);
+}
+
+/// Append a protobuf varint.
+fn protobuf_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+/// Encrypt data with AES-256-GCM using a random nonce.
+///
+/// Returns the ciphertext, nonce and tag.
+fn aes_gcm_encrypt(key: &[u8], data: &[u8]) -> Result<(Vec<u8>, [u8; 12], Vec<u8>)> {
+    let mut nonce = [0u8; 12];
+    rand::rng().fill_bytes(&mut nonce);
+
+    let cipher = Aes256Gcm::new_from_slice(key).map_err(|_| Err::Encrypt)?;
+    let mut ciphertext = cipher
+        .encrypt(&nonce.into(), data)
+        .map_err(|_| Err::Encrypt)?;
+    let tag = ciphertext.split_off(ciphertext.len() - 16);
+
+    Ok((ciphertext, nonce, tag))
+}
+
+/// Encode bytes as lowercase hexadecimal.
+fn hex(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Generate a random version 4 UUID.
+fn uuid() -> String {
+    let mut bytes = [0u8; 16];
+    rand::rng().fill_bytes(&mut bytes);
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex = hex(&bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+/// Prompt for a new password to encrypt the export with.
+fn prompt_password(matcher_main: &MainMatcher) -> Result<Plaintext> {
+    if matcher_main.no_interact() {
+        error::quit_error_msg(
+            "vault password required, cannot prompt in non-interactive mode",
+            ErrorHintsBuilder::from_matcher(matcher_main)
+                .build()
+                .unwrap(),
+        );
+    }
+
+    let password: Plaintext = rpassword::prompt_password("Vault password: ")
+        .map_err(Err::Password)?
+        .into();
+    if password.is_empty() {
+        return Err(Err::EmptyPassword.into());
+    }
+    let confirm: Plaintext = rpassword::prompt_password("Confirm vault password: ")
+        .map_err(Err::Password)?
+        .into();
+    if password != confirm {
+        return Err(Err::PasswordMismatch.into());
+    }
+
+    Ok(password)
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("no TOTP secrets found in store")]
+    NoTotp,
+
+    #[error("failed to read secret")]
+    Read(#[source] anyhow::Error),
+
+    #[error("failed to parse TOTP secret")]
+    Parse(#[source] anyhow::Error),
+
+    #[error("failed to read vault password")]
+    Password(#[source] io::Error),
+
+    #[error("vault password must not be empty")]
+    EmptyPassword,
+
+    #[error("vault passwords do not match")]
+    PasswordMismatch,
+
+    #[error("failed to encrypt vault")]
+    Encrypt,
+
+    #[error("failed to write export")]
+    Write(#[source] io::Error),
+
+    #[error("failed to render QR code")]
+    Qr(#[source] qr2term::QrError),
+}
+
+#[cfg(test)]
+mod tests {
+    use aes_gcm::aead::Payload;
+
+    use super::*;
+    use crate::cmd::handler::Handler;
+
+    /// RFC 6238 test secret, base32 encoded.
+    const SECRET: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    /// Raw bytes of the RFC 6238 test secret.
+    const SECRET_BYTES: &[u8] = b"12345678901234567890";
+
+    fn tokens() -> Vec<(String, Totp)> {
+        [
+            (
+                "site",
+                format!("otpauth://totp/x?secret={SECRET}&issuer=Example"),
+            ),
+            (
+                "counter",
+                format!("otpauth://hotp/x?secret={SECRET}&digits=8&counter=5"),
+            ),
+            (
+                "steam",
+                format!("otpauth://totp/x?secret={SECRET}&issuer=Steam&algorithm=STEAM"),
+            ),
+        ]
+        .into_iter()
+        .map(|(name, url)| (name.to_string(), Totp::from_url(&url).unwrap()))
+        .collect()
+    }
+
+    /// Decode a hexadecimal string.
+    fn unhex(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    /// Decrypt AES-256-GCM data as stored in an Aegis vault.
+    fn aegis_decrypt(key: &[u8], data: &[u8], params: &Value) -> Vec<u8> {
+        let mut data = data.to_vec();
+        data.extend(unhex(params["tag"].as_str().unwrap()));
+        let nonce = unhex(params["nonce"].as_str().unwrap());
+        Aes256Gcm::new_from_slice(key)
+            .unwrap()
+            .decrypt(
+                nonce.as_slice().into(),
+                Payload {
+                    msg: &data,
+                    aad: &[],
+                },
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn protobuf_encoding() {
+        let mut buf = vec![];
+        protobuf_varint(&mut buf, 1);
+        protobuf_varint(&mut buf, 300);
+        protobuf_varint(&mut buf, u32::MAX.into());
+        assert_eq!(buf, [0x01, 0xac, 0x02, 0xff, 0xff, 0xff, 0xff, 0x0f]);
+
+        let mut buf = vec![];
+        protobuf_varint_field(&mut buf, 2, 150);
+        protobuf_bytes(&mut buf, 3, b"abc");
+        assert_eq!(buf, [0x10, 0x96, 0x01, 0x1a, 0x03, b'a', b'b', b'c']);
+    }
+
+    #[test]
+    fn google() {
+        let matches = Handler::build().get_matches_from(["prs", "-q", "totp", "export"]);
+        let matcher_main = MainMatcher::with(&matches).unwrap();
+        let output = export_google(&tokens(), &matcher_main);
+
+        // Single migration URL, Steam secret is skipped
+        let output = output.unsecure_to_str().unwrap();
+        assert_eq!(output.lines().count(), 1);
+        let data = output
+            .trim()
+            .strip_prefix("otpauth-migration://offline?data=")
+            .unwrap()
+            .replace("%2B", "+")
+            .replace("%2F", "/")
+            .replace("%3D", "=");
+        let payload = base64::decode(data).unwrap();
+
+        // OtpParameters for TOTP: secret, name, issuer, SHA1, 6 digits, TOTP
+        let mut site = vec![0x0a, 20];
+        site.extend(SECRET_BYTES);
+        site.extend([0x12, 4]);
+        site.extend(b"site");
+        site.extend([0x1a, 7]);
+        site.extend(b"Example");
+        site.extend([0x20, 1, 0x28, 1, 0x30, 2]);
+
+        // OtpParameters for HOTP: secret, name, no issuer, SHA1, 8 digits, HOTP, counter
+        let mut counter = vec![0x0a, 20];
+        counter.extend(SECRET_BYTES);
+        counter.extend([0x12, 7]);
+        counter.extend(b"counter");
+        counter.extend([0x1a, 0]);
+        counter.extend([0x20, 1, 0x28, 2, 0x30, 1, 0x38, 5]);
+
+        // MigrationPayload: parameters, version 1, batch size 1, batch index 0, batch ID
+        let mut expected = vec![0x0a, site.len() as u8];
+        expected.extend(site);
+        expected.extend([0x0a, counter.len() as u8]);
+        expected.extend(counter);
+        expected.extend([0x10, 1, 0x18, 1, 0x20, 0, 0x28]);
+        assert_eq!(payload[..expected.len()], expected);
+    }
+
+    #[test]
+    fn aegis() {
+        let password = Plaintext::from("password");
+        let vault = export_aegis(&tokens(), &password).unwrap();
+        let vault: Value = serde_json::from_str(vault.unsecure_to_str().unwrap()).unwrap();
+        assert_eq!(vault["version"], 1);
+
+        // Derive key from password, decrypt master key from password slot
+        let slot = &vault["header"]["slots"][0];
+        assert_eq!(slot["type"], 1);
+        let log_n = slot["n"].as_u64().unwrap().trailing_zeros() as u8;
+        let params = scrypt::Params::new(
+            log_n,
+            slot["r"].as_u64().unwrap() as u32,
+            slot["p"].as_u64().unwrap() as u32,
+            32,
+        )
+        .unwrap();
+        let mut key = [0u8; 32];
+        let salt = unhex(slot["salt"].as_str().unwrap());
+        scrypt::scrypt(password.unsecure_ref(), &salt, &params, &mut key).unwrap();
+        let master_key = aegis_decrypt(
+            &key,
+            &unhex(slot["key"].as_str().unwrap()),
+            &slot["key_params"],
+        );
+
+        // Decrypt database with master key
+        let db = base64::decode(vault["db"].as_str().unwrap()).unwrap();
+        let db = aegis_decrypt(&master_key, &db, &vault["header"]["params"]);
+        let db: Value = serde_json::from_slice(&db).unwrap();
+        assert_eq!(db["version"], 2);
+
+        let entries = db["entries"].as_array().unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0]["type"], "totp");
+        assert_eq!(entries[0]["name"], "site");
+        assert_eq!(entries[0]["issuer"], "Example");
+        assert_eq!(entries[0]["info"]["secret"], SECRET);
+        assert_eq!(entries[0]["info"]["algo"], "SHA1");
+        assert_eq!(entries[0]["info"]["digits"], 6);
+        assert_eq!(entries[0]["info"]["period"], 30);
+        assert_eq!(entries[1]["type"], "hotp");
+        assert_eq!(entries[1]["info"]["digits"], 8);
+        assert_eq!(entries[1]["info"]["counter"], 5);
+        assert_eq!(entries[2]["type"], "steam");
+        assert_eq!(entries[2]["info"]["digits"], 5);
+    }
+}
@@ -49,7 +49,7 @@ impl<'a> Live<'a> {
         #[cfg(all(feature = "tomb", target_os = "linux"))]
         tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
 
-        let secret = select::store_select_secret(&store, matcher_live.query(), &matcher_main)
+        let secret = select::store_select_secret_read(&store, matcher_live.query(), &matcher_main)
             .ok_or(Err::NoneSelected)?;
 
         secret::print_name(matcher_live.query(), &secret, &store, matcher_main.quiet());
@@ -67,6 +67,9 @@ impl<'a> Live<'a> {
         let totp = totp::find_token(&plaintext)
             .ok_or(Err::NoTotp)?
             .map_err(Err::Totp)?;
+        if totp.is_hotp() {
+            return Err(Err::Hotp.into());
+        }
 
         // Finalize tomb before watching tokens
         #[cfg(all(feature = "tomb", target_os = "linux"))]
@@ -134,4 +137,7 @@ pub enum Err {
 
     #[error("failed to generate TOTP token")]
     Totp(#[source] anyhow::Error),
+
+    #[error("live tokens are not supported for counter based HOTP secrets, use 'prs totp show'")]
+    Hotp,
 }
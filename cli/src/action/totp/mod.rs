@@ -1,5 +1,7 @@
+pub mod add;
 #[cfg(feature = "clipboard")]
 pub mod copy;
+pub mod export;
 pub mod live;
 pub mod qr;
 pub mod show;
@@ -25,11 +27,19 @@ impl<'a> Totp<'a> {
         // Create the command matchers
         let matcher_totp = TotpMatcher::with(self.cmd_matches).unwrap();
 
+        if matcher_totp.cmd_add().is_some() {
+            return add::Add::new(self.cmd_matches).invoke();
+        }
+
         #[cfg(feature = "clipboard")]
         if matcher_totp.cmd_copy().is_some() {
             return copy::Copy::new(self.cmd_matches).invoke();
         }
 
+        if matcher_totp.cmd_export().is_some() {
+            return export::Export::new(self.cmd_matches).invoke();
+        }
+
         if matcher_totp.cmd_live().is_some() {
             return live::Live::new(self.cmd_matches).invoke();
         }
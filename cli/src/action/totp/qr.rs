@@ -43,7 +43,7 @@ impl<'a> Qr<'a> {
         #[cfg(all(feature = "tomb", target_os = "linux"))]
         tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
 
-        let secret = select::store_select_secret(&store, matcher_qr.query(), &matcher_main)
+        let secret = select::store_select_secret_read(&store, matcher_qr.query(), &matcher_main)
             .ok_or(Err::NoneSelected)?;
 
         secret::print_name(matcher_qr.query(), &secret, &store, matcher_main.quiet());
@@ -48,7 +48,7 @@ impl<'a> Show<'a> {
         #[cfg(all(feature = "tomb", target_os = "linux"))]
         tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
 
-        let secret = select::store_select_secret(&store, matcher_show.query(), &matcher_main)
+        let secret = select::store_select_secret_read(&store, matcher_show.query(), &matcher_main)
             .ok_or(Err::NoneSelected)?;
 
         let mut plaintext = crate::crypto::context(&matcher_main)?
@@ -60,12 +60,25 @@ impl<'a> Show<'a> {
             plaintext = plaintext.property(property).map_err(Err::Property)?;
         }
 
-        // Get current TOTP token
+        // Get current TOTP token, HOTP tokens advance the counter in the secret
         let totp = totp::find_token(&plaintext)
             .ok_or(Err::NoTotp)?
             .map_err(Err::Parse)?;
-        let token = totp.generate_current().map_err(Err::Totp)?;
-        let ttl = totp.ttl().map_err(Err::Totp)?;
+        let (token, ttl) = if totp.is_hotp() {
+            let token = totp::generate_hotp(
+                &store,
+                &secret,
+                matcher_show.property(),
+                &matcher_main,
+                matcher_show.allow_dirty(),
+                matcher_show.no_sync(),
+            )
+            .map_err(Err::Totp)?;
+            (token, None)
+        } else {
+            let token = totp.generate_current().map_err(Err::Totp)?;
+            (token, Some(totp.ttl().map_err(Err::Totp)?))
+        };
 
         // Copy to clipboard
         #[cfg(feature = "clipboard")]
@@ -87,7 +100,7 @@ impl<'a> Show<'a> {
             viewer::viewer(
                 &store,
                 &secret,
-                totp::format_token(&token, matcher_main.quiet(), Some(ttl)),
+                totp::format_token(&token, matcher_main.quiet(), ttl),
                 matcher_show.timeout().transpose()?.map(Duration::from_secs),
                 &matcher_main,
                 matcher_show.query(),
@@ -95,12 +108,12 @@ impl<'a> Show<'a> {
             .map_err(Err::Viewer)?;
         } else {
             secret::print_name(matcher_show.query(), &secret, &store, matcher_main.quiet());
-            totp::print_token(&token, matcher_main.quiet(), Some(ttl));
+            totp::print_token(&token, matcher_main.quiet(), ttl);
         }
 
         // Finalize tomb
         #[cfg(all(feature = "tomb", target_os = "linux"))]
-        tomb::finalize_tomb(&mut tomb, &matcher_main, false).map_err(Err::Tomb)?;
+        tomb::finalize_tomb(&mut tomb, &matcher_main, totp.is_hotp()).map_err(Err::Tomb)?;
 
         Ok(())
     }
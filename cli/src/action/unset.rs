@@ -0,0 +1,122 @@
+use anyhow::Result;
+use clap::ArgMatches;
+use prs_lib::{Store, Structured, crypto::prelude::*};
+use thiserror::Error;
+
+use crate::cmd::matcher::{MainMatcher, Matcher, unset::UnsetMatcher};
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+use crate::util::tomb;
+use crate::util::{search, secret, select, sync};
+
+/// Unset secret property action.
+pub struct Unset<'a> {
+    cmd_matches: &'a ArgMatches,
+}
+
+impl<'a> Unset<'a> {
+    /// Construct a new unset action.
+    pub fn new(cmd_matches: &'a ArgMatches) -> Self {
+        Self { cmd_matches }
+    }
+
+    /// Invoke the unset action.
+    pub fn invoke(&self) -> Result<()> {
+        // Create the command matchers
+        let matcher_main = MainMatcher::with(self.cmd_matches).unwrap();
+        let matcher_unset = UnsetMatcher::with(self.cmd_matches).unwrap();
+
+        let store = Store::open(matcher_main.store()).map_err(Err::Store)?;
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        let mut tomb = store.tomb(
+            !matcher_main.verbose(),
+            matcher_main.verbose(),
+            matcher_main.force(),
+        );
+        let sync = store.sync();
+
+        // Prepare tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::prepare_tomb(&mut tomb, &matcher_main).map_err(Err::Tomb)?;
+
+        // Prepare sync
+        sync::ensure_ready(&sync, matcher_unset.allow_dirty());
+        if !matcher_unset.no_sync() {
+            sync.prepare()?;
+        }
+
+        let secret = select::store_select_secret(&store, matcher_unset.query(), &matcher_main)
+            .ok_or(Err::NoneSelected)?;
+
+        secret::print_name(matcher_unset.query(), &secret, &store, matcher_main.quiet());
+
+        let mut context = crate::crypto::context(&matcher_main)?;
+        let mut plaintext = context.decrypt_file(&secret.path).map_err(Err::Read)?;
+        if plaintext.is_binary() {
+            return Err(Err::Binary.into());
+        }
+
+        // Remove the property or structured field
+        let name = matcher_unset.name();
+        if matcher_unset.field() {
+            let mut structured = Structured::parse(plaintext).map_err(Err::Unset)?;
+            structured.remove_field(name).map_err(Err::Unset)?;
+            plaintext = structured.into_plaintext();
+        } else {
+            plaintext.remove_property(name).map_err(Err::Unset)?;
+        }
+
+        // Encrypt and write changed plaintext
+        let recipients = store.recipients_for(&secret)?;
+        context
+            .encrypt_file(&recipients, plaintext.clone(), &secret.path)
+            .map_err(Err::Write)?;
+        search::update(&store, &matcher_main, |index| {
+            index.update(&secret, &plaintext).map(|_| true)
+        });
+
+        // Finalize sync
+        if !matcher_unset.no_sync() {
+            let kind = if matcher_unset.field() {
+                "field"
+            } else {
+                "property"
+            };
+            sync.finalize(format!("Unset {kind} {name} in secret {}", secret.name))?;
+        }
+
+        // Finalize tomb
+        #[cfg(all(feature = "tomb", target_os = "linux"))]
+        tomb::finalize_tomb(&mut tomb, &matcher_main, true).map_err(Err::Tomb)?;
+
+        if !matcher_main.quiet() {
+            eprintln!("Secret updated");
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to access password store")]
+    Store(#[source] anyhow::Error),
+
+    #[cfg(all(feature = "tomb", target_os = "linux"))]
+    #[error("failed to prepare password store tomb for usage")]
+    Tomb(#[source] anyhow::Error),
+
+    #[error("no secret selected")]
+    NoneSelected,
+
+    #[error("failed to read secret")]
+    Read(#[source] anyhow::Error),
+
+    #[error("cannot edit binary secret as text, use attach or extract instead")]
+    Binary,
+
+    #[error("failed to remove property from secret")]
+    Unset(#[source] anyhow::Error),
+
+    #[error("failed to write changed secret")]
+    Write(#[source] anyhow::Error),
+}
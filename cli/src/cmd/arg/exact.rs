@@ -0,0 +1,19 @@
+use clap::Arg;
+
+use super::CmdArg;
+
+/// The exact argument.
+pub struct ArgExact {}
+
+impl CmdArg for ArgExact {
+    fn name() -> &'static str {
+        "exact"
+    }
+
+    fn build() -> Arg {
+        Arg::new("exact")
+            .long("exact")
+            .num_args(0)
+            .help("Find secrets containing query, disable fuzzy matching")
+    }
+}
@@ -0,0 +1,62 @@
+use std::thread;
+
+use clap::{Arg, ArgMatches, value_parser};
+
+use super::{CmdArg, CmdArgOption};
+use crate::cmd::matcher::MainMatcher;
+
+/// Maximum number of parallel jobs used by default.
+const MAX_DEFAULT_JOBS: usize = 8;
+
+/// The jobs argument.
+pub struct ArgJobs {}
+
+impl ArgJobs {
+    /// Get the number of parallel jobs to use, or the default.
+    ///
+    /// Always uses a single job if GPG asks for passphrases in the TTY, as parallel prompts would
+    /// clash.
+    pub fn value_or_default(matches: &ArgMatches, matcher_main: &MainMatcher) -> usize {
+        match Self::value(matches) {
+            _ if matcher_main.gpg_tty() => 1,
+            Some(jobs) => jobs,
+            None => Self::default_jobs(matcher_main),
+        }
+    }
+
+    /// Get the default number of parallel jobs, based on the available parallelism.
+    pub fn default_jobs(matcher_main: &MainMatcher) -> usize {
+        if matcher_main.gpg_tty() {
+            return 1;
+        }
+        thread::available_parallelism()
+            .map(|n| n.get().min(MAX_DEFAULT_JOBS))
+            .unwrap_or(1)
+    }
+}
+
+impl CmdArg for ArgJobs {
+    fn name() -> &'static str {
+        "jobs"
+    }
+
+    fn build() -> Arg {
+        Arg::new("jobs")
+            .long("jobs")
+            .short('j')
+            .value_name("NUM")
+            .num_args(1)
+            .value_parser(value_parser!(u16).range(1..))
+            .help("Number of secrets to process in parallel")
+    }
+}
+
+impl<'a> CmdArgOption<'a> for ArgJobs {
+    type Value = Option<usize>;
+
+    fn value(matches: &'a ArgMatches) -> Self::Value {
+        matches
+            .get_one::<u16>(Self::name())
+            .map(|jobs| *jobs as usize)
+    }
+}
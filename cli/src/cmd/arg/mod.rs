@@ -1,8 +1,12 @@
 pub mod allow_dirty;
+pub mod exact;
+pub mod jobs;
 pub mod no_sync;
+pub mod output;
 pub mod property;
 pub mod query;
 pub mod store;
+pub mod tag;
 pub mod timeout;
 pub mod viewer;
 
@@ -10,10 +14,14 @@ use clap::{Arg, ArgMatches, parser::ValuesRef};
 
 // Re-export to arg module
 pub use self::allow_dirty::ArgAllowDirty;
+pub use self::exact::ArgExact;
+pub use self::jobs::ArgJobs;
 pub use self::no_sync::ArgNoSync;
+pub use self::output::{ArgOutput, OutputFormat};
 pub use self::property::ArgProperty;
 pub use self::query::ArgQuery;
 pub use self::store::ArgStore;
+pub use self::tag::ArgTag;
 pub use self::timeout::ArgTimeout;
 pub use self::viewer::ArgViewer;
 
@@ -0,0 +1,44 @@
+use clap::{Arg, ArgMatches};
+
+use super::{CmdArg, CmdArgOption};
+
+/// Output format.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human readable text.
+    Text,
+
+    /// Machine readable JSON.
+    Json,
+}
+
+/// The output format argument.
+pub struct ArgOutput {}
+
+impl CmdArg for ArgOutput {
+    fn name() -> &'static str {
+        "output"
+    }
+
+    fn build() -> Arg {
+        Arg::new("output")
+            .long("output")
+            .value_name("FORMAT")
+            .value_parser(["text", "json"])
+            .default_value("text")
+            .num_args(1)
+            .global(true)
+            .help("Output format for read commands")
+    }
+}
+
+impl<'a> CmdArgOption<'a> for ArgOutput {
+    type Value = OutputFormat;
+
+    fn value(matches: &'a ArgMatches) -> Self::Value {
+        match Self::value_raw(matches).map(|f| f.as_str()) {
+            Some("json") => OutputFormat::Json,
+            _ => OutputFormat::Text,
+        }
+    }
+}
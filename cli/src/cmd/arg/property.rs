@@ -1,6 +1,7 @@
 use clap::{Arg, ArgMatches};
 
 use super::{CmdArg, CmdArgOption};
+use crate::cmd::complete::{self, ArgComplete};
 
 /// The property argument.
 pub struct ArgProperty {}
@@ -19,6 +20,7 @@ impl CmdArg for ArgProperty {
             .num_args(1)
             .global(true)
             .help("Select a specific property")
+            .complete(complete::properties)
     }
 }
 
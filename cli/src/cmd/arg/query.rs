@@ -1,6 +1,7 @@
 use clap::{Arg, ArgMatches};
 
 use super::{CmdArg, CmdArgOption};
+use crate::cmd::complete::{self, ArgComplete};
 
 /// The query argument.
 pub struct ArgQuery {}
@@ -11,7 +12,9 @@ impl CmdArg for ArgQuery {
     }
 
     fn build() -> Arg {
-        Arg::new("QUERY").help("Secret query")
+        Arg::new("QUERY")
+            .help("Secret query")
+            .complete(complete::secrets)
     }
 }
 
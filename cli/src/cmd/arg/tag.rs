@@ -0,0 +1,33 @@
+use clap::{Arg, ArgAction, ArgMatches};
+
+use super::{CmdArg, CmdArgOption};
+use crate::cmd::complete::{self, ArgComplete};
+
+/// The tag filter argument.
+pub struct ArgTag {}
+
+impl CmdArg for ArgTag {
+    fn name() -> &'static str {
+        "tag"
+    }
+
+    fn build() -> Arg {
+        Arg::new("tag")
+            .long("tag")
+            .short('t')
+            .value_name("TAG")
+            .action(ArgAction::Append)
+            .help("Only secrets with tag, may be repeated")
+            .complete(complete::tags)
+    }
+}
+
+impl<'a> CmdArgOption<'a> for ArgTag {
+    type Value = Vec<String>;
+
+    fn value(matches: &'a ArgMatches) -> Self::Value {
+        Self::values_raw(matches)
+            .map(|tags| tags.cloned().collect())
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,120 @@
+//! Dynamic shell completion candidates.
+//!
+//! Shells call back into prs to complete arguments, see `clap_complete::CompleteEnv`. These
+//! candidates are resolved at completion time, and must never decrypt secrets or prompt.
+//!
+//! Dynamic completions depend on unstable clap APIs, and are only enabled with the
+//! `dynamic-completions` feature. Without it, candidates are not attached to arguments.
+
+use std::env;
+
+use clap::Arg;
+#[cfg(feature = "dynamic-completions")]
+use clap_complete::engine::{ArgValueCandidates, CompletionCandidate};
+use prs_lib::{
+    Store,
+    crypto::{groups::Groups, store::store_read_gpg_fingerprints},
+};
+
+/// Well known property names to complete, secrets are not decrypted to find actual names.
+const PROPERTY_NAMES: [&str; 10] = [
+    "username", "user", "login", "email", "url", "totp", "2fa", "otp", "notes", "pin",
+];
+
+/// Argument extension to attach completion candidates.
+pub trait ArgComplete {
+    /// Complete this argument with candidates from the given function.
+    fn complete(self, candidates: fn() -> Vec<String>) -> Self;
+}
+
+impl ArgComplete for Arg {
+    #[cfg(feature = "dynamic-completions")]
+    fn complete(self, candidates: fn() -> Vec<String>) -> Self {
+        self.add(ArgValueCandidates::new(move || {
+            candidates()
+                .into_iter()
+                .map(CompletionCandidate::new)
+                .collect::<Vec<_>>()
+        }))
+    }
+
+    #[cfg(not(feature = "dynamic-completions"))]
+    fn complete(self, _candidates: fn() -> Vec<String>) -> Self {
+        self
+    }
+}
+
+/// Complete secret names.
+pub fn secrets() -> Vec<String> {
+    let Some(store) = store() else {
+        return vec![];
+    };
+    let mut names: Vec<String> = store.secret_iter().map(|secret| secret.name).collect();
+    names.sort_unstable();
+    names
+}
+
+/// Complete store recipient fingerprints.
+pub fn recipients() -> Vec<String> {
+    store()
+        .and_then(|store| store_read_gpg_fingerprints(&store).ok())
+        .unwrap_or_default()
+}
+
+/// Complete recipient group names of the store.
+pub fn groups() -> Vec<String> {
+    store()
+        .and_then(|store| Groups::load(&store).ok())
+        .map(|groups| groups.names().map(String::from).collect())
+        .unwrap_or_default()
+}
+
+/// Complete git remote names of the store.
+pub fn remotes() -> Vec<String> {
+    store()
+        .and_then(|store| store.sync().remotes().ok())
+        .unwrap_or_default()
+}
+
+/// Complete secret tags used in the store.
+pub fn tags() -> Vec<String> {
+    store()
+        .and_then(|store| store.tags().ok())
+        .map(|tags| tags.all().into_iter().map(String::from).collect())
+        .unwrap_or_default()
+}
+
+/// Complete common property names.
+pub fn properties() -> Vec<String> {
+    PROPERTY_NAMES.iter().map(|name| name.to_string()).collect()
+}
+
+/// Open the store being completed for.
+///
+/// Respects a `--store` argument in the command line being completed, then `PASSWORD_STORE_DIR`.
+fn store() -> Option<Store> {
+    Store::open(store_path()).ok()
+}
+
+/// Find the store path for the command line being completed.
+fn store_path() -> String {
+    // Completion arguments follow after `--`
+    let args: Vec<String> = env::args().skip_while(|arg| arg != "--").skip(1).collect();
+
+    let mut store = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--store" || arg == "-s" {
+            store = iter.next().cloned().or(store);
+        } else if let Some(path) = arg.strip_prefix("--store=") {
+            store = Some(path.into());
+        } else if let Some(path) = arg.strip_prefix("-s").filter(|path| !path.is_empty()) {
+            store = Some(path.into());
+        }
+    }
+
+    store
+        .or_else(|| env::var("PASSWORD_STORE_DIR").ok())
+        .filter(|path| !path.trim().is_empty())
+        .unwrap_or_else(|| prs_lib::STORE_DEFAULT_ROOT.into())
+}
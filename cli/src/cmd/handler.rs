@@ -1,6 +1,6 @@
 use clap::{Arg, ArgAction, ArgMatches, Command};
 
-use super::arg::{ArgStore, CmdArg};
+use super::arg::{ArgOutput, ArgStore, CmdArg};
 use super::matcher::{self, Matcher};
 use super::subcmd;
 
@@ -74,6 +74,7 @@ impl<'a> Handler {
                     .help("Enable verbose information and logging"),
             )
             .arg(ArgStore::build())
+            .arg(ArgOutput::build())
             .arg(
                 Arg::new("gpg-tty")
                     .long("gpg-tty")
@@ -88,18 +89,28 @@ impl<'a> Handler {
 
         let app = app
             .subcommand(subcmd::CmdGenerate::build())
+            .subcommand(subcmd::CmdRotate::build())
             .subcommand(subcmd::CmdAdd::build())
             .subcommand(subcmd::CmdEdit::build())
+            .subcommand(subcmd::CmdSet::build())
+            .subcommand(subcmd::CmdUnset::build())
             .subcommand(subcmd::CmdDuplicate::build());
 
         #[cfg(feature = "alias")]
         let app = app.subcommand(subcmd::CmdAlias::build());
 
         let app = app
+            .subcommand(subcmd::CmdAttach::build())
+            .subcommand(subcmd::CmdExtract::build())
+            .subcommand(subcmd::CmdShare::build())
+            .subcommand(subcmd::CmdSplit::build())
+            .subcommand(subcmd::CmdCombine::build())
             .subcommand(subcmd::CmdMove::build())
             .subcommand(subcmd::CmdRemove::build())
             .subcommand(subcmd::CmdList::build())
             .subcommand(subcmd::CmdGrep::build())
+            .subcommand(subcmd::CmdTag::build())
+            .subcommand(subcmd::CmdExpiring::build())
             .subcommand(subcmd::CmdInit::build())
             .subcommand(subcmd::CmdClone::build())
             .subcommand(subcmd::CmdSync::build())
@@ -108,6 +119,11 @@ impl<'a> Handler {
         #[cfg(feature = "totp")]
         let app = app.subcommand(subcmd::CmdTotp::build());
 
+        #[cfg(all(feature = "ssh", unix))]
+        let app = app
+            .subcommand(subcmd::CmdSshAdd::build())
+            .subcommand(subcmd::CmdSshAgent::build());
+
         let app = app
             .subcommand(subcmd::CmdRecipients::build())
             .subcommand(subcmd::CmdGit::build());
@@ -142,11 +158,21 @@ impl<'a> Handler {
         matcher::AliasMatcher::with(&self.matches)
     }
 
+    /// Get the attach sub command, if matched.
+    pub fn attach(&'a self) -> Option<matcher::AttachMatcher<'a>> {
+        matcher::AttachMatcher::with(&self.matches)
+    }
+
     /// Get the clone sub command, if matched.
     pub fn clone(&'a self) -> Option<matcher::CloneMatcher<'a>> {
         matcher::CloneMatcher::with(&self.matches)
     }
 
+    /// Get the combine sub command, if matched.
+    pub fn combine(&'a self) -> Option<matcher::CombineMatcher<'a>> {
+        matcher::CombineMatcher::with(&self.matches)
+    }
+
     /// Get the copy sub command, if matched.
     #[cfg(feature = "clipboard")]
     pub fn copy(&'a self) -> Option<matcher::CopyMatcher<'a>> {
@@ -163,6 +189,16 @@ impl<'a> Handler {
         matcher::EditMatcher::with(&self.matches)
     }
 
+    /// Get the expiring sub command, if matched.
+    pub fn expiring(&'a self) -> Option<matcher::ExpiringMatcher<'a>> {
+        matcher::ExpiringMatcher::with(&self.matches)
+    }
+
+    /// Get the extract sub command, if matched.
+    pub fn extract(&'a self) -> Option<matcher::ExtractMatcher<'a>> {
+        matcher::ExtractMatcher::with(&self.matches)
+    }
+
     /// Get the generate sub command, if matched.
     pub fn generate(&'a self) -> Option<matcher::GenerateMatcher<'a>> {
         matcher::GenerateMatcher::with(&self.matches)
@@ -218,16 +254,53 @@ impl<'a> Handler {
         matcher::RemoveMatcher::with(&self.matches)
     }
 
+    /// Get the rotate sub command, if matched.
+    pub fn rotate(&'a self) -> Option<matcher::RotateMatcher<'a>> {
+        matcher::RotateMatcher::with(&self.matches)
+    }
+
+    /// Get the set sub command, if matched.
+    pub fn set(&'a self) -> Option<matcher::SetMatcher<'a>> {
+        matcher::SetMatcher::with(&self.matches)
+    }
+
+    /// Get the share sub command, if matched.
+    pub fn share(&'a self) -> Option<matcher::ShareMatcher<'a>> {
+        matcher::ShareMatcher::with(&self.matches)
+    }
+
     /// Get the show sub command, if matched.
     pub fn show(&'a self) -> Option<matcher::ShowMatcher<'a>> {
         matcher::ShowMatcher::with(&self.matches)
     }
 
+    /// Get the split sub command, if matched.
+    pub fn split(&'a self) -> Option<matcher::SplitMatcher<'a>> {
+        matcher::SplitMatcher::with(&self.matches)
+    }
+
+    /// Get the ssh-add sub command, if matched.
+    #[cfg(all(feature = "ssh", unix))]
+    pub fn ssh_add(&'a self) -> Option<matcher::SshAddMatcher<'a>> {
+        matcher::SshAddMatcher::with(&self.matches)
+    }
+
+    /// Get the ssh-agent sub command, if matched.
+    #[cfg(all(feature = "ssh", unix))]
+    pub fn ssh_agent(&'a self) -> Option<matcher::SshAgentMatcher<'a>> {
+        matcher::SshAgentMatcher::with(&self.matches)
+    }
+
     /// Get the sync sub command, if matched.
     pub fn sync(&'a self) -> Option<matcher::SyncMatcher<'a>> {
         matcher::SyncMatcher::with(&self.matches)
     }
 
+    /// Get the tag sub command, if matched.
+    pub fn tag(&'a self) -> Option<matcher::TagMatcher<'a>> {
+        matcher::TagMatcher::with(&self.matches)
+    }
+
     /// Get the tomb sub command, if matched.
     #[cfg(all(feature = "tomb", target_os = "linux"))]
     pub fn tomb(&'a self) -> Option<matcher::TombMatcher<'a>> {
@@ -239,4 +312,9 @@ impl<'a> Handler {
     pub fn totp(&'a self) -> Option<matcher::TotpMatcher<'a>> {
         matcher::TotpMatcher::with(&self.matches)
     }
+
+    /// Get the unset sub command, if matched.
+    pub fn unset(&'a self) -> Option<matcher::UnsetMatcher<'a>> {
+        matcher::UnsetMatcher::with(&self.matches)
+    }
 }
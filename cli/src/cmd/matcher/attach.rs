@@ -0,0 +1,44 @@
+use std::path::{Path, PathBuf};
+
+use clap::ArgMatches;
+
+use super::Matcher;
+use crate::cmd::arg::{ArgAllowDirty, ArgNoSync, CmdArgFlag};
+
+/// The attach command matcher.
+pub struct AttachMatcher<'a> {
+    matches: &'a ArgMatches,
+}
+
+impl AttachMatcher<'_> {
+    /// Secret name.
+    pub fn name(&self) -> &str {
+        self.matches.get_one::<String>("NAME").unwrap()
+    }
+
+    /// File to attach, `None` to read from stdin.
+    pub fn file(&self) -> Option<&Path> {
+        self.matches
+            .get_one::<PathBuf>("FILE")
+            .filter(|path| path.as_os_str() != "-")
+            .map(|path| path.as_path())
+    }
+
+    /// Whether to allow a dirty repository for syncing.
+    pub fn allow_dirty(&self) -> bool {
+        ArgAllowDirty::is_present(self.matches)
+    }
+
+    /// Whether to not sync.
+    pub fn no_sync(&self) -> bool {
+        ArgNoSync::is_present(self.matches)
+    }
+}
+
+impl<'a> Matcher<'a> for AttachMatcher<'a> {
+    fn with(matches: &'a ArgMatches) -> Option<Self> {
+        matches
+            .subcommand_matches("attach")
+            .map(|matches| AttachMatcher { matches })
+    }
+}
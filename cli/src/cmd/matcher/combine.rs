@@ -0,0 +1,34 @@
+use std::path::PathBuf;
+
+use clap::ArgMatches;
+
+use super::Matcher;
+use crate::cmd::arg::{ArgQuery, CmdArgOption};
+
+/// The combine command matcher.
+pub struct CombineMatcher<'a> {
+    matches: &'a ArgMatches,
+}
+
+impl CombineMatcher<'_> {
+    /// The secret query.
+    pub fn query(&self) -> Option<String> {
+        ArgQuery::value(self.matches)
+    }
+
+    /// Files to read shares from, `-` for stdin.
+    pub fn shares(&self) -> Vec<&PathBuf> {
+        self.matches
+            .get_many("share")
+            .map(|shares| shares.collect())
+            .unwrap_or_default()
+    }
+}
+
+impl<'a> Matcher<'a> for CombineMatcher<'a> {
+    fn with(matches: &'a ArgMatches) -> Option<Self> {
+        matches
+            .subcommand_matches("combine")
+            .map(|matches| CombineMatcher { matches })
+    }
+}
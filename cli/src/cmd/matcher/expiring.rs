@@ -0,0 +1,36 @@
+use clap::ArgMatches;
+
+use super::Matcher;
+use crate::cmd::arg::{ArgJobs, ArgQuery, CmdArgOption};
+use crate::cmd::matcher::MainMatcher;
+use crate::util::time::{self, ParseDurationError};
+
+/// The expiring command matcher.
+pub struct ExpiringMatcher<'a> {
+    matches: &'a ArgMatches,
+}
+
+impl ExpiringMatcher<'_> {
+    /// The secret query.
+    pub fn query(&self) -> Option<String> {
+        ArgQuery::value(self.matches)
+    }
+
+    /// Duration in seconds to include secrets expiring within.
+    pub fn within(&self) -> Result<usize, ParseDurationError> {
+        time::parse_duration(self.matches.get_one::<String>("within").unwrap())
+    }
+
+    /// Number of secrets to decrypt in parallel.
+    pub fn jobs(&self, matcher_main: &MainMatcher) -> usize {
+        ArgJobs::value_or_default(self.matches, matcher_main)
+    }
+}
+
+impl<'a> Matcher<'a> for ExpiringMatcher<'a> {
+    fn with(matches: &'a ArgMatches) -> Option<Self> {
+        matches
+            .subcommand_matches("expiring")
+            .map(|matches| ExpiringMatcher { matches })
+    }
+}
@@ -0,0 +1,34 @@
+use std::path::{Path, PathBuf};
+
+use clap::ArgMatches;
+
+use super::Matcher;
+use crate::cmd::arg::{ArgQuery, CmdArgOption};
+
+/// The extract command matcher.
+pub struct ExtractMatcher<'a> {
+    matches: &'a ArgMatches,
+}
+
+impl ExtractMatcher<'_> {
+    /// The secret query.
+    pub fn query(&self) -> Option<String> {
+        ArgQuery::value(self.matches)
+    }
+
+    /// File to write to, `None` for stdout.
+    pub fn out(&self) -> Option<&Path> {
+        self.matches
+            .get_one::<PathBuf>("out")
+            .filter(|path| path.as_os_str() != "-")
+            .map(|path| path.as_path())
+    }
+}
+
+impl<'a> Matcher<'a> for ExtractMatcher<'a> {
+    fn with(matches: &'a ArgMatches) -> Option<Self> {
+        matches
+            .subcommand_matches("extract")
+            .map(|matches| ExtractMatcher { matches })
+    }
+}
@@ -8,10 +8,10 @@ use crate::cmd::arg::ArgTimeout;
 use crate::cmd::arg::{ArgAllowDirty, ArgNoSync, CmdArgFlag};
 
 /// Default password length in characters.
-const PASSWORD_LENGTH: u16 = 24;
+pub const PASSWORD_LENGTH: u16 = 24;
 
 /// Default passphrase length in words.
-const PASSPHRASE_LENGTH: u16 = 5;
+pub const PASSPHRASE_LENGTH: u16 = 5;
 
 /// The generate command matcher.
 pub struct GenerateMatcher<'a> {
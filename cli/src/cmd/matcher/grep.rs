@@ -1,6 +1,8 @@
 use clap::ArgMatches;
 
 use super::Matcher;
+use crate::cmd::arg::{ArgJobs, ArgTag, CmdArgOption};
+use crate::cmd::matcher::MainMatcher;
 
 /// The grep command matcher.
 pub struct GrepMatcher<'a> {
@@ -9,8 +11,8 @@ pub struct GrepMatcher<'a> {
 
 impl GrepMatcher<'_> {
     /// The grep pattern.
-    pub fn pattern(&self) -> String {
-        self.matches.get_one("PATTERN").cloned().unwrap()
+    pub fn pattern(&self) -> Option<String> {
+        self.matches.get_one("PATTERN").cloned()
     }
 
     /// The secret query.
@@ -18,11 +20,26 @@ impl GrepMatcher<'_> {
         self.matches.get_one("query").cloned()
     }
 
+    /// Tags secrets must have.
+    pub fn tags(&self) -> Vec<String> {
+        ArgTag::value(self.matches)
+    }
+
     /// Whether to parse the pattern as regular expression.
     pub fn regex(&self) -> bool {
         self.matches.get_flag("regex")
     }
 
+    /// Whether to not use the search index.
+    pub fn no_index(&self) -> bool {
+        self.matches.get_flag("no-index")
+    }
+
+    /// Number of secrets to decrypt in parallel.
+    pub fn jobs(&self, matcher_main: &MainMatcher) -> usize {
+        ArgJobs::value_or_default(self.matches, matcher_main)
+    }
+
     /// Whether to include searching aliases.
     pub fn with_aliases(&self) -> bool {
         self.matches.get_flag("aliases")
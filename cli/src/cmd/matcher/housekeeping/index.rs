@@ -0,0 +1,36 @@
+use clap::ArgMatches;
+
+use super::Matcher;
+use crate::cmd::arg::ArgJobs;
+use crate::cmd::matcher::MainMatcher;
+
+/// The housekeeping index command matcher.
+pub struct IndexMatcher<'a> {
+    matches: &'a ArgMatches,
+}
+
+impl IndexMatcher<'_> {
+    /// Whether to only check the index.
+    pub fn check(&self) -> bool {
+        self.matches.get_flag("check")
+    }
+
+    /// Whether to remove the index.
+    pub fn remove(&self) -> bool {
+        self.matches.get_flag("remove")
+    }
+
+    /// Number of secrets to decrypt in parallel.
+    pub fn jobs(&self, matcher_main: &MainMatcher) -> usize {
+        ArgJobs::value_or_default(self.matches, matcher_main)
+    }
+}
+
+impl<'a> Matcher<'a> for IndexMatcher<'a> {
+    fn with(matches: &'a ArgMatches) -> Option<Self> {
+        matches
+            .subcommand_matches("housekeeping")?
+            .subcommand_matches("index")
+            .map(|matches| IndexMatcher { matches })
+    }
+}
@@ -1,3 +1,4 @@
+pub mod index;
 pub mod recrypt;
 pub mod run;
 pub mod sync_keys;
@@ -13,6 +14,11 @@ pub struct HousekeepingMatcher<'a> {
 }
 
 impl<'a> HousekeepingMatcher<'a> {
+    /// Get the housekepeing index sub command, if matched.
+    pub fn index(&'a self) -> Option<index::IndexMatcher<'a>> {
+        index::IndexMatcher::with(self.root)
+    }
+
     /// Get the housekepeing recrypt sub command, if matched.
     pub fn recrypt(&'a self) -> Option<recrypt::RecryptMatcher<'a>> {
         recrypt::RecryptMatcher::with(self.root)
@@ -1,7 +1,8 @@
 use clap::ArgMatches;
 
 use super::Matcher;
-use crate::cmd::arg::{ArgAllowDirty, ArgNoSync, ArgQuery, CmdArgFlag, CmdArgOption};
+use crate::cmd::arg::{ArgAllowDirty, ArgJobs, ArgNoSync, ArgQuery, CmdArgFlag, CmdArgOption};
+use crate::cmd::matcher::MainMatcher;
 
 /// The housekeeping recrypt command matcher.
 pub struct RecryptMatcher<'a> {
@@ -14,6 +15,21 @@ impl RecryptMatcher<'_> {
         ArgQuery::value(self.matches)
     }
 
+    /// Check whether to re-encrypt secrets already encrypted for current recipients.
+    pub fn full(&self) -> bool {
+        self.matches.get_flag("full")
+    }
+
+    /// Check whether to only show secrets that would be re-encrypted.
+    pub fn dry_run(&self) -> bool {
+        self.matches.get_flag("dry-run")
+    }
+
+    /// Number of secrets to re-encrypt in parallel.
+    pub fn jobs(&self, matcher_main: &MainMatcher) -> usize {
+        ArgJobs::value_or_default(self.matches, matcher_main)
+    }
+
     /// Whether to allow a dirty repository for syncing.
     pub fn allow_dirty(&self) -> bool {
         ArgAllowDirty::is_present(self.matches)
@@ -45,7 +45,7 @@ impl<'a> CompletionsMatcher<'a> {
     /// The target directory to output the shell completion files to.
     pub fn output(&'a self) -> PathBuf {
         self.matches
-            .get_one::<String>("output")
+            .get_one::<String>("output-dir")
             .map(PathBuf::from)
             .unwrap_or_else(|| PathBuf::from("./"))
     }
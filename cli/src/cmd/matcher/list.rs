@@ -1,7 +1,7 @@
 use clap::ArgMatches;
 
 use super::Matcher;
-use crate::cmd::arg::{ArgQuery, CmdArgOption};
+use crate::cmd::arg::{ArgQuery, ArgTag, CmdArgOption};
 
 /// The list command matcher.
 pub struct ListMatcher<'a> {
@@ -14,6 +14,11 @@ impl ListMatcher<'_> {
         ArgQuery::value(self.matches)
     }
 
+    /// Tags secrets must have.
+    pub fn tags(&self) -> Vec<String> {
+        ArgTag::value(self.matches)
+    }
+
     /// Whether to show as plain list.
     pub fn list(&self) -> bool {
         self.matches.get_flag("list")
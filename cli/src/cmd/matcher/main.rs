@@ -1,7 +1,7 @@
 use clap::ArgMatches;
 
 use super::Matcher;
-use crate::cmd::arg::{ArgStore, CmdArgOption};
+use crate::cmd::arg::{ArgExact, ArgOutput, ArgStore, CmdArg, CmdArgOption, OutputFormat};
 
 /// The main command matcher.
 pub struct MainMatcher<'a> {
@@ -19,6 +19,22 @@ impl MainMatcher<'_> {
         self.matches.get_flag("no-interact")
     }
 
+    /// Check whether to disable fuzzy matching when finding secrets.
+    ///
+    /// Only commands selecting a secret have this flag, it is read from the invoked subcommand.
+    pub fn exact(&self) -> bool {
+        let mut matches = self.matches;
+        while let Some((_, sub)) = matches.subcommand() {
+            matches = sub;
+        }
+        matches
+            .try_get_one::<bool>(ArgExact::name())
+            .ok()
+            .flatten()
+            .copied()
+            .unwrap_or(false)
+    }
+
     /// Check whether to assume yes.
     pub fn assume_yes(&self) -> bool {
         self.matches.get_flag("yes")
@@ -39,6 +55,16 @@ impl MainMatcher<'_> {
         ArgStore::value(self.matches)
     }
 
+    /// The output format.
+    pub fn output(&self) -> OutputFormat {
+        ArgOutput::value(self.matches)
+    }
+
+    /// Check whether to output JSON.
+    pub fn json(&self) -> bool {
+        self.output() == OutputFormat::Json
+    }
+
     /// Check whether to use GPG in TTY mode.
     pub fn gpg_tty(&self) -> bool {
         self.matches.get_flag("gpg-tty")
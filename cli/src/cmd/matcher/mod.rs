@@ -1,11 +1,15 @@
 pub mod add;
 #[cfg(feature = "alias")]
 pub mod alias;
+pub mod attach;
 pub mod clone;
+pub mod combine;
 #[cfg(feature = "clipboard")]
 pub mod copy;
 pub mod duplicate;
 pub mod edit;
+pub mod expiring;
+pub mod extract;
 pub mod generate;
 pub mod git;
 pub mod grep;
@@ -17,23 +21,37 @@ pub mod main;
 pub mod r#move;
 pub mod recipients;
 pub mod remove;
+pub mod rotate;
+pub mod set;
+pub mod share;
 pub mod show;
 pub mod slam;
+pub mod split;
+#[cfg(all(feature = "ssh", unix))]
+pub mod ssh_add;
+#[cfg(all(feature = "ssh", unix))]
+pub mod ssh_agent;
 pub mod sync;
+pub mod tag;
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 pub mod tomb;
 #[cfg(feature = "totp")]
 pub mod totp;
+pub mod unset;
 
 // Re-export to matcher module
 pub use self::add::AddMatcher;
 #[cfg(feature = "alias")]
 pub use self::alias::AliasMatcher;
+pub use self::attach::AttachMatcher;
 pub use self::clone::CloneMatcher;
+pub use self::combine::CombineMatcher;
 #[cfg(feature = "clipboard")]
 pub use self::copy::CopyMatcher;
 pub use self::duplicate::DuplicateMatcher;
 pub use self::edit::EditMatcher;
+pub use self::expiring::ExpiringMatcher;
+pub use self::extract::ExtractMatcher;
 pub use self::generate::GenerateMatcher;
 pub use self::git::GitMatcher;
 pub use self::grep::GrepMatcher;
@@ -45,13 +63,23 @@ pub use self::main::MainMatcher;
 pub use self::r#move::MoveMatcher;
 pub use self::recipients::RecipientsMatcher;
 pub use self::remove::RemoveMatcher;
+pub use self::rotate::RotateMatcher;
+pub use self::set::SetMatcher;
+pub use self::share::ShareMatcher;
 pub use self::show::ShowMatcher;
 pub use self::slam::SlamMatcher;
+pub use self::split::SplitMatcher;
+#[cfg(all(feature = "ssh", unix))]
+pub use self::ssh_add::SshAddMatcher;
+#[cfg(all(feature = "ssh", unix))]
+pub use self::ssh_agent::SshAgentMatcher;
 pub use self::sync::SyncMatcher;
+pub use self::tag::TagMatcher;
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 pub use self::tomb::TombMatcher;
 #[cfg(feature = "totp")]
 pub use self::totp::TotpMatcher;
+pub use self::unset::UnsetMatcher;
 
 use clap::ArgMatches;
 
@@ -9,6 +9,22 @@ pub struct AddMatcher<'a> {
 }
 
 impl AddMatcher<'_> {
+    /// Fingerprint of key to add.
+    pub fn fingerprint(&self) -> Option<&String> {
+        self.matches.get_one("FINGERPRINT")
+    }
+
+    /// Key file to import key to add from.
+    pub fn key_file(&self) -> Option<&String> {
+        self.matches.get_one("key-file")
+    }
+
+    /// Keyserver to fetch key to add from.
+    #[cfg(feature = "keyserver")]
+    pub fn keyserver(&self) -> Option<&String> {
+        self.matches.get_one("keyserver")
+    }
+
     /// Check whether to skip re-encrypting secrets.
     pub fn no_recrypt(&self) -> bool {
         self.matches.get_flag("no-recrypt")
@@ -0,0 +1,46 @@
+use clap::ArgMatches;
+
+use super::Matcher;
+use crate::cmd::arg::{ArgAllowDirty, ArgJobs, ArgNoSync, ArgQuery, CmdArgFlag, CmdArgOption};
+use crate::cmd::matcher::MainMatcher;
+
+/// The recipients audit command matcher.
+pub struct AuditMatcher<'a> {
+    matches: &'a ArgMatches,
+}
+
+impl AuditMatcher<'_> {
+    /// The secret query.
+    pub fn query(&self) -> Option<String> {
+        ArgQuery::value(self.matches)
+    }
+
+    /// Check whether to re-encrypt secrets with different recipients.
+    pub fn fix(&self) -> bool {
+        self.matches.get_flag("fix")
+    }
+
+    /// Number of secrets to audit in parallel.
+    pub fn jobs(&self, matcher_main: &MainMatcher) -> usize {
+        ArgJobs::value_or_default(self.matches, matcher_main)
+    }
+
+    /// Whether to allow a dirty repository for syncing.
+    pub fn allow_dirty(&self) -> bool {
+        ArgAllowDirty::is_present(self.matches)
+    }
+
+    /// Whether to not sync.
+    pub fn no_sync(&self) -> bool {
+        ArgNoSync::is_present(self.matches)
+    }
+}
+
+impl<'a> Matcher<'a> for AuditMatcher<'a> {
+    fn with(matches: &'a ArgMatches) -> Option<Self> {
+        matches
+            .subcommand_matches("recipients")?
+            .subcommand_matches("audit")
+            .map(|matches| AuditMatcher { matches })
+    }
+}
@@ -8,6 +8,11 @@ pub struct ExportMatcher<'a> {
 }
 
 impl ExportMatcher<'_> {
+    /// Fingerprint of recipient to export.
+    pub fn fingerprint(&self) -> Option<&String> {
+        self.matches.get_one("FINGERPRINT")
+    }
+
     /// File to output to.
     pub fn output_file(&self) -> Option<&String> {
         self.matches.get_one("output-file")
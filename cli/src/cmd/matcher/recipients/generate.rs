@@ -1,7 +1,11 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
 use clap::ArgMatches;
 
 use super::Matcher;
 use crate::cmd::arg::{ArgAllowDirty, ArgNoSync, CmdArgFlag};
+use crate::util::error::{ErrorHints, quit_error};
 
 /// The recipients generate command matcher.
 pub struct GenerateMatcher<'a> {
@@ -9,6 +13,31 @@ pub struct GenerateMatcher<'a> {
 }
 
 impl GenerateMatcher<'_> {
+    /// Name for the new key.
+    pub fn name(&self) -> Option<&String> {
+        self.matches.get_one("name")
+    }
+
+    /// Email address for the new key.
+    pub fn email(&self) -> Option<&String> {
+        self.matches.get_one("email")
+    }
+
+    /// Duration after which the new key expires, `None` to never expire.
+    pub fn expire(&self) -> Option<Duration> {
+        let time: &String = self.matches.get_one("expire")?;
+        match crate::util::time::parse_duration(time) {
+            Ok(0) => None,
+            Ok(time) => Some(Duration::from_secs(time as u64)),
+            Err(err) => quit_error(err.into(), ErrorHints::default()),
+        }
+    }
+
+    /// File to write the secret key to.
+    pub fn secret_key_file(&self) -> Option<&PathBuf> {
+        self.matches.get_one("secret-key-file")
+    }
+
     /// Check whether to skip adding key to store.
     pub fn no_add(&self) -> bool {
         self.matches.get_flag("no-add")
@@ -0,0 +1,46 @@
+use clap::ArgMatches;
+
+use super::Matcher;
+use crate::cmd::arg::{ArgAllowDirty, ArgNoSync, CmdArgFlag};
+
+/// The recipients group add command matcher.
+pub struct AddMatcher<'a> {
+    matches: &'a ArgMatches,
+}
+
+impl AddMatcher<'_> {
+    /// Name of recipient group.
+    pub fn group(&self) -> &String {
+        self.matches.get_one("GROUP").unwrap()
+    }
+
+    /// Fingerprints of keys to add to group.
+    pub fn fingerprints(&self) -> Vec<&String> {
+        self.matches.get_many("FINGERPRINT").unwrap().collect()
+    }
+
+    /// Check whether to skip re-encrypting secrets.
+    pub fn no_recrypt(&self) -> bool {
+        self.matches.get_flag("no-recrypt")
+    }
+
+    /// Whether to allow a dirty repository for syncing.
+    pub fn allow_dirty(&self) -> bool {
+        ArgAllowDirty::is_present(self.matches)
+    }
+
+    /// Whether to not sync.
+    pub fn no_sync(&self) -> bool {
+        ArgNoSync::is_present(self.matches)
+    }
+}
+
+impl<'a> Matcher<'a> for AddMatcher<'a> {
+    fn with(matches: &'a ArgMatches) -> Option<Self> {
+        matches
+            .subcommand_matches("recipients")?
+            .subcommand_matches("group")?
+            .subcommand_matches("add")
+            .map(|matches| AddMatcher { matches })
+    }
+}
@@ -0,0 +1,25 @@
+use clap::ArgMatches;
+
+use super::Matcher;
+
+/// The recipients group list command matcher.
+pub struct ListMatcher<'a> {
+    matches: &'a ArgMatches,
+}
+
+impl ListMatcher<'_> {
+    /// Name of recipient group to list.
+    pub fn group(&self) -> Option<&String> {
+        self.matches.get_one("GROUP")
+    }
+}
+
+impl<'a> Matcher<'a> for ListMatcher<'a> {
+    fn with(matches: &'a ArgMatches) -> Option<Self> {
+        matches
+            .subcommand_matches("recipients")?
+            .subcommand_matches("group")?
+            .subcommand_matches("list")
+            .map(|matches| ListMatcher { matches })
+    }
+}
@@ -0,0 +1,41 @@
+pub mod add;
+pub mod list;
+pub mod remove;
+
+use clap::ArgMatches;
+
+use super::Matcher;
+
+/// The recipients group matcher.
+pub struct GroupMatcher<'a> {
+    root: &'a ArgMatches,
+    _matches: &'a ArgMatches,
+}
+
+impl<'a> GroupMatcher<'a> {
+    /// Get the recipient group add sub command, if matched.
+    pub fn cmd_add(&'a self) -> Option<add::AddMatcher<'a>> {
+        add::AddMatcher::with(self.root)
+    }
+
+    /// Get the recipient group list sub command, if matched.
+    pub fn cmd_list(&'a self) -> Option<list::ListMatcher<'a>> {
+        list::ListMatcher::with(self.root)
+    }
+
+    /// Get the recipient group remove sub command, if matched.
+    pub fn cmd_remove(&'a self) -> Option<remove::RemoveMatcher<'a>> {
+        remove::RemoveMatcher::with(self.root)
+    }
+}
+
+impl<'a> Matcher<'a> for GroupMatcher<'a> {
+    fn with(root: &'a ArgMatches) -> Option<Self> {
+        root.subcommand_matches("recipients")?
+            .subcommand_matches("group")
+            .map(|matches| GroupMatcher {
+                root,
+                _matches: matches,
+            })
+    }
+}
@@ -1,8 +1,12 @@
 pub mod add;
+pub mod audit;
 pub mod export;
 pub mod generate;
+pub mod group;
 pub mod list;
 pub mod remove;
+pub mod rotate;
+pub mod trust;
 
 use clap::ArgMatches;
 
@@ -20,6 +24,11 @@ impl<'a> RecipientsMatcher<'_> {
         add::AddMatcher::with(self.root)
     }
 
+    /// Get the recipient audit sub command, if matched.
+    pub fn cmd_audit(&'a self) -> Option<audit::AuditMatcher<'a>> {
+        audit::AuditMatcher::with(self.root)
+    }
+
     /// Get the recipient export sub command, if matched.
     pub fn cmd_export(&'a self) -> Option<export::ExportMatcher<'a>> {
         export::ExportMatcher::with(self.root)
@@ -30,6 +39,11 @@ impl<'a> RecipientsMatcher<'_> {
         generate::GenerateMatcher::with(self.root)
     }
 
+    /// Get the recipient group sub command, if matched.
+    pub fn cmd_group(&'a self) -> Option<group::GroupMatcher<'a>> {
+        group::GroupMatcher::with(self.root)
+    }
+
     /// Get the recipient list sub command, if matched.
     pub fn cmd_list(&'a self) -> Option<list::ListMatcher<'a>> {
         list::ListMatcher::with(self.root)
@@ -39,6 +53,16 @@ impl<'a> RecipientsMatcher<'_> {
     pub fn cmd_remove(&'a self) -> Option<remove::RemoveMatcher<'a>> {
         remove::RemoveMatcher::with(self.root)
     }
+
+    /// Get the recipient rotate sub command, if matched.
+    pub fn cmd_rotate(&'a self) -> Option<rotate::RotateMatcher<'a>> {
+        rotate::RotateMatcher::with(self.root)
+    }
+
+    /// Get the recipient trust sub command, if matched.
+    pub fn cmd_trust(&'a self) -> Option<trust::TrustMatcher<'a>> {
+        trust::TrustMatcher::with(self.root)
+    }
 }
 
 impl<'a> Matcher<'a> for RecipientsMatcher<'a> {
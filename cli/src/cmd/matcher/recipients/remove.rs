@@ -9,6 +9,11 @@ pub struct RemoveMatcher<'a> {
 }
 
 impl RemoveMatcher<'_> {
+    /// Fingerprint of recipient to remove.
+    pub fn fingerprint(&self) -> Option<&String> {
+        self.matches.get_one("FINGERPRINT")
+    }
+
     /// Check whether to re-encrypt secrets.
     pub fn recrypt(&self) -> bool {
         self.matches.get_flag("recrypt")
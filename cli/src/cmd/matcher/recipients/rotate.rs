@@ -0,0 +1,46 @@
+use clap::ArgMatches;
+
+use super::Matcher;
+use crate::cmd::arg::{ArgAllowDirty, ArgJobs, ArgNoSync, CmdArgFlag};
+use crate::cmd::matcher::MainMatcher;
+
+/// The recipients rotate command matcher.
+pub struct RotateMatcher<'a> {
+    matches: &'a ArgMatches,
+}
+
+impl RotateMatcher<'_> {
+    /// Fingerprint of recipient to replace.
+    pub fn from(&self) -> Option<&String> {
+        self.matches.get_one("from")
+    }
+
+    /// Fingerprint of new key.
+    pub fn to(&self) -> Option<&String> {
+        self.matches.get_one("to")
+    }
+
+    /// Number of secrets to re-encrypt in parallel.
+    pub fn jobs(&self, matcher_main: &MainMatcher) -> usize {
+        ArgJobs::value_or_default(self.matches, matcher_main)
+    }
+
+    /// Whether to allow a dirty repository for syncing.
+    pub fn allow_dirty(&self) -> bool {
+        ArgAllowDirty::is_present(self.matches)
+    }
+
+    /// Whether to not sync.
+    pub fn no_sync(&self) -> bool {
+        ArgNoSync::is_present(self.matches)
+    }
+}
+
+impl<'a> Matcher<'a> for RotateMatcher<'a> {
+    fn with(matches: &'a ArgMatches) -> Option<Self> {
+        matches
+            .subcommand_matches("recipients")?
+            .subcommand_matches("rotate")
+            .map(|matches| RotateMatcher { matches })
+    }
+}
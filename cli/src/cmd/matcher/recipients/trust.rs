@@ -0,0 +1,29 @@
+use clap::ArgMatches;
+
+use super::Matcher;
+
+/// The recipients trust command matcher.
+pub struct TrustMatcher<'a> {
+    matches: &'a ArgMatches,
+}
+
+impl TrustMatcher<'_> {
+    /// Fingerprint of recipient to trust.
+    pub fn fingerprint(&self) -> Option<&String> {
+        self.matches.get_one("FINGERPRINT")
+    }
+
+    /// Check whether to remove the recipient from trusted keys.
+    pub fn remove(&self) -> bool {
+        self.matches.get_flag("remove")
+    }
+}
+
+impl<'a> Matcher<'a> for TrustMatcher<'a> {
+    fn with(matches: &'a ArgMatches) -> Option<Self> {
+        matches
+            .subcommand_matches("recipients")?
+            .subcommand_matches("trust")
+            .map(|matches| TrustMatcher { matches })
+    }
+}
@@ -0,0 +1,74 @@
+#[cfg(feature = "clipboard")]
+use anyhow::Result;
+use clap::ArgMatches;
+
+use super::{
+    Matcher,
+    generate::{PASSPHRASE_LENGTH, PASSWORD_LENGTH},
+};
+#[cfg(feature = "clipboard")]
+use crate::cmd::arg::ArgTimeout;
+use crate::cmd::arg::{ArgAllowDirty, ArgNoSync, ArgQuery, CmdArgFlag, CmdArgOption};
+
+/// The rotate command matcher.
+pub struct RotateMatcher<'a> {
+    matches: &'a ArgMatches,
+}
+
+impl RotateMatcher<'_> {
+    /// The secret query.
+    pub fn query(&self) -> Option<String> {
+        ArgQuery::value(self.matches)
+    }
+
+    /// Check whether to generate a passphrase.
+    pub fn passphrase(&self) -> bool {
+        self.matches.get_flag("passphrase")
+    }
+
+    /// What length to use.
+    pub fn length(&self) -> u16 {
+        self.matches.get_one("length").copied().unwrap_or_else(|| {
+            if self.passphrase() {
+                PASSPHRASE_LENGTH
+            } else {
+                PASSWORD_LENGTH
+            }
+        })
+    }
+
+    /// Check whether to copy the new password.
+    #[cfg(feature = "clipboard")]
+    pub fn copy(&self) -> bool {
+        self.matches.get_flag("copy")
+    }
+
+    /// Clipboard timeout in seconds.
+    #[cfg(feature = "clipboard")]
+    pub fn timeout(&self) -> Result<u64> {
+        ArgTimeout::value_or_default(self.matches)
+    }
+
+    /// Check whether to show the new password.
+    pub fn show(&self) -> bool {
+        self.matches.get_flag("show")
+    }
+
+    /// Whether to allow a dirty repository for syncing.
+    pub fn allow_dirty(&self) -> bool {
+        ArgAllowDirty::is_present(self.matches)
+    }
+
+    /// Whether to not sync.
+    pub fn no_sync(&self) -> bool {
+        ArgNoSync::is_present(self.matches)
+    }
+}
+
+impl<'a> Matcher<'a> for RotateMatcher<'a> {
+    fn with(matches: &'a ArgMatches) -> Option<Self> {
+        matches
+            .subcommand_matches("rotate")
+            .map(|matches| RotateMatcher { matches })
+    }
+}
@@ -0,0 +1,67 @@
+use clap::ArgMatches;
+
+use super::{Matcher, generate::PASSWORD_LENGTH};
+use crate::cmd::arg::{ArgAllowDirty, ArgNoSync, ArgQuery, CmdArgFlag, CmdArgOption};
+
+/// The set command matcher.
+pub struct SetMatcher<'a> {
+    matches: &'a ArgMatches,
+}
+
+impl SetMatcher<'_> {
+    /// The secret query.
+    pub fn query(&self) -> Option<String> {
+        ArgQuery::value(self.matches)
+    }
+
+    /// The property name.
+    pub fn name(&self) -> &String {
+        self.matches.get_one("NAME").unwrap()
+    }
+
+    /// The property value.
+    pub fn value(&self) -> Option<&String> {
+        self.matches.get_one("VALUE")
+    }
+
+    /// Check whether to read the value from stdin.
+    pub fn stdin(&self) -> bool {
+        self.matches.get_flag("stdin")
+    }
+
+    /// Check whether to generate the value.
+    pub fn generate(&self) -> bool {
+        self.matches.get_flag("generate")
+    }
+
+    /// Length of the generated value.
+    pub fn length(&self) -> u16 {
+        self.matches
+            .get_one("length")
+            .copied()
+            .unwrap_or(PASSWORD_LENGTH)
+    }
+
+    /// Whether to set a field in a structured secret.
+    pub fn field(&self) -> bool {
+        self.matches.get_flag("field")
+    }
+
+    /// Whether to allow a dirty repository for syncing.
+    pub fn allow_dirty(&self) -> bool {
+        ArgAllowDirty::is_present(self.matches)
+    }
+
+    /// Whether to not sync.
+    pub fn no_sync(&self) -> bool {
+        ArgNoSync::is_present(self.matches)
+    }
+}
+
+impl<'a> Matcher<'a> for SetMatcher<'a> {
+    fn with(matches: &'a ArgMatches) -> Option<Self> {
+        matches
+            .subcommand_matches("set")
+            .map(|matches| SetMatcher { matches })
+    }
+}
@@ -0,0 +1,54 @@
+use std::path::{Path, PathBuf};
+
+use clap::ArgMatches;
+
+use super::Matcher;
+use crate::cmd::arg::{ArgAllowDirty, ArgNoSync, ArgProperty, ArgQuery, CmdArgFlag, CmdArgOption};
+
+/// The share command matcher.
+pub struct ShareMatcher<'a> {
+    matches: &'a ArgMatches,
+}
+
+impl ShareMatcher<'_> {
+    /// The secret query.
+    pub fn query(&self) -> Option<String> {
+        ArgQuery::value(self.matches)
+    }
+
+    /// Recipient to share with.
+    pub fn to(&self) -> &String {
+        self.matches.get_one("to").unwrap()
+    }
+
+    /// The selected property.
+    pub fn property(&self) -> Option<&String> {
+        ArgProperty::value(self.matches)
+    }
+
+    /// File to write to, `None` for stdout.
+    pub fn out(&self) -> Option<&Path> {
+        self.matches
+            .get_one::<PathBuf>("out")
+            .filter(|path| path.as_os_str() != "-")
+            .map(|path| path.as_path())
+    }
+
+    /// Whether to allow a dirty repository for syncing.
+    pub fn allow_dirty(&self) -> bool {
+        ArgAllowDirty::is_present(self.matches)
+    }
+
+    /// Whether to not sync.
+    pub fn no_sync(&self) -> bool {
+        ArgNoSync::is_present(self.matches)
+    }
+}
+
+impl<'a> Matcher<'a> for ShareMatcher<'a> {
+    fn with(matches: &'a ArgMatches) -> Option<Self> {
+        matches
+            .subcommand_matches("share")
+            .map(|matches| ShareMatcher { matches })
+    }
+}
@@ -30,6 +30,11 @@ impl ShowMatcher<'_> {
         ArgProperty::value(self.matches)
     }
 
+    /// The selected structured secret field.
+    pub fn field(&self) -> Option<&String> {
+        self.matches.get_one("field")
+    }
+
     /// Check whether to read from copy.
     #[cfg(feature = "clipboard")]
     pub fn copy(&self) -> bool {
@@ -0,0 +1,52 @@
+use clap::ArgMatches;
+
+use super::Matcher;
+use crate::cmd::arg::{ArgAllowDirty, ArgNoSync, ArgQuery, CmdArgFlag, CmdArgOption};
+
+/// The split command matcher.
+pub struct SplitMatcher<'a> {
+    matches: &'a ArgMatches,
+}
+
+impl SplitMatcher<'_> {
+    /// The secret query.
+    pub fn query(&self) -> Option<String> {
+        ArgQuery::value(self.matches)
+    }
+
+    /// Recipients to encrypt a share for.
+    pub fn to(&self) -> Vec<&String> {
+        self.matches
+            .get_many("to")
+            .map(|to| to.collect())
+            .unwrap_or_default()
+    }
+
+    /// Number of shares needed to reconstruct the secret.
+    pub fn threshold(&self) -> u8 {
+        *self.matches.get_one("threshold").unwrap()
+    }
+
+    /// Number of shares, if given.
+    pub fn shares(&self) -> Option<u8> {
+        self.matches.get_one("shares").copied()
+    }
+
+    /// Whether to allow a dirty repository for syncing.
+    pub fn allow_dirty(&self) -> bool {
+        ArgAllowDirty::is_present(self.matches)
+    }
+
+    /// Whether to not sync.
+    pub fn no_sync(&self) -> bool {
+        ArgNoSync::is_present(self.matches)
+    }
+}
+
+impl<'a> Matcher<'a> for SplitMatcher<'a> {
+    fn with(matches: &'a ArgMatches) -> Option<Self> {
+        matches
+            .subcommand_matches("split")
+            .map(|matches| SplitMatcher { matches })
+    }
+}
@@ -0,0 +1,34 @@
+use clap::ArgMatches;
+
+use super::Matcher;
+use crate::cmd::arg::{ArgQuery, CmdArgOption};
+
+/// The ssh-add command matcher.
+pub struct SshAddMatcher<'a> {
+    matches: &'a ArgMatches,
+}
+
+impl SshAddMatcher<'_> {
+    /// The secret query.
+    pub fn query(&self) -> Option<String> {
+        ArgQuery::value(self.matches)
+    }
+
+    /// Key lifetime in seconds.
+    pub fn lifetime(&self) -> Option<u32> {
+        self.matches.get_one("lifetime").copied()
+    }
+
+    /// Whether the agent must confirm each use of the key.
+    pub fn confirm(&self) -> bool {
+        self.matches.get_flag("confirm")
+    }
+}
+
+impl<'a> Matcher<'a> for SshAddMatcher<'a> {
+    fn with(matches: &'a ArgMatches) -> Option<Self> {
+        matches
+            .subcommand_matches("ssh-add")
+            .map(|matches| SshAddMatcher { matches })
+    }
+}
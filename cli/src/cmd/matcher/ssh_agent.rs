@@ -0,0 +1,38 @@
+use std::path::PathBuf;
+
+use clap::ArgMatches;
+
+use super::Matcher;
+
+/// The ssh-agent command matcher.
+pub struct SshAgentMatcher<'a> {
+    matches: &'a ArgMatches,
+}
+
+impl SshAgentMatcher<'_> {
+    /// The secret queries.
+    pub fn queries(&self) -> Vec<String> {
+        self.matches
+            .get_many::<String>("QUERY")
+            .map(|q| q.cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Agent socket path.
+    pub fn socket(&self) -> Option<PathBuf> {
+        self.matches.get_one::<String>("socket").map(PathBuf::from)
+    }
+
+    /// Whether to confirm each use of a key.
+    pub fn confirm(&self) -> bool {
+        self.matches.get_flag("confirm")
+    }
+}
+
+impl<'a> Matcher<'a> for SshAgentMatcher<'a> {
+    fn with(matches: &'a ArgMatches) -> Option<Self> {
+        matches
+            .subcommand_matches("ssh-agent")
+            .map(|matches| SshAgentMatcher { matches })
+    }
+}
@@ -12,6 +12,11 @@ impl RemoteMatcher<'_> {
     pub fn git_url(&self) -> Option<&String> {
         self.matches.get_one("GIT_URL")
     }
+
+    /// Get the git remote name.
+    pub fn name(&self) -> Option<&String> {
+        self.matches.get_one("name")
+    }
 }
 
 impl<'a> Matcher<'a> for RemoteMatcher<'a> {
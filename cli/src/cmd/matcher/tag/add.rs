@@ -0,0 +1,40 @@
+use clap::ArgMatches;
+
+use super::Matcher;
+use crate::cmd::arg::{ArgAllowDirty, ArgNoSync, ArgQuery, CmdArgFlag, CmdArgOption};
+
+/// The tag add command matcher.
+pub struct AddMatcher<'a> {
+    matches: &'a ArgMatches,
+}
+
+impl AddMatcher<'_> {
+    /// The secret query.
+    pub fn query(&self) -> Option<String> {
+        ArgQuery::value(self.matches)
+    }
+
+    /// Tags to add.
+    pub fn tags(&self) -> Vec<&String> {
+        self.matches.get_many("TAG").unwrap().collect()
+    }
+
+    /// Whether to allow a dirty repository for syncing.
+    pub fn allow_dirty(&self) -> bool {
+        ArgAllowDirty::is_present(self.matches)
+    }
+
+    /// Whether to not sync.
+    pub fn no_sync(&self) -> bool {
+        ArgNoSync::is_present(self.matches)
+    }
+}
+
+impl<'a> Matcher<'a> for AddMatcher<'a> {
+    fn with(matches: &'a ArgMatches) -> Option<Self> {
+        matches
+            .subcommand_matches("tag")?
+            .subcommand_matches("add")
+            .map(|matches| AddMatcher { matches })
+    }
+}
@@ -0,0 +1,25 @@
+use clap::ArgMatches;
+
+use super::Matcher;
+use crate::cmd::arg::{ArgQuery, CmdArgOption};
+
+/// The tag list command matcher.
+pub struct ListMatcher<'a> {
+    matches: &'a ArgMatches,
+}
+
+impl ListMatcher<'_> {
+    /// The secret query.
+    pub fn query(&self) -> Option<String> {
+        ArgQuery::value(self.matches)
+    }
+}
+
+impl<'a> Matcher<'a> for ListMatcher<'a> {
+    fn with(matches: &'a ArgMatches) -> Option<Self> {
+        matches
+            .subcommand_matches("tag")?
+            .subcommand_matches("list")
+            .map(|matches| ListMatcher { matches })
+    }
+}
@@ -0,0 +1,39 @@
+pub mod add;
+pub mod list;
+pub mod remove;
+
+use clap::ArgMatches;
+
+use super::Matcher;
+
+/// The tag command matcher.
+pub struct TagMatcher<'a> {
+    root: &'a ArgMatches,
+    _matches: &'a ArgMatches,
+}
+
+impl<'a> TagMatcher<'a> {
+    /// Get the tag add sub command, if matched.
+    pub fn cmd_add(&'a self) -> Option<add::AddMatcher<'a>> {
+        add::AddMatcher::with(self.root)
+    }
+
+    /// Get the tag list sub command, if matched.
+    pub fn cmd_list(&'a self) -> Option<list::ListMatcher<'a>> {
+        list::ListMatcher::with(self.root)
+    }
+
+    /// Get the tag remove sub command, if matched.
+    pub fn cmd_remove(&'a self) -> Option<remove::RemoveMatcher<'a>> {
+        remove::RemoveMatcher::with(self.root)
+    }
+}
+
+impl<'a> Matcher<'a> for TagMatcher<'a> {
+    fn with(root: &'a ArgMatches) -> Option<Self> {
+        root.subcommand_matches("tag").map(|matches| TagMatcher {
+            root,
+            _matches: matches,
+        })
+    }
+}
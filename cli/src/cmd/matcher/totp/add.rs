@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use clap::ArgMatches;
+
+use super::Matcher;
+use crate::cmd::arg::{ArgAllowDirty, ArgNoSync, CmdArgFlag};
+
+/// The TOTP add command matcher.
+pub struct AddMatcher<'a> {
+    matches: &'a ArgMatches,
+}
+
+impl AddMatcher<'_> {
+    /// Secret destination.
+    pub fn name(&self) -> &String {
+        self.matches.get_one("NAME").unwrap()
+    }
+
+    /// Image file with QR code.
+    pub fn qr(&self) -> Option<&PathBuf> {
+        self.matches.get_one("qr")
+    }
+
+    /// Check whether to read QR code image from stdin.
+    pub fn qr_stdin(&self) -> bool {
+        self.matches.get_flag("qr-stdin")
+    }
+
+    /// Whether to allow a dirty repository for syncing.
+    pub fn allow_dirty(&self) -> bool {
+        ArgAllowDirty::is_present(self.matches)
+    }
+
+    /// Whether to not sync.
+    pub fn no_sync(&self) -> bool {
+        ArgNoSync::is_present(self.matches)
+    }
+}
+
+impl<'a> Matcher<'a> for AddMatcher<'a> {
+    fn with(matches: &'a ArgMatches) -> Option<Self> {
+        matches
+            .subcommand_matches("totp")?
+            .subcommand_matches("add")
+            .map(|matches| AddMatcher { matches })
+    }
+}
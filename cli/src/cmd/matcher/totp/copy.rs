@@ -2,7 +2,9 @@ use anyhow::Result;
 use clap::ArgMatches;
 
 use super::Matcher;
-use crate::cmd::arg::{ArgProperty, ArgQuery, ArgTimeout, CmdArgOption};
+use crate::cmd::arg::{
+    ArgAllowDirty, ArgNoSync, ArgProperty, ArgQuery, ArgTimeout, CmdArgFlag, CmdArgOption,
+};
 
 /// The TOTP copy command matcher.
 pub struct CopyMatcher<'a> {
@@ -29,6 +31,16 @@ impl CopyMatcher<'_> {
     pub fn property(&self) -> Option<&String> {
         ArgProperty::value(self.matches)
     }
+
+    /// Whether to allow a dirty repository for syncing.
+    pub fn allow_dirty(&self) -> bool {
+        ArgAllowDirty::is_present(self.matches)
+    }
+
+    /// Whether to not sync.
+    pub fn no_sync(&self) -> bool {
+        ArgNoSync::is_present(self.matches)
+    }
 }
 
 impl<'a> Matcher<'a> for CopyMatcher<'a> {
@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+
+use clap::ArgMatches;
+
+use super::Matcher;
+
+/// TOTP export format.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Plain list of otpauth URIs.
+    Uri,
+
+    /// Encrypted Aegis vault.
+    Aegis,
+
+    /// Plain andOTP JSON backup.
+    AndOtp,
+
+    /// Google Authenticator migration QR codes.
+    Google,
+}
+
+/// The TOTP export command matcher.
+pub struct ExportMatcher<'a> {
+    matches: &'a ArgMatches,
+}
+
+impl ExportMatcher<'_> {
+    /// The export format.
+    pub fn format(&self) -> ExportFormat {
+        match self.matches.get_one::<String>("FORMAT").map(|f| f.as_str()) {
+            Some("aegis") => ExportFormat::Aegis,
+            Some("andotp") => ExportFormat::AndOtp,
+            Some("google") => ExportFormat::Google,
+            _ => ExportFormat::Uri,
+        }
+    }
+
+    /// File to write to.
+    pub fn out(&self) -> Option<&PathBuf> {
+        self.matches.get_one("out")
+    }
+}
+
+impl<'a> Matcher<'a> for ExportMatcher<'a> {
+    fn with(matches: &'a ArgMatches) -> Option<Self> {
+        matches
+            .subcommand_matches("totp")?
+            .subcommand_matches("export")
+            .map(|matches| ExportMatcher { matches })
+    }
+}
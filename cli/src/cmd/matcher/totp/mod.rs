@@ -1,5 +1,7 @@
+pub mod add;
 #[cfg(feature = "clipboard")]
 pub mod copy;
+pub mod export;
 pub mod live;
 pub mod qr;
 pub mod show;
@@ -15,12 +17,22 @@ pub struct TotpMatcher<'a> {
 }
 
 impl<'a> TotpMatcher<'a> {
+    /// Get the TOTP add sub command, if matched.
+    pub fn cmd_add(&'a self) -> Option<add::AddMatcher<'a>> {
+        add::AddMatcher::with(self.root)
+    }
+
     /// Get the TOTP copy sub command, if matched.
     #[cfg(feature = "clipboard")]
     pub fn cmd_copy(&'a self) -> Option<copy::CopyMatcher<'a>> {
         copy::CopyMatcher::with(self.root)
     }
 
+    /// Get the TOTP export sub command, if matched.
+    pub fn cmd_export(&'a self) -> Option<export::ExportMatcher<'a>> {
+        export::ExportMatcher::with(self.root)
+    }
+
     /// Get the TOTP live sub command, if matched.
     pub fn cmd_live(&'a self) -> Option<live::LiveMatcher<'a>> {
         live::LiveMatcher::with(self.root)
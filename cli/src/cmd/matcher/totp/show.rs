@@ -2,7 +2,10 @@ use anyhow::Result;
 use clap::ArgMatches;
 
 use super::Matcher;
-use crate::cmd::arg::{ArgProperty, ArgQuery, ArgTimeout, ArgViewer, CmdArgFlag, CmdArgOption};
+use crate::cmd::arg::{
+    ArgAllowDirty, ArgNoSync, ArgProperty, ArgQuery, ArgTimeout, ArgViewer, CmdArgFlag,
+    CmdArgOption,
+};
 
 /// The TOTP show command matcher.
 pub struct ShowMatcher<'a> {
@@ -35,6 +38,16 @@ impl ShowMatcher<'_> {
     pub fn viewer(&self) -> bool {
         ArgViewer::is_present(self.matches) || self.timeout().is_some()
     }
+
+    /// Whether to allow a dirty repository for syncing.
+    pub fn allow_dirty(&self) -> bool {
+        ArgAllowDirty::is_present(self.matches)
+    }
+
+    /// Whether to not sync.
+    pub fn no_sync(&self) -> bool {
+        ArgNoSync::is_present(self.matches)
+    }
 }
 
 impl<'a> Matcher<'a> for ShowMatcher<'a> {
@@ -0,0 +1,44 @@
+use clap::ArgMatches;
+
+use super::Matcher;
+use crate::cmd::arg::{ArgAllowDirty, ArgNoSync, ArgQuery, CmdArgFlag, CmdArgOption};
+
+/// The unset command matcher.
+pub struct UnsetMatcher<'a> {
+    matches: &'a ArgMatches,
+}
+
+impl UnsetMatcher<'_> {
+    /// The secret query.
+    pub fn query(&self) -> Option<String> {
+        ArgQuery::value(self.matches)
+    }
+
+    /// The property name.
+    pub fn name(&self) -> &String {
+        self.matches.get_one("NAME").unwrap()
+    }
+
+    /// Whether to remove a field from a structured secret.
+    pub fn field(&self) -> bool {
+        self.matches.get_flag("field")
+    }
+
+    /// Whether to allow a dirty repository for syncing.
+    pub fn allow_dirty(&self) -> bool {
+        ArgAllowDirty::is_present(self.matches)
+    }
+
+    /// Whether to not sync.
+    pub fn no_sync(&self) -> bool {
+        ArgNoSync::is_present(self.matches)
+    }
+}
+
+impl<'a> Matcher<'a> for UnsetMatcher<'a> {
+    fn with(matches: &'a ArgMatches) -> Option<Self> {
+        matches
+            .subcommand_matches("unset")
+            .map(|matches| UnsetMatcher { matches })
+    }
+}
@@ -1,4 +1,5 @@
 pub mod arg;
+pub mod complete;
 pub mod handler;
 pub mod matcher;
 pub mod subcmd;
@@ -1,6 +1,6 @@
 use clap::{Arg, Command};
 
-use crate::cmd::arg::{ArgAllowDirty, ArgNoSync, ArgQuery, CmdArg};
+use crate::cmd::arg::{ArgAllowDirty, ArgExact, ArgNoSync, ArgQuery, CmdArg};
 
 /// The alias command definition.
 pub struct CmdAlias;
@@ -14,6 +14,7 @@ impl CmdAlias {
             .about("Alias/symlink a secret")
             .long_about("Alias/symlink a secret without duplicating its content")
             .arg(ArgQuery::build().required(true))
+            .arg(ArgExact::build())
             .arg(
                 Arg::new("DEST")
                     .help("Secret destination path")
@@ -0,0 +1,23 @@
+use clap::{Arg, Command, value_parser};
+
+use crate::cmd::arg::{ArgAllowDirty, ArgNoSync, CmdArg};
+
+/// The attach command definition.
+pub struct CmdAttach;
+
+impl CmdAttach {
+    pub fn build() -> Command {
+        Command::new("attach")
+            .alias("att")
+            .about("Add a file as binary secret")
+            .arg(Arg::new("NAME").help("Secret name and path").required(true))
+            .arg(
+                Arg::new("FILE")
+                    .help("File to attach, use - to read from stdin")
+                    .value_parser(value_parser!(std::path::PathBuf))
+                    .required(true),
+            )
+            .arg(ArgAllowDirty::build())
+            .arg(ArgNoSync::build())
+    }
+}
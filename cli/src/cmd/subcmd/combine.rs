@@ -0,0 +1,24 @@
+use clap::{Arg, ArgAction, Command, value_parser};
+
+use crate::cmd::arg::{ArgExact, ArgQuery, CmdArg};
+
+/// The combine command definition.
+pub struct CmdCombine;
+
+impl CmdCombine {
+    pub fn build() -> Command {
+        Command::new("combine")
+            .about("Reconstruct a split secret from its shares")
+            .arg(ArgQuery::build())
+            .arg(ArgExact::build())
+            .arg(
+                Arg::new("share")
+                    .long("share")
+                    .value_name("FILE")
+                    .num_args(1)
+                    .action(ArgAction::Append)
+                    .value_parser(value_parser!(std::path::PathBuf))
+                    .help("File with decrypted or encrypted shares, '-' for stdin"),
+            )
+    }
+}
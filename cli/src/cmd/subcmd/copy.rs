@@ -1,6 +1,6 @@
 use clap::{Arg, Command};
 
-use crate::cmd::arg::{ArgProperty, ArgQuery, ArgTimeout, CmdArg};
+use crate::cmd::arg::{ArgExact, ArgProperty, ArgQuery, ArgTimeout, CmdArg};
 
 /// The copy command definition.
 pub struct CmdCopy;
@@ -22,6 +22,7 @@ impl CmdCopy {
                     .help("Copy whole secret, not just first line"),
             )
             .arg(ArgQuery::build())
+            .arg(ArgExact::build())
             .arg(ArgTimeout::build())
             .arg(ArgProperty::build().conflicts_with("all"))
     }
@@ -1,6 +1,6 @@
 use clap::{Arg, Command};
 
-use crate::cmd::arg::{ArgAllowDirty, ArgNoSync, ArgQuery, CmdArg};
+use crate::cmd::arg::{ArgAllowDirty, ArgExact, ArgNoSync, ArgQuery, CmdArg};
 
 /// The duplicate command definition.
 pub struct CmdDuplicate;
@@ -12,6 +12,7 @@ impl CmdDuplicate {
             .about("Duplicate a secret")
             .long_about("Duplicate the contents of a secret to a new file")
             .arg(ArgQuery::build().required(true))
+            .arg(ArgExact::build())
             .arg(
                 Arg::new("DEST")
                     .help("Secret destination path")
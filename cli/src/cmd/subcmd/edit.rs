@@ -1,6 +1,6 @@
 use clap::{Arg, Command};
 
-use crate::cmd::arg::{ArgAllowDirty, ArgNoSync, ArgQuery, CmdArg};
+use crate::cmd::arg::{ArgAllowDirty, ArgExact, ArgNoSync, ArgQuery, CmdArg};
 
 /// The edit command definition.
 pub struct CmdEdit;
@@ -11,6 +11,7 @@ impl CmdEdit {
             .alias("e")
             .about("Edit a secret")
             .arg(ArgQuery::build())
+            .arg(ArgExact::build())
             .arg(
                 Arg::new("stdin")
                     .long("stdin")
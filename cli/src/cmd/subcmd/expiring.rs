@@ -0,0 +1,26 @@
+use clap::{Arg, Command};
+
+use crate::cmd::arg::{ArgJobs, ArgQuery, CmdArg};
+
+/// The expiring command definition.
+pub struct CmdExpiring;
+
+impl CmdExpiring {
+    pub fn build() -> Command {
+        Command::new("expiring")
+            .alias("expired")
+            .alias("due")
+            .about("List expired secrets or secrets due for rotation")
+            .arg(ArgQuery::build())
+            .arg(
+                Arg::new("within")
+                    .long("within")
+                    .short('w')
+                    .value_name("DURATION")
+                    .num_args(1)
+                    .default_value("14d")
+                    .help("Include secrets expiring within duration, such as 30d"),
+            )
+            .arg(ArgJobs::build())
+    }
+}
@@ -0,0 +1,25 @@
+use clap::{Arg, Command, value_parser};
+
+use crate::cmd::arg::{ArgExact, ArgQuery, CmdArg};
+
+/// The extract command definition.
+pub struct CmdExtract;
+
+impl CmdExtract {
+    pub fn build() -> Command {
+        Command::new("extract")
+            .alias("ext")
+            .about("Write a binary secret to a file")
+            .arg(ArgQuery::build())
+            .arg(ArgExact::build())
+            .arg(
+                Arg::new("out")
+                    .long("out")
+                    .short('o')
+                    .value_name("PATH")
+                    .num_args(1)
+                    .value_parser(value_parser!(std::path::PathBuf))
+                    .help("File to write to, defaults to stdout"),
+            )
+    }
+}
@@ -2,7 +2,7 @@ use clap::{Arg, Command};
 
 #[cfg(feature = "clipboard")]
 use crate::cmd::arg::ArgTimeout;
-use crate::cmd::arg::{ArgAllowDirty, ArgNoSync, CmdArg};
+use crate::cmd::arg::{ArgAllowDirty, ArgExact, ArgNoSync, CmdArg};
 
 /// The generate command definition.
 pub struct CmdGenerate;
@@ -25,6 +25,7 @@ impl CmdGenerate {
                         "copy",
                     ]),
             )
+            .arg(ArgExact::build())
             .arg(
                 Arg::new("passphrase")
                     .long("passphrase")
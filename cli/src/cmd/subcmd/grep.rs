@@ -1,6 +1,6 @@
 use clap::{Arg, Command};
 
-use crate::cmd::arg::{ArgQuery, CmdArg};
+use crate::cmd::arg::{ArgJobs, ArgQuery, ArgTag, CmdArg};
 
 /// The grep command definition.
 pub struct CmdGrep;
@@ -8,9 +8,13 @@ pub struct CmdGrep;
 impl CmdGrep {
     pub fn build() -> Command {
         Command::new("grep")
-            .alias("find")
-            .about("Grep all secrets")
-            .arg(Arg::new("PATTERN").required(true).help("Grep pattern"))
+            .visible_alias("find")
+            .about("Grep all secrets, or find secrets by tag")
+            .arg(
+                Arg::new("PATTERN")
+                    .required_unless_present("tag")
+                    .help("Grep pattern"),
+            )
             .arg(
                 ArgQuery::build()
                     .id("query")
@@ -18,6 +22,10 @@ impl CmdGrep {
                     .short('Q')
                     .help("Limit grep to secrets by query"),
             )
+            .arg(ArgTag::build().long_help(
+                "Only secrets with tag, may be repeated. Without pattern, tagged secrets are \
+                 listed without decrypting anything, as in 'find --tag <TAG>'.",
+            ))
             .arg(
                 Arg::new("regex")
                     .long("regex")
@@ -36,5 +44,12 @@ impl CmdGrep {
                     .num_args(0)
                     .help("Include grepping aliases"),
             )
+            .arg(
+                Arg::new("no-index")
+                    .long("no-index")
+                    .num_args(0)
+                    .help("Don't use search index, decrypt all secrets"),
+            )
+            .arg(ArgJobs::build())
     }
 }
@@ -0,0 +1,39 @@
+use clap::{Arg, Command};
+
+use crate::cmd::arg::{ArgJobs, CmdArg};
+
+/// The housekeeping index command definition.
+pub struct CmdIndex;
+
+impl CmdIndex {
+    pub fn build() -> Command {
+        Command::new("index")
+            .alias("reindex")
+            .alias("search-index")
+            .about("Build encrypted search index for fast grep")
+            .long_about(
+                "Build encrypted search index for fast grep.\n\n\
+                 The index holds the contents of all secrets in a single file encrypted to the \
+                 store recipients, so grep only has to decrypt once. It is kept up-to-date when \
+                 changing secrets, and is local to this machine. Secrets in directories with \
+                 their own .gpg-id file are not indexed, grep decrypts those directly.",
+            )
+            .arg(
+                Arg::new("check")
+                    .long("check")
+                    .short('c')
+                    .alias("verify")
+                    .num_args(0)
+                    .help("Check search index integrity, don't rebuild"),
+            )
+            .arg(
+                Arg::new("remove")
+                    .long("remove")
+                    .alias("delete")
+                    .num_args(0)
+                    .help("Remove the search index")
+                    .conflicts_with("check"),
+            )
+            .arg(ArgJobs::build())
+    }
+}
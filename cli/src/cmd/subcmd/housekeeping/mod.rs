@@ -1,3 +1,4 @@
+pub mod index;
 pub mod recrypt;
 pub mod run;
 pub mod sync_keys;
@@ -19,5 +20,6 @@ impl CmdHousekeeping {
             .subcommand(run::CmdRun::build())
             .subcommand(recrypt::CmdRecrypt::build())
             .subcommand(sync_keys::CmdSyncKeys::build())
+            .subcommand(index::CmdIndex::build())
     }
 }
@@ -1,6 +1,6 @@
 use clap::{Arg, Command};
 
-use crate::cmd::arg::{ArgAllowDirty, ArgNoSync, ArgQuery, CmdArg};
+use crate::cmd::arg::{ArgAllowDirty, ArgJobs, ArgNoSync, ArgQuery, CmdArg};
 
 /// The housekeeping recrypt command definition.
 pub struct CmdRecrypt;
@@ -19,6 +19,20 @@ impl CmdRecrypt {
                     .conflicts_with("QUERY"),
             )
             .arg(ArgQuery::build().required_unless_present("all"))
+            .arg(
+                Arg::new("full")
+                    .long("full")
+                    .num_args(0)
+                    .help("Re-encrypt secrets already encrypted for current recipients"),
+            )
+            .arg(
+                Arg::new("dry-run")
+                    .long("dry-run")
+                    .short('n')
+                    .num_args(0)
+                    .help("Show secrets that would be re-encrypted, don't change anything"),
+            )
+            .arg(ArgJobs::build())
             .arg(ArgAllowDirty::build())
             .arg(ArgNoSync::build())
     }
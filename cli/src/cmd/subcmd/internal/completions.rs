@@ -34,10 +34,9 @@ impl CmdCompletions {
                     .ignore_case(true),
             )
             .arg(
-                Arg::new("output")
-                    .long("output")
+                Arg::new("output-dir")
+                    .long("output-dir")
                     .short('o')
-                    .alias("output-dir")
                     .alias("out")
                     .alias("dir")
                     .num_args(1)
@@ -50,7 +49,7 @@ impl CmdCompletions {
                     .alias("print")
                     .num_args(0)
                     .help("Output completion files to stdout instead")
-                    .conflicts_with("output"),
+                    .conflicts_with("output-dir"),
             )
             .arg(
                 Arg::new("name")
@@ -1,6 +1,6 @@
 use clap::{Arg, Command};
 
-use crate::cmd::arg::{ArgQuery, CmdArg};
+use crate::cmd::arg::{ArgQuery, ArgTag, CmdArg};
 
 /// The list command definition.
 pub struct CmdList;
@@ -13,6 +13,7 @@ impl CmdList {
             .alias("search")
             .about("List all secrets")
             .arg(ArgQuery::build())
+            .arg(ArgTag::build())
             .arg(
                 Arg::new("list")
                     .long("list")
@@ -1,11 +1,15 @@
 pub mod add;
 #[cfg(feature = "alias")]
 pub mod alias;
+pub mod attach;
 pub mod clone;
+pub mod combine;
 #[cfg(feature = "clipboard")]
 pub mod copy;
 pub mod duplicate;
 pub mod edit;
+pub mod expiring;
+pub mod extract;
 pub mod generate;
 pub mod git;
 pub mod grep;
@@ -16,23 +20,37 @@ pub mod list;
 pub mod r#move;
 pub mod recipients;
 pub mod remove;
+pub mod rotate;
+pub mod set;
+pub mod share;
 pub mod show;
 pub mod slam;
+pub mod split;
+#[cfg(all(feature = "ssh", unix))]
+pub mod ssh_add;
+#[cfg(all(feature = "ssh", unix))]
+pub mod ssh_agent;
 pub mod sync;
+pub mod tag;
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 pub mod tomb;
 #[cfg(feature = "totp")]
 pub mod totp;
+pub mod unset;
 
 // Re-export to cmd module
 pub use self::add::CmdAdd;
 #[cfg(feature = "alias")]
 pub use self::alias::CmdAlias;
+pub use self::attach::CmdAttach;
 pub use self::clone::CmdClone;
+pub use self::combine::CmdCombine;
 #[cfg(feature = "clipboard")]
 pub use self::copy::CmdCopy;
 pub use self::duplicate::CmdDuplicate;
 pub use self::edit::CmdEdit;
+pub use self::expiring::CmdExpiring;
+pub use self::extract::CmdExtract;
 pub use self::generate::CmdGenerate;
 pub use self::git::CmdGit;
 pub use self::grep::CmdGrep;
@@ -43,10 +61,20 @@ pub use self::list::CmdList;
 pub use self::r#move::CmdMove;
 pub use self::recipients::CmdRecipients;
 pub use self::remove::CmdRemove;
+pub use self::rotate::CmdRotate;
+pub use self::set::CmdSet;
+pub use self::share::CmdShare;
 pub use self::show::CmdShow;
 pub use self::slam::CmdSlam;
+pub use self::split::CmdSplit;
+#[cfg(all(feature = "ssh", unix))]
+pub use self::ssh_add::CmdSshAdd;
+#[cfg(all(feature = "ssh", unix))]
+pub use self::ssh_agent::CmdSshAgent;
 pub use self::sync::CmdSync;
+pub use self::tag::CmdTag;
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 pub use self::tomb::CmdTomb;
 #[cfg(feature = "totp")]
 pub use self::totp::CmdTotp;
+pub use self::unset::CmdUnset;
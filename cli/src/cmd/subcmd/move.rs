@@ -1,6 +1,6 @@
 use clap::{Arg, Command};
 
-use crate::cmd::arg::{ArgAllowDirty, ArgNoSync, ArgQuery, CmdArg};
+use crate::cmd::arg::{ArgAllowDirty, ArgExact, ArgNoSync, ArgQuery, CmdArg};
 
 /// The move command definition.
 pub struct CmdMove;
@@ -15,6 +15,7 @@ impl CmdMove {
             .alias("ren")
             .about("Move a secret")
             .arg(ArgQuery::build().required(true))
+            .arg(ArgExact::build())
             .arg(
                 Arg::new("DEST")
                     .help("Secret destination path")
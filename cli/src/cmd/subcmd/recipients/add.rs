@@ -7,9 +7,23 @@ pub struct CmdAdd;
 
 impl CmdAdd {
     pub fn build() -> Command {
-        Command::new("add")
+        #[cfg_attr(not(feature = "keyserver"), expect(unused_mut))]
+        let mut cmd = Command::new("add")
             .alias("a")
             .about("Add store recipient")
+            .arg(
+                Arg::new("FINGERPRINT")
+                    .help("Fingerprint of key or @group to add, select interactively if not given"),
+            )
+            .arg(
+                Arg::new("key-file")
+                    .long("key-file")
+                    .alias("file")
+                    .short('k')
+                    .value_name("PATH")
+                    .num_args(1)
+                    .help("Import key to add from armored key file, use - for stdin"),
+            )
             .arg(
                 Arg::new("secret")
                     .long("secret")
@@ -27,6 +41,22 @@ impl CmdAdd {
                     .help("Skip re-encrypting all secrets"),
             )
             .arg(ArgAllowDirty::build())
-            .arg(ArgNoSync::build())
+            .arg(ArgNoSync::build());
+
+        #[cfg(feature = "keyserver")]
+        {
+            cmd = cmd.arg(
+                Arg::new("keyserver")
+                    .long("keyserver")
+                    .value_name("URL")
+                    .num_args(0..=1)
+                    .default_missing_value(crate::util::keyserver::DEFAULT_KEYSERVER)
+                    .requires("FINGERPRINT")
+                    .conflicts_with("key-file")
+                    .help("Fetch key to add from keyserver, defaults to keys.openpgp.org"),
+            );
+        }
+
+        cmd
     }
 }
@@ -0,0 +1,24 @@
+use clap::{Arg, Command};
+
+use crate::cmd::arg::{ArgAllowDirty, ArgJobs, ArgNoSync, ArgQuery, CmdArg};
+
+/// The recipient audit command definition.
+pub struct CmdAudit;
+
+impl CmdAudit {
+    pub fn build() -> Command {
+        Command::new("audit")
+            .alias("check")
+            .about("Find secrets not encrypted for the current recipients")
+            .arg(ArgQuery::build())
+            .arg(
+                Arg::new("fix")
+                    .long("fix")
+                    .num_args(0)
+                    .help("Re-encrypt secrets with different recipients"),
+            )
+            .arg(ArgJobs::build())
+            .arg(ArgAllowDirty::build())
+            .arg(ArgNoSync::build())
+    }
+}
@@ -1,5 +1,7 @@
 use clap::{Arg, Command};
 
+use crate::cmd::complete::{self, ArgComplete};
+
 /// The recipient export command definition.
 pub struct CmdExport;
 
@@ -10,11 +12,15 @@ impl CmdExport {
             .alias("exp")
             .alias("ex")
             .about("Export recipient key")
+            .arg(
+                Arg::new("FINGERPRINT")
+                    .help("Fingerprint of recipient to export, select interactively if not given")
+                    .complete(complete::recipients),
+            )
             .arg(
                 Arg::new("output-file")
                     .long("output-file")
                     .short('o')
-                    .alias("output")
                     .alias("file")
                     .value_name("PATH")
                     .num_args(1)
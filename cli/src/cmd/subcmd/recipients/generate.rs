@@ -1,4 +1,6 @@
-use clap::{Arg, Command};
+use std::path::PathBuf;
+
+use clap::{Arg, Command, value_parser};
 
 use crate::cmd::arg::{ArgAllowDirty, ArgNoSync, CmdArg};
 
@@ -11,6 +13,37 @@ impl CmdGenerate {
             .alias("gen")
             .alias("g")
             .about("Generate new key pair, add it to the store")
+            .arg(
+                Arg::new("name")
+                    .long("name")
+                    .value_name("NAME")
+                    .num_args(1)
+                    .help("Name for the new key"),
+            )
+            .arg(
+                Arg::new("email")
+                    .long("email")
+                    .value_name("EMAIL")
+                    .num_args(1)
+                    .help("Email address for the new key"),
+            )
+            .arg(
+                Arg::new("expire")
+                    .long("expire")
+                    .alias("expires")
+                    .value_name("DURATION")
+                    .num_args(1)
+                    .default_value("104w")
+                    .help("Expire key after duration, 0 to never expire"),
+            )
+            .arg(
+                Arg::new("secret-key-file")
+                    .long("secret-key-file")
+                    .value_name("FILE")
+                    .num_args(1)
+                    .value_parser(value_parser!(PathBuf))
+                    .help("File to write secret key to, required if the backend cannot keep it"),
+            )
             .arg(
                 Arg::new("no-add")
                     .long("no-add")
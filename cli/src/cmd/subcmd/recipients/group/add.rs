@@ -0,0 +1,41 @@
+use clap::{Arg, Command};
+
+use crate::cmd::{
+    arg::{ArgAllowDirty, ArgNoSync, CmdArg},
+    complete::{self, ArgComplete},
+};
+
+/// The recipient group add command definition.
+pub struct CmdAdd;
+
+impl CmdAdd {
+    pub fn build() -> Command {
+        Command::new("add")
+            .alias("a")
+            .alias("new")
+            .about("Add members to recipient group, creates group if it doesn't exist")
+            .arg(
+                Arg::new("GROUP")
+                    .help("Name of recipient group")
+                    .required(true)
+                    .complete(complete::groups),
+            )
+            .arg(
+                Arg::new("FINGERPRINT")
+                    .help("Fingerprints of keys to add to group")
+                    .required(true)
+                    .num_args(1..),
+            )
+            .arg(
+                Arg::new("no-recrypt")
+                    .long("no-recrypt")
+                    .alias("no-reencrypt")
+                    .alias("skip-recrypt")
+                    .alias("skip-reencrypt")
+                    .num_args(0)
+                    .help("Skip re-encrypting secrets of group"),
+            )
+            .arg(ArgAllowDirty::build())
+            .arg(ArgNoSync::build())
+    }
+}
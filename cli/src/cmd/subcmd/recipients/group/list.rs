@@ -0,0 +1,20 @@
+use clap::{Arg, Command};
+
+use crate::cmd::complete::{self, ArgComplete};
+
+/// The recipient group list command definition.
+pub struct CmdList;
+
+impl CmdList {
+    pub fn build() -> Command {
+        Command::new("list")
+            .alias("ls")
+            .alias("l")
+            .about("List recipient groups and their members")
+            .arg(
+                Arg::new("GROUP")
+                    .help("Name of recipient group to list members for")
+                    .complete(complete::groups),
+            )
+    }
+}
@@ -0,0 +1,23 @@
+pub mod add;
+pub mod list;
+pub mod remove;
+
+use clap::Command;
+
+/// The recipient group command definition.
+pub struct CmdGroup;
+
+impl CmdGroup {
+    pub fn build() -> Command {
+        Command::new("group")
+            .alias("groups")
+            .alias("grp")
+            .about("Manage recipient groups")
+            .arg_required_else_help(true)
+            .subcommand_required(true)
+            .subcommand_value_name("CMD")
+            .subcommand(add::CmdAdd::build())
+            .subcommand(remove::CmdRemove::build())
+            .subcommand(list::CmdList::build())
+    }
+}
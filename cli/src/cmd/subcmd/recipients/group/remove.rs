@@ -0,0 +1,43 @@
+use clap::{Arg, Command};
+
+use crate::cmd::{
+    arg::{ArgAllowDirty, ArgNoSync, CmdArg},
+    complete::{self, ArgComplete},
+};
+
+/// The recipient group remove command definition.
+pub struct CmdRemove;
+
+impl CmdRemove {
+    pub fn build() -> Command {
+        Command::new("remove")
+            .alias("rm")
+            .alias("delete")
+            .alias("del")
+            .about("Remove members from recipient group")
+            .arg(
+                Arg::new("GROUP")
+                    .help("Name of recipient group")
+                    .required(true)
+                    .complete(complete::groups),
+            )
+            .arg(
+                Arg::new("FINGERPRINT")
+                    .help("Fingerprints of keys to remove from group")
+                    .required(true)
+                    .num_args(1..)
+                    .complete(complete::recipients),
+            )
+            .arg(
+                Arg::new("no-recrypt")
+                    .long("no-recrypt")
+                    .alias("no-reencrypt")
+                    .alias("skip-recrypt")
+                    .alias("skip-reencrypt")
+                    .num_args(0)
+                    .help("Skip re-encrypting secrets of group"),
+            )
+            .arg(ArgAllowDirty::build())
+            .arg(ArgNoSync::build())
+    }
+}
@@ -1,8 +1,12 @@
 pub mod add;
+pub mod audit;
 pub mod export;
 pub mod generate;
+pub mod group;
 pub mod list;
 pub mod remove;
+pub mod rotate;
+pub mod trust;
 
 use clap::Command;
 
@@ -22,9 +26,13 @@ impl CmdRecipients {
             .subcommand_required(true)
             .subcommand_value_name("CMD")
             .subcommand(add::CmdAdd::build())
+            .subcommand(audit::CmdAudit::build())
             .subcommand(generate::CmdGenerate::build())
+            .subcommand(group::CmdGroup::build())
             .subcommand(list::CmdList::build())
             .subcommand(remove::CmdRemove::build())
+            .subcommand(rotate::CmdRotate::build())
+            .subcommand(trust::CmdTrust::build())
             .subcommand(export::CmdExport::build())
     }
 }
@@ -1,6 +1,9 @@
 use clap::{Arg, Command};
 
-use crate::cmd::arg::{ArgAllowDirty, ArgNoSync, CmdArg};
+use crate::cmd::{
+    arg::{ArgAllowDirty, ArgNoSync, CmdArg},
+    complete::{self, ArgComplete},
+};
 
 /// The recipient remove command definition.
 pub struct CmdRemove;
@@ -12,6 +15,11 @@ impl CmdRemove {
             .alias("delete")
             .alias("del")
             .about("Remove store recipient")
+            .arg(
+                Arg::new("FINGERPRINT")
+                    .help("Fingerprint or @group to remove, select interactively if not given")
+                    .complete(complete::recipients),
+            )
             .arg(
                 Arg::new("recrypt")
                     .long("recrypt")
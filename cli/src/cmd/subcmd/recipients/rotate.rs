@@ -0,0 +1,33 @@
+use clap::{Arg, Command};
+
+use crate::cmd::{
+    arg::{ArgAllowDirty, ArgJobs, ArgNoSync, CmdArg},
+    complete::{self, ArgComplete},
+};
+
+/// The recipient rotate command definition.
+pub struct CmdRotate;
+
+impl CmdRotate {
+    pub fn build() -> Command {
+        Command::new("rotate")
+            .alias("replace")
+            .about("Replace a recipient key with a new key")
+            .arg(
+                Arg::new("from")
+                    .long("from")
+                    .value_name("FINGERPRINT")
+                    .help("Fingerprint of recipient to replace, select interactively if not given")
+                    .complete(complete::recipients),
+            )
+            .arg(
+                Arg::new("to")
+                    .long("to")
+                    .value_name("FINGERPRINT")
+                    .help("Fingerprint of new key, select own key interactively if not given"),
+            )
+            .arg(ArgJobs::build())
+            .arg(ArgAllowDirty::build())
+            .arg(ArgNoSync::build())
+    }
+}
@@ -0,0 +1,27 @@
+use clap::{Arg, Command};
+
+use crate::cmd::complete::{self, ArgComplete};
+
+/// The recipient trust command definition.
+pub struct CmdTrust;
+
+impl CmdTrust {
+    pub fn build() -> Command {
+        Command::new("trust")
+            .alias("pin")
+            .about("Pin store recipient key as trusted, list pinned keys if not given")
+            .arg(
+                Arg::new("FINGERPRINT")
+                    .help("Fingerprint of recipient to trust")
+                    .complete(complete::recipients),
+            )
+            .arg(
+                Arg::new("remove")
+                    .long("remove")
+                    .alias("untrust")
+                    .num_args(0)
+                    .requires("FINGERPRINT")
+                    .help("Remove recipient from trusted keys"),
+            )
+    }
+}
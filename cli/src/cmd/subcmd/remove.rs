@@ -1,6 +1,6 @@
 use clap::Command;
 
-use crate::cmd::arg::{ArgAllowDirty, ArgNoSync, ArgQuery, CmdArg};
+use crate::cmd::arg::{ArgAllowDirty, ArgExact, ArgNoSync, ArgQuery, CmdArg};
 
 /// The remove command definition.
 pub struct CmdRemove;
@@ -14,6 +14,7 @@ impl CmdRemove {
             .alias("yeet")
             .about("Remove a secret")
             .arg(ArgQuery::build())
+            .arg(ArgExact::build())
             .arg(ArgAllowDirty::build())
             .arg(ArgNoSync::build())
     }
@@ -0,0 +1,65 @@
+use clap::{Arg, Command};
+
+#[cfg(feature = "clipboard")]
+use crate::cmd::arg::ArgTimeout;
+use crate::cmd::arg::{ArgAllowDirty, ArgExact, ArgNoSync, ArgQuery, CmdArg};
+
+/// The rotate command definition.
+pub struct CmdRotate;
+
+impl CmdRotate {
+    pub fn build() -> Command {
+        #[cfg_attr(not(feature = "clipboard"), expect(clippy::let_and_return))]
+        let cmd = Command::new("rotate")
+            .alias("rot")
+            .alias("renew")
+            .about("Rotate secret password, keep other lines")
+            .arg(ArgQuery::build())
+            .arg(ArgExact::build())
+            .arg(
+                Arg::new("passphrase")
+                    .long("passphrase")
+                    .short('P')
+                    .num_args(0)
+                    .help("Generate passhprase instead of random string"),
+            )
+            .arg(
+                Arg::new("length")
+                    .value_name("NUM")
+                    .long("length")
+                    .short('l')
+                    .alias("len")
+                    .num_args(1)
+                    .value_parser(clap::value_parser!(u16))
+                    .help("Generated password length in characters")
+                    .long_help(
+                        "Generated password length in characters. Passphrase length in words.",
+                    ),
+            )
+            .arg(
+                Arg::new("show")
+                    .long("show")
+                    .alias("cat")
+                    .alias("display")
+                    .alias("stdout")
+                    .num_args(0)
+                    .help("Display new password after rotation"),
+            )
+            .arg(ArgAllowDirty::build())
+            .arg(ArgNoSync::build());
+
+        #[cfg(feature = "clipboard")]
+        let cmd = cmd
+            .arg(
+                Arg::new("copy")
+                    .long("copy")
+                    .short('c')
+                    .alias("cp")
+                    .num_args(0)
+                    .help("Copy new password to clipboard"),
+            )
+            .arg(ArgTimeout::build().requires("copy"));
+
+        cmd
+    }
+}
@@ -0,0 +1,67 @@
+use clap::{Arg, Command, value_parser};
+
+use crate::cmd::{
+    arg::{ArgAllowDirty, ArgExact, ArgNoSync, ArgQuery, CmdArg},
+    complete::{self, ArgComplete},
+};
+
+/// The set command definition.
+pub struct CmdSet;
+
+impl CmdSet {
+    pub fn build() -> Command {
+        Command::new("set")
+            .about("Set a property in a secret")
+            .arg(ArgQuery::build().required(true))
+            .arg(ArgExact::build())
+            .arg(
+                Arg::new("NAME")
+                    .help("Property name")
+                    .required(true)
+                    .complete(complete::properties),
+            )
+            .arg(
+                Arg::new("VALUE")
+                    .help("Property value")
+                    .required_unless_present_any(["stdin", "generate"]),
+            )
+            .arg(
+                Arg::new("stdin")
+                    .long("stdin")
+                    .short('S')
+                    .alias("from-stdin")
+                    .num_args(0)
+                    .help("Read property value from stdin")
+                    .conflicts_with_all(["VALUE", "generate"]),
+            )
+            .arg(
+                Arg::new("generate")
+                    .long("generate")
+                    .short('g')
+                    .alias("gen")
+                    .num_args(0)
+                    .help("Generate a secure random property value")
+                    .conflicts_with("VALUE"),
+            )
+            .arg(
+                Arg::new("length")
+                    .value_name("NUM")
+                    .long("length")
+                    .short('l')
+                    .alias("len")
+                    .num_args(1)
+                    .value_parser(value_parser!(u16))
+                    .requires("generate")
+                    .help("Generated value length in characters"),
+            )
+            .arg(
+                Arg::new("field")
+                    .long("field")
+                    .short('F')
+                    .num_args(0)
+                    .help("Set a field in a structured secret, converting the secret if needed"),
+            )
+            .arg(ArgAllowDirty::build())
+            .arg(ArgNoSync::build())
+    }
+}
@@ -0,0 +1,36 @@
+use clap::{Arg, Command, value_parser};
+
+use crate::cmd::arg::{ArgAllowDirty, ArgExact, ArgNoSync, ArgProperty, ArgQuery, CmdArg};
+
+/// The share command definition.
+pub struct CmdShare;
+
+impl CmdShare {
+    pub fn build() -> Command {
+        Command::new("share")
+            .about("Encrypt a secret for someone outside the store")
+            .arg(ArgQuery::build())
+            .arg(ArgExact::build())
+            .arg(
+                Arg::new("to")
+                    .long("to")
+                    .short('t')
+                    .value_name("RECIPIENT")
+                    .num_args(1)
+                    .required(true)
+                    .help("Fingerprint, public key file or age recipient to share with"),
+            )
+            .arg(ArgProperty::build())
+            .arg(
+                Arg::new("out")
+                    .long("out")
+                    .short('o')
+                    .value_name("PATH")
+                    .num_args(1)
+                    .value_parser(value_parser!(std::path::PathBuf))
+                    .help("File to write to, defaults to stdout"),
+            )
+            .arg(ArgAllowDirty::build())
+            .arg(ArgNoSync::build())
+    }
+}
@@ -1,6 +1,9 @@
 use clap::{Arg, Command};
 
-use crate::cmd::arg::{ArgProperty, ArgQuery, ArgTimeout, ArgViewer, CmdArg};
+use crate::cmd::{
+    arg::{ArgExact, ArgProperty, ArgQuery, ArgTimeout, ArgViewer, CmdArg},
+    complete::{self, ArgComplete},
+};
 
 /// The show command definition.
 pub struct CmdShow;
@@ -23,12 +26,23 @@ impl CmdShow {
                     .help("Show only the first line of the secret"),
             )
             .arg(ArgQuery::build())
+            .arg(ArgExact::build())
             .arg(
                 ArgTimeout::build()
                     .conflicts_with_all(["no-interact", "viewer"])
                     .help("Timeout after which to clear output, implies --viewer"),
             )
             .arg(ArgProperty::build().conflicts_with("first"))
+            .arg(
+                Arg::new("field")
+                    .long("field")
+                    .short('F')
+                    .value_name("NAME")
+                    .num_args(1)
+                    .conflicts_with_all(["first", "property"])
+                    .help("Select a field from a structured secret")
+                    .complete(complete::properties),
+            )
             .arg(ArgViewer::build());
 
         #[cfg(feature = "clipboard")]
@@ -0,0 +1,46 @@
+use clap::{Arg, ArgAction, Command, value_parser};
+
+use crate::cmd::arg::{ArgAllowDirty, ArgExact, ArgNoSync, ArgQuery, CmdArg};
+
+/// The split command definition.
+pub struct CmdSplit;
+
+impl CmdSplit {
+    pub fn build() -> Command {
+        Command::new("split")
+            .about("Split a secret into shares for multiple recipients")
+            .arg(ArgQuery::build())
+            .arg(ArgExact::build())
+            .arg(
+                Arg::new("to")
+                    .long("to")
+                    .short('t')
+                    .value_name("RECIPIENT")
+                    .num_args(1..)
+                    .action(ArgAction::Append)
+                    .required(true)
+                    .help("Fingerprints or public key files to encrypt a share for, one each"),
+            )
+            .arg(
+                Arg::new("threshold")
+                    .long("threshold")
+                    .short('m')
+                    .value_name("NUM")
+                    .num_args(1)
+                    .required(true)
+                    .value_parser(value_parser!(u8).range(2..))
+                    .help("Number of shares needed to reconstruct the secret"),
+            )
+            .arg(
+                Arg::new("shares")
+                    .long("shares")
+                    .short('n')
+                    .value_name("NUM")
+                    .num_args(1)
+                    .value_parser(value_parser!(u8).range(2..))
+                    .help("Number of shares, defaults to the number of recipients"),
+            )
+            .arg(ArgAllowDirty::build())
+            .arg(ArgNoSync::build())
+    }
+}
@@ -0,0 +1,32 @@
+use clap::{Arg, Command};
+
+use crate::cmd::arg::{ArgExact, ArgQuery, CmdArg};
+
+/// The ssh-add command definition.
+pub struct CmdSshAdd;
+
+impl CmdSshAdd {
+    pub fn build() -> Command {
+        Command::new("ssh-add")
+            .about("Add SSH key from secret to ssh-agent")
+            .arg(ArgQuery::build())
+            .arg(ArgExact::build())
+            .arg(
+                Arg::new("lifetime")
+                    .long("lifetime")
+                    .short('t')
+                    .alias("life")
+                    .value_name("SECONDS")
+                    .num_args(1)
+                    .value_parser(clap::value_parser!(u32))
+                    .help("Lifetime after which the agent drops the key"),
+            )
+            .arg(
+                Arg::new("confirm")
+                    .long("confirm")
+                    .short('c')
+                    .num_args(0)
+                    .help("Require agent to confirm each use of the key"),
+            )
+    }
+}
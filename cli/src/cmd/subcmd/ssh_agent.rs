@@ -0,0 +1,36 @@
+use clap::{Arg, ArgAction, Command};
+
+use crate::cmd::arg::{ArgExact, CmdArg};
+
+/// The ssh-agent command definition.
+pub struct CmdSshAgent;
+
+impl CmdSshAgent {
+    pub fn build() -> Command {
+        Command::new("ssh-agent")
+            .about("Run SSH agent serving SSH keys from secrets")
+            .arg(
+                Arg::new("QUERY")
+                    .num_args(1..)
+                    .action(ArgAction::Append)
+                    .help("Secret queries for SSH keys to serve"),
+            )
+            .arg(ArgExact::build())
+            .arg(
+                Arg::new("socket")
+                    .long("socket")
+                    .short('a')
+                    .alias("bind")
+                    .value_name("PATH")
+                    .num_args(1)
+                    .help("Agent socket path to bind to"),
+            )
+            .arg(
+                Arg::new("confirm")
+                    .long("confirm")
+                    .short('c')
+                    .num_args(0)
+                    .help("Confirm each use of a key"),
+            )
+    }
+}
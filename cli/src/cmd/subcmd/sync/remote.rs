@@ -1,5 +1,7 @@
 use clap::{Arg, Command};
 
+use crate::cmd::complete::{self, ArgComplete};
+
 /// The sync remote command definition.
 pub struct CmdRemote;
 
@@ -8,5 +10,15 @@ impl CmdRemote {
         Command::new("remote")
             .about("Get or set git remote URL for sync")
             .arg(Arg::new("GIT_URL").help("Remote git URL to set"))
+            .arg(
+                Arg::new("name")
+                    .long("name")
+                    .short('n')
+                    .alias("remote")
+                    .value_name("REMOTE")
+                    .num_args(1)
+                    .help("Name of git remote, required if multiple are configured")
+                    .complete(complete::remotes),
+            )
     }
 }
@@ -0,0 +1,29 @@
+use clap::{Arg, Command};
+
+use crate::cmd::{
+    arg::{ArgAllowDirty, ArgExact, ArgNoSync, ArgQuery, CmdArg},
+    complete::{self, ArgComplete},
+};
+
+/// The tag add command definition.
+pub struct CmdAdd;
+
+impl CmdAdd {
+    pub fn build() -> Command {
+        Command::new("add")
+            .alias("a")
+            .alias("set")
+            .about("Add tags to a secret")
+            .arg(ArgQuery::build().required(true))
+            .arg(ArgExact::build())
+            .arg(
+                Arg::new("TAG")
+                    .help("Tags to add")
+                    .required(true)
+                    .num_args(1..)
+                    .complete(complete::tags),
+            )
+            .arg(ArgAllowDirty::build())
+            .arg(ArgNoSync::build())
+    }
+}
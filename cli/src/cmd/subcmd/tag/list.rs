@@ -0,0 +1,17 @@
+use clap::Command;
+
+use crate::cmd::arg::{ArgExact, ArgQuery, CmdArg};
+
+/// The tag list command definition.
+pub struct CmdList;
+
+impl CmdList {
+    pub fn build() -> Command {
+        Command::new("list")
+            .alias("ls")
+            .alias("l")
+            .about("List tags, all or of a secret")
+            .arg(ArgQuery::build())
+            .arg(ArgExact::build())
+    }
+}
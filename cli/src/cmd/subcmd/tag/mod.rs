@@ -0,0 +1,22 @@
+pub mod add;
+pub mod list;
+pub mod remove;
+
+use clap::Command;
+
+/// The tag command definition.
+pub struct CmdTag;
+
+impl CmdTag {
+    pub fn build() -> Command {
+        Command::new("tag")
+            .alias("tags")
+            .about("Manage secret tags")
+            .arg_required_else_help(true)
+            .subcommand_required(true)
+            .subcommand_value_name("CMD")
+            .subcommand(add::CmdAdd::build())
+            .subcommand(remove::CmdRemove::build())
+            .subcommand(list::CmdList::build())
+    }
+}
@@ -0,0 +1,29 @@
+use clap::{Arg, Command};
+
+use crate::cmd::{
+    arg::{ArgAllowDirty, ArgExact, ArgNoSync, ArgQuery, CmdArg},
+    complete::{self, ArgComplete},
+};
+
+/// The tag remove command definition.
+pub struct CmdRemove;
+
+impl CmdRemove {
+    pub fn build() -> Command {
+        Command::new("remove")
+            .alias("rm")
+            .alias("delete")
+            .about("Remove tags from a secret")
+            .arg(ArgQuery::build().required(true))
+            .arg(ArgExact::build())
+            .arg(
+                Arg::new("TAG")
+                    .help("Tags to remove")
+                    .required(true)
+                    .num_args(1..)
+                    .complete(complete::tags),
+            )
+            .arg(ArgAllowDirty::build())
+            .arg(ArgNoSync::build())
+    }
+}
@@ -0,0 +1,46 @@
+use clap::{Arg, ArgGroup, Command, value_parser};
+
+use crate::cmd::{
+    arg::{ArgAllowDirty, ArgNoSync, CmdArg},
+    complete::{self, ArgComplete},
+};
+
+/// The TOTP add command definition.
+pub struct CmdAdd;
+
+impl CmdAdd {
+    pub fn build() -> Command {
+        Command::new("add")
+            .alias("a")
+            .alias("new")
+            .alias("enroll")
+            .about("Add TOTP secret from QR code image")
+            .arg(
+                Arg::new("NAME")
+                    .help("Secret name and path, may be an existing secret")
+                    .required(true)
+                    .complete(complete::secrets),
+            )
+            .arg(
+                Arg::new("qr")
+                    .long("qr")
+                    .short('Q')
+                    .value_name("IMAGE")
+                    .value_parser(value_parser!(std::path::PathBuf))
+                    .help("Image file with QR code to decode"),
+            )
+            .arg(
+                Arg::new("qr-stdin")
+                    .long("qr-stdin")
+                    .num_args(0)
+                    .help("Read image with QR code from stdin"),
+            )
+            .group(
+                ArgGroup::new("source")
+                    .args(["qr", "qr-stdin"])
+                    .required(true),
+            )
+            .arg(ArgAllowDirty::build())
+            .arg(ArgNoSync::build())
+    }
+}
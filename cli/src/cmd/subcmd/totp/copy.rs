@@ -1,6 +1,8 @@
 use clap::{Arg, Command};
 
-use crate::cmd::arg::{ArgProperty, ArgQuery, ArgStore, ArgTimeout, CmdArg};
+use crate::cmd::arg::{
+    ArgAllowDirty, ArgExact, ArgNoSync, ArgProperty, ArgQuery, ArgStore, ArgTimeout, CmdArg,
+};
 
 /// The TOTP copy command definition.
 pub struct CmdCopy;
@@ -15,6 +17,7 @@ impl CmdCopy {
             .alias("clipboard")
             .about("Copy TOTP token to clipboard")
             .arg(ArgQuery::build())
+            .arg(ArgExact::build())
             .arg(ArgTimeout::build())
             .arg(ArgStore::build())
             .arg(ArgProperty::build())
@@ -25,5 +28,7 @@ impl CmdCopy {
                     .num_args(0)
                     .help("Don't recopy token when it changes within the timeout"),
             )
+            .arg(ArgAllowDirty::build())
+            .arg(ArgNoSync::build())
     }
 }
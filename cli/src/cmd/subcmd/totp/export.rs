@@ -0,0 +1,28 @@
+use clap::{Arg, Command, value_parser};
+
+/// The TOTP export command definition.
+pub struct CmdExport;
+
+impl CmdExport {
+    pub fn build() -> Command {
+        Command::new("export")
+            .alias("exp")
+            .alias("migrate")
+            .about("Export all TOTP secrets to another authenticator")
+            .arg(
+                Arg::new("FORMAT")
+                    .value_parser(["uri", "aegis", "andotp", "google"])
+                    .default_value("uri")
+                    .help("Export format: otpauth URI list, encrypted Aegis vault, andOTP JSON or Google Authenticator QR codes"),
+            )
+            .arg(
+                Arg::new("out")
+                    .long("out")
+                    .short('o')
+                    .value_name("PATH")
+                    .num_args(1)
+                    .value_parser(value_parser!(std::path::PathBuf))
+                    .help("File to write to, defaults to stdout"),
+            )
+    }
+}
@@ -1,6 +1,6 @@
 use clap::{Arg, Command};
 
-use crate::cmd::arg::{ArgProperty, ArgQuery, CmdArg};
+use crate::cmd::arg::{ArgExact, ArgProperty, ArgQuery, CmdArg};
 
 /// The TOTP live command definition.
 pub struct CmdLive;
@@ -15,6 +15,7 @@ impl CmdLive {
             .alias("f")
             .about("Watch TOTP token")
             .arg(ArgQuery::build())
+            .arg(ArgExact::build())
             .arg(ArgProperty::build())
             .arg(
                 Arg::new("follow")
@@ -1,5 +1,7 @@
+pub mod add;
 #[cfg(feature = "clipboard")]
 pub mod copy;
+pub mod export;
 pub mod live;
 pub mod qr;
 pub mod show;
@@ -25,5 +27,7 @@ impl CmdTotp {
 
         cmd.subcommand(live::CmdLive::build())
             .subcommand(qr::CmdQr::build())
+            .subcommand(add::CmdAdd::build())
+            .subcommand(export::CmdExport::build())
     }
 }
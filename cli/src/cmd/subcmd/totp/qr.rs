@@ -1,6 +1,6 @@
 use clap::Command;
 
-use crate::cmd::arg::{ArgProperty, ArgQuery, CmdArg};
+use crate::cmd::arg::{ArgExact, ArgProperty, ArgQuery, CmdArg};
 
 /// The TOTP QR code command definition.
 pub struct CmdQr;
@@ -14,6 +14,7 @@ impl CmdQr {
             .alias("share")
             .about("Show TOTP QR code")
             .arg(ArgQuery::build())
+            .arg(ArgExact::build())
             .arg(ArgProperty::build())
     }
 }
@@ -2,7 +2,9 @@
 use clap::Arg;
 use clap::Command;
 
-use crate::cmd::arg::{ArgProperty, ArgQuery, ArgTimeout, ArgViewer, CmdArg};
+use crate::cmd::arg::{
+    ArgAllowDirty, ArgExact, ArgNoSync, ArgProperty, ArgQuery, ArgTimeout, ArgViewer, CmdArg,
+};
 
 /// The TOTP show command definition.
 pub struct CmdShow;
@@ -16,13 +18,16 @@ impl CmdShow {
             .alias("print")
             .about("Show TOTP token")
             .arg(ArgQuery::build())
+            .arg(ArgExact::build())
             .arg(
                 ArgTimeout::build()
                     .conflicts_with_all(["no-interact", "viewer"])
                     .help("Timeout after which to clear output, implies --viewer"),
             )
             .arg(ArgProperty::build())
-            .arg(ArgViewer::build());
+            .arg(ArgViewer::build())
+            .arg(ArgAllowDirty::build())
+            .arg(ArgNoSync::build());
 
         #[cfg(feature = "clipboard")]
         let cmd = cmd.arg(
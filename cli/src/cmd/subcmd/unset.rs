@@ -0,0 +1,33 @@
+use clap::{Arg, Command};
+
+use crate::cmd::{
+    arg::{ArgAllowDirty, ArgExact, ArgNoSync, ArgQuery, CmdArg},
+    complete::{self, ArgComplete},
+};
+
+/// The unset command definition.
+pub struct CmdUnset;
+
+impl CmdUnset {
+    pub fn build() -> Command {
+        Command::new("unset")
+            .about("Remove a property from a secret")
+            .arg(ArgQuery::build().required(true))
+            .arg(ArgExact::build())
+            .arg(
+                Arg::new("NAME")
+                    .help("Property name")
+                    .required(true)
+                    .complete(complete::properties),
+            )
+            .arg(
+                Arg::new("field")
+                    .long("field")
+                    .short('F')
+                    .num_args(0)
+                    .help("Remove a field from a structured secret"),
+            )
+            .arg(ArgAllowDirty::build())
+            .arg(ArgNoSync::build())
+    }
+}
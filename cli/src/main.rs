@@ -36,6 +36,10 @@ fn main() {
     #[cfg(windows)]
     colored::control::set_override(false);
 
+    // Handle dynamic shell completion requests
+    #[cfg(feature = "dynamic-completions")]
+    clap_complete::CompleteEnv::with_factory(Handler::build).complete();
+
     // Parse CLI arguments
     let cmd_handler = Handler::parse();
 
@@ -60,10 +64,18 @@ fn invoke_action(handler: &Handler) -> Result<()> {
         return action::alias::Alias::new(handler.matches()).invoke();
     }
 
+    if handler.attach().is_some() {
+        return action::attach::Attach::new(handler.matches()).invoke();
+    }
+
     if handler.clone().is_some() {
         return action::clone::Clone::new(handler.matches()).invoke();
     }
 
+    if handler.combine().is_some() {
+        return action::combine::Combine::new(handler.matches()).invoke();
+    }
+
     #[cfg(feature = "clipboard")]
     if handler.copy().is_some() {
         return action::copy::Copy::new(handler.matches()).invoke();
@@ -77,6 +89,14 @@ fn invoke_action(handler: &Handler) -> Result<()> {
         return action::edit::Edit::new(handler.matches()).invoke();
     }
 
+    if handler.expiring().is_some() {
+        return action::expiring::Expiring::new(handler.matches()).invoke();
+    }
+
+    if handler.extract().is_some() {
+        return action::extract::Extract::new(handler.matches()).invoke();
+    }
+
     if handler.generate().is_some() {
         return action::generate::Generate::new(handler.matches()).invoke();
     }
@@ -121,14 +141,44 @@ fn invoke_action(handler: &Handler) -> Result<()> {
         return action::remove::Remove::new(handler.matches()).invoke();
     }
 
+    if handler.rotate().is_some() {
+        return action::rotate::Rotate::new(handler.matches()).invoke();
+    }
+
+    if handler.set().is_some() {
+        return action::set::Set::new(handler.matches()).invoke();
+    }
+
+    if handler.share().is_some() {
+        return action::share::Share::new(handler.matches()).invoke();
+    }
+
     if handler.show().is_some() {
         return action::show::Show::new(handler.matches()).invoke();
     }
 
+    if handler.split().is_some() {
+        return action::split::Split::new(handler.matches()).invoke();
+    }
+
+    #[cfg(all(feature = "ssh", unix))]
+    if handler.ssh_add().is_some() {
+        return action::ssh_add::SshAdd::new(handler.matches()).invoke();
+    }
+
+    #[cfg(all(feature = "ssh", unix))]
+    if handler.ssh_agent().is_some() {
+        return action::ssh_agent::SshAgent::new(handler.matches()).invoke();
+    }
+
     if handler.sync().is_some() {
         return action::sync::Sync::new(handler.matches()).invoke();
     }
 
+    if handler.tag().is_some() {
+        return action::tag::Tag::new(handler.matches()).invoke();
+    }
+
     #[cfg(all(feature = "tomb", target_os = "linux"))]
     if handler.tomb().is_some() {
         return action::tomb::Tomb::new(handler.matches()).invoke();
@@ -139,6 +189,10 @@ fn invoke_action(handler: &Handler) -> Result<()> {
         return action::totp::Totp::new(handler.matches()).invoke();
     }
 
+    if handler.unset().is_some() {
+        return action::unset::Unset::new(handler.matches()).invoke();
+    }
+
     // Get the main matcher
     let matcher_main = MainMatcher::with(handler.matches()).unwrap();
     if !matcher_main.quiet() {
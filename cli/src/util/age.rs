@@ -0,0 +1,65 @@
+//! Encrypt for age recipients through the `age` binary.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use anyhow::Result;
+use prs_lib::Plaintext;
+use thiserror::Error;
+
+/// Binary name.
+#[cfg(not(windows))]
+const BIN_NAME: &str = "age";
+#[cfg(windows)]
+const BIN_NAME: &str = "age.exe";
+
+/// Prefix of age X25519 recipients.
+const RECIPIENT_PREFIX: &str = "age1";
+
+/// Check whether the given string looks like an age recipient.
+pub fn is_recipient(recipient: &str) -> bool {
+    recipient.starts_with(RECIPIENT_PREFIX)
+        && recipient.len() > RECIPIENT_PREFIX.len()
+        && recipient
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+}
+
+/// Encrypt plaintext for the given age recipient.
+///
+/// Returns the ASCII armored ciphertext.
+pub fn encrypt(recipient: &str, plaintext: &Plaintext) -> Result<Vec<u8>> {
+    let mut child = Command::new(BIN_NAME)
+        .args(["--encrypt", "--armor", "--recipient", recipient])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(Err::Invoke)?;
+
+    // Pass stdin to child process, from separate thread to not block on large output
+    let mut stdin = child.stdin.take().unwrap();
+    let output = std::thread::scope(|s| {
+        let writer = s.spawn(move || stdin.write_all(plaintext.unsecure_ref()));
+        let output = child.wait_with_output().map_err(Err::Invoke)?;
+        writer
+            .join()
+            .expect("age stdin writer panicked")
+            .map_err(Err::Invoke)?;
+        Ok::<_, Err>(output)
+    })?;
+
+    if !output.status.success() {
+        return Err(Err::Encrypt(String::from_utf8_lossy(&output.stderr).trim().into()).into());
+    }
+    Ok(output.stdout)
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to invoke '{BIN_NAME}', is it installed?")]
+    Invoke(#[source] std::io::Error),
+
+    #[error("failed to encrypt for age recipient: {}", _0)]
+    Encrypt(String),
+}
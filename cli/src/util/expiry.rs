@@ -0,0 +1,214 @@
+//! Secret expiry and rotation reminders.
+//!
+//! A secret expires at the date in its `expires` property, or after the duration in its
+//! `rotate-after` property since it was last changed. The last change is the time of the last
+//! git commit of the secret, or its file modification time if not committed.
+
+use std::time::SystemTime;
+
+use anyhow::Result;
+use chrono::{DateTime, Local, NaiveDate, TimeDelta, TimeZone};
+use prs_lib::{Plaintext, Secret, Store};
+use thiserror::Error;
+
+use crate::cmd::matcher::MainMatcher;
+use crate::util::{error, style, time};
+
+/// Property with the date a secret expires at.
+pub const PROPERTY_EXPIRES: &str = "expires";
+
+/// Property with the duration after which a secret must be rotated.
+pub const PROPERTY_ROTATE_AFTER: &str = "rotate-after";
+
+/// Format to show expiry dates in.
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// Check whether the given plaintext has any expiry property.
+pub fn has_expiry(plaintext: &Plaintext) -> bool {
+    plaintext.property(PROPERTY_EXPIRES).is_ok()
+        || plaintext.property(PROPERTY_ROTATE_AFTER).is_ok()
+}
+
+/// Determine when the given secret expires.
+///
+/// `changed` is only called when the secret has a `rotate-after` property. If both properties
+/// are set, the earliest time is used. Returns `None` if the secret has no expiry properties, or
+/// if it expires too far in the future to represent.
+pub fn expiry<F>(plaintext: &Plaintext, changed: F) -> Result<Option<DateTime<Local>>>
+where
+    F: FnOnce() -> Option<SystemTime>,
+{
+    let expires = property(plaintext, PROPERTY_EXPIRES)
+        .map(|value| parse_date(&value))
+        .transpose()?;
+
+    let rotate = match property(plaintext, PROPERTY_ROTATE_AFTER) {
+        Some(value) => {
+            let secs = time::parse_duration(&value).map_err(|err| Err::Duration(value, err))?;
+            changed().and_then(|changed| add_duration(DateTime::from(changed), secs))
+        }
+        None => None,
+    };
+
+    Ok(match (expires, rotate) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    })
+}
+
+/// Renew the `expires` property after rotating a secret.
+///
+/// Only if the secret has both an `expires` and a `rotate-after` property, the `expires` date is
+/// moved forward to now plus the rotation duration. Returns the new expiry date if changed.
+///
+/// If the new expiry date is too far in the future to represent, the secret never expires and the
+/// `expires` property is removed.
+pub fn renew(plaintext: &mut Plaintext) -> Result<Option<DateTime<Local>>> {
+    let rotate_after = match (
+        property(plaintext, PROPERTY_EXPIRES),
+        property(plaintext, PROPERTY_ROTATE_AFTER),
+    ) {
+        (Some(_), Some(rotate_after)) => rotate_after,
+        _ => return Ok(None),
+    };
+
+    let secs =
+        time::parse_duration(&rotate_after).map_err(|err| Err::Duration(rotate_after, err))?;
+    let Some(expires) = add_duration(Local::now(), secs) else {
+        plaintext.remove_property(PROPERTY_EXPIRES)?;
+        return Ok(None);
+    };
+    plaintext.set_property(
+        PROPERTY_EXPIRES,
+        expires.format(DATE_FORMAT).to_string().into(),
+    )?;
+    Ok(Some(expires))
+}
+
+/// Add a duration in seconds to the given time.
+///
+/// Returns `None` if the result is out of range, such a time should be treated as never.
+pub fn add_duration(time: DateTime<Local>, secs: usize) -> Option<DateTime<Local>> {
+    i64::try_from(secs)
+        .ok()
+        .and_then(TimeDelta::try_seconds)
+        .and_then(|delta| time.checked_add_signed(delta))
+}
+
+/// Determine when the given secret was last changed.
+///
+/// Uses the time of the last git commit changing the secret, falls back to the file modification
+/// time.
+pub fn changed_time(store: &Store, secret: &Secret) -> Option<SystemTime> {
+    store
+        .sync()
+        .last_commit_time(&secret.path)
+        .ok()
+        .flatten()
+        .or_else(|| secret.path.metadata().and_then(|m| m.modified()).ok())
+}
+
+/// Print a warning if the given secret is expired.
+///
+/// Does nothing when quiet. Invalid expiry properties are reported as warning as well.
+pub fn warn_expired(
+    store: &Store,
+    secret: &Secret,
+    plaintext: &Plaintext,
+    matcher_main: &MainMatcher,
+) {
+    if matcher_main.quiet() || !has_expiry(plaintext) {
+        return;
+    }
+
+    match expiry(plaintext, || changed_time(store, secret)) {
+        Ok(Some(expires)) if expires <= Local::now() => {
+            error::print_warning(format!(
+                "secret expired on {}, use '{}' to rotate it",
+                expires.format(DATE_FORMAT),
+                style::highlight(format!(
+                    "{} rotate {}",
+                    crate::util::bin_name(),
+                    secret.name
+                )),
+            ));
+        }
+        Ok(_) => {}
+        Err(err) => error::print_warning(format!("invalid secret expiry: {err}")),
+    }
+}
+
+/// Parse an expiry date.
+///
+/// Supports dates such as `2024-12-31`, which expire at the start of that day in local time, and
+/// RFC 3339 date and time strings such as `2024-12-31T12:00:00Z`.
+pub fn parse_date(value: &str) -> Result<DateTime<Local>> {
+    let value = value.trim();
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Local));
+    }
+
+    NaiveDate::parse_from_str(value, DATE_FORMAT)
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .and_then(|time| Local.from_local_datetime(&time).earliest())
+        .ok_or_else(|| Err::Date(value.into()).into())
+}
+
+/// Get a trimmed property value from the given plaintext.
+fn property(plaintext: &Plaintext, property: &str) -> Option<String> {
+    plaintext
+        .property(property)
+        .ok()
+        .and_then(|p| p.unsecure_to_str().ok().map(|p| p.trim().to_string()))
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("invalid expiry date '{}', use a date such as 2024-12-31", _0)]
+    Date(String),
+
+    #[error("invalid rotation duration '{}', use a duration such as 90d", _0)]
+    Duration(String, #[source] time::ParseDurationError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_duration_overflow() {
+        let now = Local::now();
+        assert_eq!(add_duration(now, 60), Some(now + TimeDelta::seconds(60)));
+        assert_eq!(add_duration(now, usize::MAX), None);
+        assert_eq!(add_duration(now, i64::MAX as usize), None);
+        assert_eq!(add_duration(now, 300_000 * 366 * 24 * 60 * 60), None);
+    }
+
+    #[test]
+    fn expiry_overflow() {
+        let plaintext = Plaintext::from("pass\nrotate-after: 99999999999999999w");
+        assert_eq!(
+            expiry(&plaintext, || Some(SystemTime::now())).unwrap(),
+            None
+        );
+
+        let plaintext =
+            Plaintext::from("pass\nexpires: 2024-12-31\nrotate-after: 99999999999999999w");
+        let expires = expiry(&plaintext, || Some(SystemTime::now())).unwrap();
+        assert_eq!(expires, Some(parse_date("2024-12-31").unwrap()));
+    }
+
+    #[test]
+    fn renew_overflow() {
+        let mut plaintext =
+            Plaintext::from("pass\nexpires: 2024-12-31\nrotate-after: 99999999999999999w");
+        assert_eq!(renew(&mut plaintext).unwrap(), None);
+        assert!(plaintext.property(PROPERTY_EXPIRES).is_err());
+        assert_eq!(expiry(&plaintext, || None).unwrap(), None);
+
+        let mut plaintext = Plaintext::from("pass\nexpires: 2024-12-31\nrotate-after: 1w");
+        let expires = renew(&mut plaintext).unwrap().unwrap();
+        assert!(expires > Local::now() + TimeDelta::days(6));
+    }
+}
@@ -1,5 +1,8 @@
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 use std::fs;
+use std::fs::{File, OpenOptions};
+#[cfg(unix)]
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
 use std::path::Path;
 
 use anyhow::Result;
@@ -31,6 +34,26 @@ pub fn ensure_dir_free(path: &Path) -> Result<(), std::io::Error> {
     )
 }
 
+/// Create a file only readable by the current user.
+///
+/// Fails if the file already exists, unless `overwrite` is set. An overwritten file is truncated
+/// and its permissions are restricted as well.
+pub fn create_private(path: &Path, overwrite: bool) -> Result<File, std::io::Error> {
+    let mut options = OpenOptions::new();
+    options.write(true);
+    if overwrite {
+        options.create(true).truncate(true);
+    } else {
+        options.create_new(true);
+    }
+    #[cfg(unix)]
+    options.mode(0o600);
+    let file = options.open(path)?;
+    #[cfg(unix)]
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    Ok(file)
+}
+
 /// Check whether the system has SWAP enabled.
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 pub fn has_swap() -> Result<bool, Err> {
@@ -0,0 +1,38 @@
+//! Fetch public keys from a keyserver.
+
+use anyhow::Result;
+use prs_lib::crypto::util::{format_fingerprint, is_armored_public_key};
+use thiserror::Error;
+
+/// Default keyserver to fetch keys from.
+pub const DEFAULT_KEYSERVER: &str = "https://keys.openpgp.org";
+
+/// Fetch the public key with the given fingerprint from a keyserver.
+///
+/// Uses the HKP protocol, supported by most keyservers. Returns the ASCII armored key.
+pub fn fetch(keyserver: &str, fingerprint: &str) -> Result<Vec<u8>> {
+    let url = format!(
+        "{}/pks/lookup?op=get&options=mr&search=0x{}",
+        keyserver.trim_end_matches('/'),
+        format_fingerprint(fingerprint).replace(' ', ""),
+    );
+
+    let key = ureq::get(&url)
+        .call()
+        .and_then(|mut response| response.body_mut().read_to_vec())
+        .map_err(|err| Err::Fetch(fingerprint.into(), err))?;
+
+    if !is_armored_public_key(&key) {
+        return Err(Err::NoKey(fingerprint.into()).into());
+    }
+    Ok(key)
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to fetch key '{}' from keyserver", _0)]
+    Fetch(String, #[source] ureq::Error),
+
+    #[error("keyserver did not return a public key for '{}'", _0)]
+    NoKey(String),
+}
@@ -1,4 +1,5 @@
-#[cfg(feature = "clipboard")]
+pub mod age;
+#[cfg(any(feature = "clipboard", feature = "totp"))]
 pub mod base64;
 pub mod cli;
 #[cfg(feature = "clipboard")]
@@ -6,9 +7,14 @@ pub mod clipboard;
 pub mod cmd;
 pub mod edit;
 pub mod error;
+pub mod expiry;
 pub mod fs;
+#[cfg(feature = "keyserver")]
+pub mod keyserver;
+pub mod output;
 pub mod pass;
 pub mod progress;
+pub mod search;
 pub mod secret;
 pub mod select;
 pub mod select_basic;
@@ -18,10 +24,11 @@ pub mod select_fzf_bin;
 pub mod select_skim;
 #[cfg(feature = "select-skim-bin")]
 pub mod select_skim_bin;
+#[cfg(all(feature = "ssh", unix))]
+pub mod ssh;
 pub mod stdin;
 pub mod style;
 pub mod sync;
-#[cfg(all(feature = "tomb", target_os = "linux"))]
 pub mod time;
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 pub mod tomb;
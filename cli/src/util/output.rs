@@ -0,0 +1,262 @@
+//! Structured output for read commands.
+//!
+//! The structures defined here form the stable JSON schema emitted with `--output json`. Only add
+//! fields, never rename or remove them.
+
+use std::io::{self, Write};
+
+use chrono::{DateTime, Local};
+use prs_lib::{
+    Key, Secret, Store,
+    crypto::Proto,
+    sync::{Readyness, Sync},
+};
+use serde::Serialize;
+
+use crate::action::recipients;
+
+/// Print the given value as JSON on stdout.
+pub fn print_json<T: Serialize + ?Sized>(value: &T) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    serde_json::to_writer_pretty(&mut stdout, value)?;
+    writeln!(stdout)
+}
+
+/// A secret.
+#[derive(Debug, Serialize)]
+pub struct SecretOutput {
+    /// Secret name.
+    pub name: String,
+
+    /// Absolute path to the secret file.
+    pub path: String,
+
+    /// Name of the secret this alias points to, `null` if not an alias.
+    pub alias_target: Option<String>,
+}
+
+impl SecretOutput {
+    /// Build output for the given secret.
+    pub fn new(store: &Store, secret: &Secret) -> Self {
+        Self {
+            name: secret.name.clone(),
+            path: secret.path.display().to_string(),
+            alias_target: secret.alias_target(store).ok().map(|target| target.name),
+        }
+    }
+}
+
+/// A secret with its plaintext content.
+#[derive(Debug, Serialize)]
+pub struct SecretContentOutput<'a> {
+    #[serde(flatten)]
+    pub secret: SecretOutput,
+
+    /// Secret content, or just the selected line or property. `None` if binary.
+    pub content: Option<&'a str>,
+
+    /// Whether the secret is binary, such as an attached file.
+    pub binary: bool,
+
+    /// Content size in bytes.
+    pub size: usize,
+}
+
+/// A secret that is expired or due for rotation.
+#[derive(Debug, Serialize)]
+pub struct ExpiringOutput {
+    #[serde(flatten)]
+    pub secret: SecretOutput,
+
+    /// Time the secret expires at, in RFC 3339 format.
+    pub expires: String,
+
+    /// Whether the secret is already expired.
+    pub expired: bool,
+}
+
+/// A secret with recipients differing from the store recipients.
+#[derive(Debug, Serialize)]
+pub struct AuditOutput {
+    #[serde(flatten)]
+    pub secret: SecretOutput,
+
+    /// Store recipients the secret is not encrypted for.
+    pub missing: Vec<KeyOutput>,
+
+    /// Key IDs the secret is encrypted for, that are not of a store recipient.
+    pub unexpected: Vec<String>,
+}
+
+/// A secret tag.
+#[derive(Debug, Serialize)]
+pub struct TagOutput {
+    /// Tag name.
+    pub tag: String,
+
+    /// Names of secrets having this tag.
+    pub secrets: Vec<String>,
+}
+
+/// A recipient key.
+#[derive(Debug, Serialize)]
+pub struct KeyOutput {
+    /// Key protocol, such as `gpg`.
+    pub protocol: String,
+
+    /// Full key fingerprint.
+    pub fingerprint: String,
+
+    /// Key user IDs.
+    pub user_ids: Vec<String>,
+
+    /// Key status: `usable`, `expiring`, `expired`, `revoked`, `no-encrypt` or `missing` if the
+    /// key is not in the keychain.
+    pub status: String,
+
+    /// Time the key was created at in RFC 3339 format, `null` if unknown.
+    pub created: Option<String>,
+
+    /// Time the key expires at in RFC 3339 format, `null` if it does not expire or if unknown.
+    pub expires: Option<String>,
+
+    /// Whether the key is revoked.
+    pub revoked: bool,
+
+    /// Usable key capabilities as GnuPG style letters, such as `ESC`, `null` if unknown.
+    pub capabilities: Option<String>,
+
+    /// Public key algorithm, such as `ed25519`, `null` if unknown.
+    pub algorithm: Option<String>,
+}
+
+impl KeyOutput {
+    /// Build output for a key that is missing in the keychain.
+    pub fn missing(proto: Proto, fingerprint: String) -> Self {
+        Self {
+            protocol: proto.name().to_lowercase(),
+            fingerprint,
+            user_ids: vec![],
+            status: "missing".into(),
+            created: None,
+            expires: None,
+            revoked: false,
+            capabilities: None,
+            algorithm: None,
+        }
+    }
+}
+
+impl From<&Key> for KeyOutput {
+    fn from(key: &Key) -> Self {
+        let meta = key.meta();
+        let rfc3339 = |time| DateTime::<Local>::from(time).to_rfc3339();
+        Self {
+            protocol: key.proto().name().to_lowercase(),
+            fingerprint: key.fingerprint(false),
+            user_ids: key.user_ids().to_vec(),
+            status: recipients::key_status(key).into(),
+            created: meta.created.map(rfc3339),
+            expires: meta.expires.map(rfc3339),
+            revoked: meta.revoked,
+            capabilities: meta.capabilities.map(|caps| caps.to_string()),
+            algorithm: meta.algorithm.clone(),
+        }
+    }
+}
+
+/// A recipient group.
+#[derive(Debug, Serialize)]
+pub struct GroupOutput {
+    /// Group name, without `@` prefix.
+    pub name: String,
+
+    /// Group members, fingerprints only if a key is not in the keychain.
+    pub members: Vec<KeyOutput>,
+
+    /// GPG IDs files referencing this group, relative to the store root.
+    pub used_by: Vec<String>,
+}
+
+/// Password store sync status.
+#[derive(Debug, Serialize)]
+pub struct SyncStatusOutput {
+    /// Sync readyness: `no-sync`, `ready`, `dirty` or `repo-state`.
+    pub readyness: &'static str,
+
+    /// Special git repository state, only set if readyness is `repo-state`.
+    pub repo_state: Option<String>,
+
+    /// Whether the repository has uncommitted changes.
+    pub dirty: bool,
+
+    /// Configured sync remotes.
+    pub remotes: Vec<RemoteOutput>,
+}
+
+impl SyncStatusOutput {
+    /// Build sync status output.
+    pub fn new(sync: &Sync, readyness: &Readyness) -> anyhow::Result<Self> {
+        let (readyness_name, repo_state) = match readyness {
+            Readyness::NoSync => ("no-sync", None),
+            Readyness::Ready => ("ready", None),
+            Readyness::Dirty => ("dirty", None),
+            Readyness::RepoState(state) => ("repo-state", Some(format!("{state:?}"))),
+        };
+
+        let remotes = if *readyness != Readyness::NoSync {
+            sync.remotes()?
+                .into_iter()
+                .map(|name| RemoteOutput {
+                    url: sync.remote_url(&name).ok(),
+                    name,
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+
+        Ok(Self {
+            readyness: readyness_name,
+            repo_state,
+            dirty: *readyness == Readyness::Dirty,
+            remotes,
+        })
+    }
+}
+
+/// A git sync remote.
+#[derive(Debug, Serialize)]
+pub struct RemoteOutput {
+    /// Remote name.
+    pub name: String,
+
+    /// Remote URL.
+    pub url: Option<String>,
+}
+
+/// Password store Tomb status.
+#[cfg(all(feature = "tomb", target_os = "linux"))]
+#[derive(Debug, Default, Serialize)]
+pub struct TombStatusOutput {
+    /// Whether the password store is a Tomb.
+    pub tomb: bool,
+
+    /// Whether the Tomb is open.
+    pub open: Option<bool>,
+
+    /// Whether an automatic close timer is active.
+    pub close_timer: Option<bool>,
+
+    /// Path to the Tomb file.
+    pub tomb_path: Option<String>,
+
+    /// Path to the Tomb key file.
+    pub tomb_key_path: Option<String>,
+
+    /// Password store size in bytes.
+    pub store_size: Option<u64>,
+
+    /// Tomb file size in bytes.
+    pub tomb_file_size: Option<u64>,
+}
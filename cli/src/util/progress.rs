@@ -44,21 +44,10 @@ fn trunc_msg(msg: &str, limit: usize) -> String {
 pub(crate) trait ProgressBarExt {
     /// Set the progress bar message and truncate it.
     fn set_message_trunc(&self, msg: &str);
-
-    /// Always print a newline, even if hidden.
-    fn println_always<I: AsRef<str>>(&self, msg: I);
 }
 
 impl ProgressBarExt for ProgressBar {
     fn set_message_trunc(&self, msg: &str) {
         self.set_message(trunc_msg(msg, MSG_LEN));
     }
-
-    fn println_always<I: AsRef<str>>(&self, msg: I) {
-        if self.is_hidden() {
-            println!("{}", msg.as_ref())
-        } else {
-            self.println(msg)
-        }
-    }
 }
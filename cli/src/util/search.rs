@@ -0,0 +1,48 @@
+//! Search index helpers.
+
+use anyhow::Result;
+use prs_lib::{Store, search::SearchIndex};
+
+use crate::cmd::matcher::MainMatcher;
+use crate::util::{self, error, style};
+
+/// Update the search index of the given store, if it has one.
+///
+/// The closure changes the loaded index, and returns whether it changed. The index is saved if
+/// changed. Errors are reported but never fail, the index can always be rebuilt.
+pub fn update<F>(store: &Store, matcher_main: &MainMatcher, f: F)
+where
+    F: FnOnce(&mut SearchIndex) -> Result<bool>,
+{
+    if !SearchIndex::exists(store) {
+        return;
+    }
+
+    if let Err(err) = try_update(store, matcher_main, f) {
+        error::print_error(err.context("failed to update search index, ignoring"));
+        print_rebuild_hint(matcher_main);
+    }
+}
+
+/// Load, change and save the search index of the given store.
+fn try_update<F>(store: &Store, matcher_main: &MainMatcher, f: F) -> Result<()>
+where
+    F: FnOnce(&mut SearchIndex) -> Result<bool>,
+{
+    let mut context = crate::crypto::context(matcher_main)?;
+    let mut index = SearchIndex::load(store, &mut context)?;
+    if f(&mut index)? {
+        index.save(store, &mut context)?;
+    }
+    Ok(())
+}
+
+/// Print a hint on how to rebuild the search index.
+pub fn print_rebuild_hint(matcher_main: &MainMatcher) {
+    if !matcher_main.quiet() {
+        eprintln!(
+            "Use '{}' to rebuild the search index",
+            style::highlight(format!("{} housekeeping index", util::bin_name()))
+        );
+    }
+}
@@ -7,18 +7,49 @@ use crate::{
 
 /// Find and select a secret in the given store.
 ///
-/// If no exact secret is found, the user will be able to choose.
+/// If no exact secret is found, the user will be able to choose. In no-interact mode this errors
+/// if the query matches many secrets.
 ///
 /// `None` is returned if no secret was found or selected.
 pub fn store_select_secret(
     store: &Store,
     query: Option<String>,
     matcher_main: &MainMatcher,
+) -> Option<Secret> {
+    select_secret(store, query, matcher_main, false)
+}
+
+/// Find and select a secret in the given store to read.
+///
+/// Like [`store_select_secret`], but in no-interact mode the best fuzzy match is selected, unless
+/// exact matching is enabled. Only use this for commands that don't change the secret.
+pub fn store_select_secret_read(
+    store: &Store,
+    query: Option<String>,
+    matcher_main: &MainMatcher,
+) -> Option<Secret> {
+    select_secret(store, query, matcher_main, true)
+}
+
+/// Find and select a secret in the given store.
+///
+/// If `best` is set, the best fuzzy match is selected in no-interact mode.
+fn select_secret(
+    store: &Store,
+    query: Option<String>,
+    matcher_main: &MainMatcher,
+    best: bool,
 ) -> Option<Secret> {
     let has_query = query.as_ref().is_some_and(|q| !q.trim().is_empty());
 
+    let found = if matcher_main.exact() {
+        store.find_exact(query)
+    } else {
+        store.find(query)
+    };
+
     #[allow(unreachable_code)]
-    match store.find(query) {
+    match found {
         FindSecret::Exact(secret) => Some(secret),
         FindSecret::Many(mut secrets) => {
             // Do not show selection dialog if no secret is selected
@@ -31,6 +62,19 @@ pub fn store_select_secret(
                 return secrets.pop();
             }
 
+            // Without interaction, select best match which is ranked first
+            if best && matcher_main.no_interact() && has_query && !matcher_main.exact() {
+                let secret = secrets.remove(0);
+                if !matcher_main.quiet() {
+                    eprintln!(
+                        "Selected best of {} matches: {}",
+                        secrets.len() + 1,
+                        secret.name
+                    );
+                }
+                return Some(secret);
+            }
+
             // Cannot choose out of many without interaction, error instead
             if matcher_main.no_interact() {
                 quit_error_msg(
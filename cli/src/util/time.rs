@@ -18,6 +18,8 @@ use thiserror::Error;
 /// - `23h14m`
 /// - `9m55s`
 /// - `1s1s1s1s1s`
+///
+/// Durations too large to represent saturate at `usize::MAX` seconds.
 pub fn parse_duration(duration: &str) -> Result<usize, ParseDurationError> {
     // Build a regex to grab time parts
     let re = Regex::new(r"(?i)([0-9]+)(([a-z]|\s*$))")
@@ -37,15 +39,16 @@ pub fn parse_duration(duration: &str) -> Result<usize, ParseDurationError> {
             .map_err(ParseDurationError::InvalidValue)?;
         let modifier = capture[2].trim().to_lowercase();
 
-        // Multiply and sum seconds by modifier
-        seconds += match modifier.as_str() {
-            "" | "s" => number,
-            "m" => number * 60,
-            "h" => number * 60 * 60,
-            "d" => number * 60 * 60 * 24,
-            "w" => number * 60 * 60 * 24 * 7,
+        // Multiply and sum seconds by modifier, saturate on overflow
+        let unit = match modifier.as_str() {
+            "" | "s" => 1,
+            "m" => 60,
+            "h" => 60 * 60,
+            "d" => 60 * 60 * 24,
+            "w" => 60 * 60 * 24 * 7,
             m => return Err(ParseDurationError::UnknownIdentifier(m.into())),
         };
+        seconds = number.saturating_mul(unit).saturating_add(seconds);
     }
 
     Ok(seconds)
@@ -2,24 +2,67 @@
 use std::io::{Error as IoError, Write};
 #[cfg(feature = "clipboard")]
 use std::process::{Child, Stdio};
-use std::time::SystemTimeError;
+use std::time::{SystemTime, SystemTimeError, UNIX_EPOCH};
 
 use anyhow::Result;
+use hmac::{Hmac, Mac};
 use linkify::{LinkFinder, LinkKind};
-use prs_lib::Plaintext;
+use md5::Md5;
+use prs_lib::{Plaintext, Secret as StoreSecret, Store, crypto::prelude::*};
+use sha2::{Digest, Sha256};
 use thiserror::Error;
 use totp_rs::{Algorithm, Secret, TOTP};
 
+use crate::cmd::matcher::MainMatcher;
+use crate::util::{base64, search, sync};
+
 /// OTPAUTH URL scheme.
 const OTPAUTH_SCHEME: &str = "otpauth://";
 
+/// OTPAUTH URL prefix for counter based HOTP secrets.
+const HOTP_PREFIX: &str = "otpauth://hotp/";
+
+/// OTPAUTH URL prefix for time based TOTP secrets.
+const TOTP_PREFIX: &str = "otpauth://totp/";
+
 /// Possible property names to search in for TOTP tokens.
 const PROPERTY_NAMES: [&str; 2] = ["totp", "2fa"];
 
+/// Property to configure the number of digits for encoded TOTP secrets.
+const PROPERTY_DIGITS: &str = "totp-digits";
+
+/// Property to configure the algorithm for encoded TOTP secrets.
+const PROPERTY_ALGORITHM: &str = "totp-algorithm";
+
+/// Property to configure the period in seconds for encoded TOTP secrets.
+const PROPERTY_PERIOD: &str = "totp-period";
+
+/// Number of characters in a Steam Guard token.
+const STEAM_DIGITS: usize = 5;
+
+/// Number of characters in a Yandex.Key token.
+const YANDEX_DIGITS: u32 = 8;
+
+/// Number of secret bytes used by Yandex.Key, remaining bytes are a checksum.
+const YANDEX_SECRET_LEN: usize = 16;
+
+/// Number of characters in a Mobile-OTP token.
+const MOTP_DIGITS: usize = 6;
+
+/// Default period in seconds for Mobile-OTP tokens.
+const MOTP_PERIOD: u64 = 10;
+
 /// Try to find a TOTP token in the given plaintext.
 ///
 /// Returns `None` if no TOTP is found.
 pub fn find_token(plaintext: &Plaintext) -> Option<Result<Totp>> {
+    find_token_with(plaintext, plaintext)
+}
+
+/// Try to find a TOTP token in the given plaintext.
+///
+/// Encoded TOTP secrets are configured through properties in `params`.
+fn find_token_with(plaintext: &Plaintext, params: &Plaintext) -> Option<Result<Totp>> {
     // Find first TOTP URL globally
     if let totp @ Some(_) = find_otpauth_url(plaintext) {
         return totp;
@@ -29,13 +72,13 @@ pub fn find_token(plaintext: &Plaintext) -> Option<Result<Totp>> {
     if let totp @ Some(_) = PROPERTY_NAMES
         .iter()
         .flat_map(|p| plaintext.property(p))
-        .find_map(|p| find_token(&p))
+        .find_map(|p| find_token_with(&p, params))
     {
         return totp;
     }
 
     // Try to parse full secret as encoded TOTP secret
-    parse_encoded(plaintext).map(Ok)
+    parse_encoded(plaintext, params)
 }
 
 /// Scan the plaintext for `otpauth` URLs.
@@ -46,7 +89,7 @@ fn find_otpauth_url(plaintext: &Plaintext) -> Option<Result<Totp>> {
     finder.kinds(&[LinkKind::Url]);
 
     finder
-        .links(plaintext.unsecure_to_str().unwrap())
+        .links(plaintext.unsecure_to_str().ok()?)
         .filter(|l| l.as_str().starts_with(OTPAUTH_SCHEME))
         .map(|l| Totp::from_url(l.as_str()))
         .next()
@@ -57,24 +100,100 @@ fn find_otpauth_url(plaintext: &Plaintext) -> Option<Result<Totp>> {
 /// Uses RFC6238 defaults, see:
 /// - https://docs.rs/totp-rs/3.1.0/totp_rs/struct.Rfc6238.html#method.with_defaults
 /// - https://tools.ietf.org/html/rfc6238
-fn parse_encoded(plaintext: &Plaintext) -> Option<Totp> {
-    // Trim plaintext, must be base32 encoded
-    let plaintext = plaintext.unsecure_to_str().unwrap().trim();
-    if !is_base32(plaintext) {
+///
+/// The defaults may be overridden with `totp-digits`, `totp-algorithm` and `totp-period`
+/// properties in `params`. Steam Guard secrets may also be base64 encoded, as exported by most
+/// Steam tools.
+fn parse_encoded(plaintext: &Plaintext, params: &Plaintext) -> Option<Result<Totp>> {
+    let plaintext = plaintext.unsecure_to_str().ok()?.trim();
+    let algorithm = param_property(params, PROPERTY_ALGORITHM);
+    let steam = algorithm
+        .as_deref()
+        .is_some_and(|a| a.eq_ignore_ascii_case("steam"));
+
+    // Must be base32 encoded with at least 16 bytes, Steam may be base64 encoded
+    let bytes = if is_base32(plaintext) && plaintext.len() >= 16 {
+        Secret::Encoded(plaintext.to_string()).to_bytes().ok()?
+    } else if steam && !plaintext.is_empty() && !plaintext.contains(char::is_whitespace) {
+        base64::decode(plaintext).ok()?
+    } else {
         return None;
-    }
+    };
 
-    // Encoded secret must have at least 16 bytes
-    if plaintext.len() < 16 {
-        return None;
-    }
+    // Parse RFC6238 TOTP (with looser requirements)
+    Some(
+        encoded_params(algorithm, params).map(|(algorithm, digits, step)| {
+            TOTP::new_unchecked(algorithm, digits, 1, step, bytes, None, "".into()).into()
+        }),
+    )
+}
 
-    // Decode to bytes
-    let secret = Secret::Encoded(plaintext.to_string());
-    let bytes = secret.to_bytes().unwrap();
+/// Parse the algorithm, digits and period to use for an encoded TOTP secret.
+fn encoded_params(
+    algorithm: Option<String>,
+    params: &Plaintext,
+) -> Result<(Algorithm, usize, u64)> {
+    let algorithm = match algorithm.map(|a| a.to_uppercase()).as_deref() {
+        None | Some("SHA1") => Algorithm::SHA1,
+        Some("SHA256") => Algorithm::SHA256,
+        Some("SHA512") => Algorithm::SHA512,
+        Some("STEAM") => Algorithm::Steam,
+        Some(other) => return Err(Err::Algorithm(other.into()).into()),
+    };
 
-    // Parse RFC6238 TOTP (with looser requirements)
-    Some(TOTP::new_unchecked(Algorithm::SHA1, 6, 1, 30, bytes, None, "".into()).into())
+    let digits = match param_property(params, PROPERTY_DIGITS) {
+        _ if algorithm == Algorithm::Steam => STEAM_DIGITS,
+        Some(digits) => digits
+            .parse()
+            .ok()
+            .filter(|d| (1..=10).contains(d))
+            .ok_or(Err::Digits(digits))?,
+        None => 6,
+    };
+
+    let step = match param_property(params, PROPERTY_PERIOD) {
+        Some(period) => period
+            .parse()
+            .ok()
+            .filter(|p| *p > 0)
+            .ok_or(Err::Period(period))?,
+        None => 30,
+    };
+
+    Ok((algorithm, digits, step))
+}
+
+/// Get a trimmed TOTP parameter property from the given plaintext.
+fn param_property(plaintext: &Plaintext, property: &str) -> Option<String> {
+    plaintext
+        .property(property)
+        .ok()
+        .and_then(|p| p.unsecure_to_str().ok().map(|p| p.trim().to_string()))
+}
+
+/// Decode the otpauth URL from a QR code in the given image.
+///
+/// The image may be in any supported format, such as PNG or JPEG. The first QR code found in the
+/// image must contain a valid TOTP or HOTP URL.
+pub fn decode_qr(image: &[u8]) -> Result<Plaintext> {
+    let image = image::load_from_memory(image)
+        .map_err(Err::Image)?
+        .to_luma8();
+    let mut image = rqrr::PreparedImage::prepare(image);
+
+    // Decode first QR code in image
+    let grid = image.detect_grids().into_iter().next().ok_or(Err::NoQr)?;
+    let (_, url) = grid.decode().map_err(Err::DecodeQr)?;
+    let url = Plaintext::from(url);
+
+    // Must be valid otpauth URL
+    let text = url.unsecure_to_str().map_err(|_| Err::NoOtpauthUrl)?;
+    if !text.starts_with(OTPAUTH_SCHEME) {
+        return Err(Err::NoOtpauthUrl.into());
+    }
+    Totp::from_url(text)?;
+
+    Ok(url)
 }
 
 /// Format a token as a string.
@@ -87,12 +206,17 @@ pub fn format_token(token: &Plaintext, quiet: bool, ttl: Option<u64>) -> Plainte
         return token.clone();
     }
 
-    // Format with spaces
-    let mut formatted = if token.unsecure_ref().len() > 5 {
+    // Format with spaces, in groups of four for 8 character tokens
+    let len = token.unsecure_ref().len();
+    let mut formatted = if len > 5 {
         Plaintext::from(
             token
                 .unsecure_ref()
-                .chunks(3)
+                .chunks(if len.is_multiple_of(4) && !len.is_multiple_of(3) {
+                    4
+                } else {
+                    3
+                })
                 .map(|c| std::str::from_utf8(c).unwrap())
                 .collect::<Vec<_>>()
                 .join(" "),
@@ -118,46 +242,435 @@ pub fn print_token(token: &Plaintext, quiet: bool, ttl: Option<u64>) {
     );
 }
 
+/// Generate a HOTP token and advance the counter stored in the secret.
+///
+/// The secret is synced and decrypted again to get the latest counter. The token is generated
+/// from it, after which the secret is re-encrypted with an incremented counter and committed.
+pub fn generate_hotp(
+    store: &Store,
+    secret: &StoreSecret,
+    property: Option<&String>,
+    matcher_main: &MainMatcher,
+    allow_dirty: bool,
+    no_sync: bool,
+) -> Result<Plaintext> {
+    let sync = store.sync();
+
+    // Prepare sync, to get the latest counter
+    sync::ensure_ready(&sync, allow_dirty);
+    if !no_sync {
+        sync.prepare().map_err(Err::Sync)?;
+    }
+
+    let mut context = crate::crypto::context(matcher_main)?;
+    let plaintext = context.decrypt_file(&secret.path).map_err(Err::Read)?;
+
+    // Find HOTP secret, optionally within property
+    let totp = match property {
+        Some(property) => find_token(&plaintext.property(property).map_err(Err::Property)?),
+        None => find_token(&plaintext),
+    }
+    .ok_or(Err::NoHotp)??;
+    let Variant::Counter(counter) = totp.variant else {
+        return Err(Err::NoHotp.into());
+    };
+
+    // Generate token, write back incremented counter
+    let token = totp.generate_current()?;
+    let plaintext = totp.increment_in(&plaintext).ok_or(Err::NoHotp)?;
+    context
+        .encrypt_file(
+            &store.recipients_for(secret)?,
+            plaintext.clone(),
+            &secret.path,
+        )
+        .map_err(Err::Write)?;
+    search::update(store, matcher_main, |index| {
+        index.update(secret, &plaintext).map(|_| true)
+    });
+
+    if !no_sync {
+        sync.finalize(format!(
+            "Increment HOTP counter to {} for secret {}",
+            counter + 1,
+            secret.name,
+        ))
+        .map_err(Err::Sync)?;
+    }
+
+    Ok(token)
+}
+
 /// A secure TOTP type.
 ///
-/// This TOTP type outputs tokens as secure `Plaintext` and zeroes on drop.
+/// This TOTP type outputs tokens as secure `Plaintext` and zeroes on drop. It also supports
+/// counter based HOTP secrets, Steam Guard, Yandex.Key and Mobile-OTP.
 pub struct Totp {
     totp: TOTP,
+
+    /// The kind of token to generate.
+    variant: Variant,
+
+    /// The URL as found in the secret, for variants that can't be regenerated from `totp`.
+    url: Option<String>,
+}
+
+/// Kind of token to generate.
+enum Variant {
+    /// Standard time based token, including Steam Guard.
+    Time,
+
+    /// Counter based HOTP token, with the counter to generate the next token with.
+    Counter(u64),
+
+    /// Yandex.Key token, with PIN.
+    Yandex(Plaintext),
+
+    /// Mobile-OTP token, with PIN.
+    Motp(Plaintext),
 }
 
 impl Totp {
-    /// Construct a TOTP from the given TOTP URL.
+    /// Construct a TOTP from the given TOTP or HOTP URL.
+    ///
+    /// Yandex.Key and Mobile-OTP secrets are selected with an `algorithm=YANDEX` or
+    /// `algorithm=MOTP` parameter, and require a `pin` parameter.
     pub fn from_url(url: &str) -> Result<Self> {
+        // Parse HOTP as TOTP with a step of one, to use the counter as time
+        if let Some(rest) = url.strip_prefix(HOTP_PREFIX) {
+            let counter = match query_param(url, "counter") {
+                Some(counter) => counter.parse().map_err(|_| Err::Counter(counter.into()))?,
+                None => 0,
+            };
+            let mut totp =
+                TOTP::from_url_unchecked(format!("{TOTP_PREFIX}{rest}")).map_err(Err::Url)?;
+            totp.step = 1;
+            return Ok(Self {
+                totp,
+                variant: Variant::Counter(counter),
+                url: Some(url.into()),
+            });
+        }
+
+        match query_param(url, "algorithm")
+            .map(|a| a.to_uppercase())
+            .as_deref()
+        {
+            Some("YANDEX") => return Self::from_variant_url(url, true),
+            Some("MOTP") => return Self::from_variant_url(url, false),
+            _ => {}
+        }
+
         TOTP::from_url_unchecked(url)
             .map(|t| t.into())
             .map_err(|e| Err::Url(e).into())
     }
 
+    /// Construct a Yandex.Key or Mobile-OTP secret from the given URL.
+    fn from_variant_url(url: &str, yandex: bool) -> Result<Self> {
+        let secret = query_param(url, "secret").ok_or(Err::Secret)?;
+        let pin = Plaintext::from(query_param(url, "pin").ok_or(Err::Pin)?);
+        let period = query_param(url, "period")
+            .map(|p| {
+                p.parse()
+                    .ok()
+                    .filter(|p| *p > 0)
+                    .ok_or(Err::Period(p.into()))
+            })
+            .transpose()?;
+
+        let (bytes, digits, step, variant) = if yandex {
+            // Yandex secrets are base32 encoded, and end with a checksum
+            let mut bytes = Secret::Encoded(secret.to_uppercase())
+                .to_bytes()
+                .map_err(|_| Err::Secret)?;
+            if bytes.len() < YANDEX_SECRET_LEN {
+                return Err(Err::Secret.into());
+            }
+            bytes.truncate(YANDEX_SECRET_LEN);
+            let digits = YANDEX_DIGITS as usize;
+            (bytes, digits, period.unwrap_or(30), Variant::Yandex(pin))
+        } else {
+            // Mobile-OTP secrets are used as hexadecimal string
+            let bytes = secret.to_lowercase().into_bytes();
+            let step = period.unwrap_or(MOTP_PERIOD);
+            (bytes, MOTP_DIGITS, step, Variant::Motp(pin))
+        };
+
+        let totp = TOTP::new_unchecked(Algorithm::SHA1, digits, 1, step, bytes, None, "".into());
+        Ok(Self {
+            totp,
+            variant,
+            url: Some(url.into()),
+        })
+    }
+
+    /// Check whether this is a counter based HOTP secret.
+    pub fn is_hotp(&self) -> bool {
+        matches!(self.variant, Variant::Counter(_))
+    }
+
     /// Generate a token from the current system time.
+    ///
+    /// For HOTP secrets the token is generated from the current counter, which is not advanced.
     pub fn generate_current(&self) -> Result<Plaintext> {
-        self.totp
-            .generate_current()
-            .map(|t| t.into())
-            .map_err(|e| Err::Time(e).into())
+        let counter = || -> Result<u64> {
+            let time = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_err(Err::Time)?;
+            Ok(time.as_secs() / self.totp.step)
+        };
+
+        match &self.variant {
+            Variant::Time => self
+                .totp
+                .generate_current()
+                .map(|t| t.into())
+                .map_err(|e| Err::Time(e).into()),
+            Variant::Counter(counter) => Ok(self.totp.generate(*counter).into()),
+            Variant::Yandex(pin) => Ok(yandex_token(&self.totp.secret, pin, counter()?)),
+            Variant::Motp(pin) => Ok(motp_token(&self.totp.secret, pin, counter()?)),
+        }
     }
 
     /// Generate an URL for this TOTP secret.
     pub fn generate_url(&self) -> Plaintext {
-        self.totp.get_url().into()
+        match &self.url {
+            Some(url) => url.clone().into(),
+            None => self.totp.get_url().into(),
+        }
     }
 
     /// Give the ttl (in seconds) of the current token.
+    ///
+    /// Errors for HOTP secrets, as their tokens don't expire.
     pub fn ttl(&self) -> Result<u64> {
+        if self.is_hotp() {
+            return Err(Err::NoTtl.into());
+        }
+
         self.totp.ttl().map_err(|e| Err::Time(e).into())
     }
+
+    /// Kind of token, as named by most authenticator apps.
+    pub fn kind(&self) -> &'static str {
+        match self.variant {
+            Variant::Time if self.totp.algorithm == Algorithm::Steam => "steam",
+            Variant::Time => "totp",
+            Variant::Counter(_) => "hotp",
+            Variant::Yandex(_) => "yandex",
+            Variant::Motp(_) => "motp",
+        }
+    }
+
+    /// The raw secret bytes.
+    ///
+    /// Returns `None` if a Mobile-OTP secret is not valid hexadecimal.
+    pub fn secret(&self) -> Option<Vec<u8>> {
+        match self.variant {
+            Variant::Motp(_) => {
+                let hex = std::str::from_utf8(&self.totp.secret).ok()?;
+                if hex.len() % 2 != 0 {
+                    return None;
+                }
+                (0..hex.len())
+                    .step_by(2)
+                    .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+                    .collect()
+            }
+            _ => Some(self.totp.secret.clone()),
+        }
+    }
+
+    /// The base32 encoded secret.
+    pub fn secret_base32(&self) -> Option<String> {
+        match Secret::Raw(self.secret()?).to_encoded() {
+            Secret::Encoded(secret) => Some(secret),
+            Secret::Raw(_) => None,
+        }
+    }
+
+    /// Name of the hash algorithm used.
+    pub fn algorithm(&self) -> &'static str {
+        match (&self.variant, self.totp.algorithm) {
+            (Variant::Yandex(_), _) => "SHA256",
+            (Variant::Motp(_), _) => "MD5",
+            (_, Algorithm::SHA256) => "SHA256",
+            (_, Algorithm::SHA512) => "SHA512",
+            (_, Algorithm::SHA1 | Algorithm::Steam) => "SHA1",
+        }
+    }
+
+    /// Number of characters in a token.
+    pub fn digits(&self) -> usize {
+        self.totp.digits
+    }
+
+    /// Period in seconds, `None` for HOTP secrets.
+    pub fn period(&self) -> Option<u64> {
+        (!self.is_hotp()).then_some(self.totp.step)
+    }
+
+    /// Counter to generate the next token with, only for HOTP secrets.
+    pub fn counter(&self) -> Option<u64> {
+        match self.variant {
+            Variant::Counter(counter) => Some(counter),
+            _ => None,
+        }
+    }
+
+    /// PIN, only for Yandex.Key and Mobile-OTP secrets.
+    pub fn pin(&self) -> Option<&Plaintext> {
+        match &self.variant {
+            Variant::Yandex(pin) | Variant::Motp(pin) => Some(pin),
+            _ => None,
+        }
+    }
+
+    /// Issuer of this secret, if known.
+    pub fn issuer(&self) -> Option<&str> {
+        self.totp.issuer.as_deref().filter(|i| !i.is_empty())
+    }
+
+    /// Generate an URL for this TOTP secret with the given label.
+    pub fn generate_url_with_label(&self, label: &str) -> Plaintext {
+        let Some(url) = &self.url else {
+            let mut totp = self.totp.clone();
+            totp.account_name = label.into();
+            return totp.get_url().into();
+        };
+
+        // Replace label in URL path
+        let Some((host, rest)) = url
+            .strip_prefix(OTPAUTH_SCHEME)
+            .and_then(|u| u.split_once('/'))
+        else {
+            return url.clone().into();
+        };
+        let query = rest.split_once('?').map(|(_, q)| q).unwrap_or("");
+        format!("{OTPAUTH_SCHEME}{host}/{}?{query}", url_encode(label)).into()
+    }
+
+    /// Increment the HOTP counter in the given plaintext.
+    ///
+    /// Returns the updated plaintext, or `None` if this is not a HOTP secret or if its URL is
+    /// not in the plaintext.
+    fn increment_in(&self, plaintext: &Plaintext) -> Option<Plaintext> {
+        let (Variant::Counter(counter), Some(url)) = (&self.variant, &self.url) else {
+            return None;
+        };
+        let text = plaintext.unsecure_to_str().ok()?;
+        if !text.contains(url.as_str()) {
+            return None;
+        }
+
+        let next = set_query_param(url, "counter", &(counter + 1).to_string());
+        Some(text.replacen(url.as_str(), &next, 1).into())
+    }
 }
 
 impl From<TOTP> for Totp {
     fn from(totp: TOTP) -> Self {
-        Self { totp }
+        Self {
+            totp,
+            variant: Variant::Time,
+            url: None,
+        }
     }
 }
 
+/// Generate a Yandex.Key token.
+///
+/// The key is derived from the PIN and secret, tokens consist of lowercase letters.
+fn yandex_token(secret: &[u8], pin: &Plaintext, counter: u64) -> Plaintext {
+    let mut key = Sha256::new()
+        .chain_update(pin.unsecure_ref())
+        .chain_update(secret)
+        .finalize()
+        .to_vec();
+    if key[0] == 0 {
+        key.remove(0);
+    }
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(&key).expect("HMAC accepts any key length");
+    mac.update(&counter.to_be_bytes());
+    let hash = mac.finalize().into_bytes();
+
+    // Dynamic truncation to 63 bits, encoded in base 26
+    let offset = (hash[hash.len() - 1] & 0xf) as usize;
+    let mut code =
+        u64::from_be_bytes(hash[offset..offset + 8].try_into().unwrap()) & 0x7fff_ffff_ffff_ffff;
+    code %= 26u64.pow(YANDEX_DIGITS);
+    let mut token = vec![0; YANDEX_DIGITS as usize];
+    for c in token.iter_mut().rev() {
+        *c = b'a' + (code % 26) as u8;
+        code /= 26;
+    }
+
+    token.into()
+}
+
+/// Generate a Mobile-OTP token.
+///
+/// Tokens are the first hexadecimal characters of the MD5 hash over the counter, secret and PIN.
+fn motp_token(secret: &[u8], pin: &Plaintext, counter: u64) -> Plaintext {
+    let hash = Md5::new()
+        .chain_update(counter.to_string())
+        .chain_update(secret)
+        .chain_update(pin.unsecure_ref())
+        .finalize();
+    let mut token = format!("{hash:x}");
+    token.truncate(MOTP_DIGITS);
+    token.into()
+}
+
+/// Percent encode the given string for use in an URL.
+pub fn url_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            b => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+/// Get the value of a query parameter in the given URL.
+fn query_param<'a>(url: &'a str, key: &str) -> Option<&'a str> {
+    url.split_once('?')?
+        .1
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| v)
+}
+
+/// Set the value of a query parameter in the given URL, appending it if not yet present.
+fn set_query_param(url: &str, key: &str, value: &str) -> String {
+    let Some((base, query)) = url.split_once('?') else {
+        return format!("{url}?{key}={value}");
+    };
+
+    let mut found = false;
+    let mut pairs: Vec<String> = query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((k, _)) if k == key && !found => {
+                found = true;
+                format!("{key}={value}")
+            }
+            _ => pair.into(),
+        })
+        .collect();
+    if !found {
+        pairs.push(format!("{key}={value}"));
+    }
+
+    format!("{base}?{}", pairs.join("&"))
+}
+
 /// Check if string is base32 compliant
 ///
 /// RFC: https://www.rfc-editor.org/rfc/rfc4648#page-9
@@ -215,4 +728,152 @@ pub enum Err {
 
     #[error("TOTP system time error")]
     Time(#[source] SystemTimeError),
+
+    #[error("failed to load QR code image")]
+    Image(#[source] image::ImageError),
+
+    #[error("no QR code found in image")]
+    NoQr,
+
+    #[error("failed to decode QR code")]
+    DecodeQr(#[source] rqrr::DeQRError),
+
+    #[error("QR code does not contain an otpauth URL")]
+    NoOtpauthUrl,
+
+    #[error("invalid TOTP algorithm: {}", _0)]
+    Algorithm(String),
+
+    #[error("invalid number of TOTP digits: {}", _0)]
+    Digits(String),
+
+    #[error("invalid TOTP period: {}", _0)]
+    Period(String),
+
+    #[error("missing or invalid TOTP secret in URL")]
+    Secret,
+
+    #[error("missing PIN in TOTP URL")]
+    Pin,
+
+    #[error("invalid HOTP counter: {}", _0)]
+    Counter(String),
+
+    #[error("HOTP tokens do not expire")]
+    NoTtl,
+
+    #[error("no HOTP secret found")]
+    NoHotp,
+
+    #[error("failed to sync password store")]
+    Sync(#[source] anyhow::Error),
+
+    #[error("failed to read secret")]
+    Read(#[source] anyhow::Error),
+
+    #[error("failed to select property from secret")]
+    Property(#[source] anyhow::Error),
+
+    #[error("failed to write secret with incremented HOTP counter")]
+    Write(#[source] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// RFC 6238 test secret for SHA1, base32 encoded.
+    const RFC_SECRET_SHA1: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    /// Generate a token at the given time for a secret with a standard or Steam variant.
+    fn token_at(plaintext: &str, time: u64) -> String {
+        let totp = find_token(&Plaintext::from(plaintext)).unwrap().unwrap();
+        assert!(matches!(totp.variant, Variant::Time));
+        totp.totp.generate(time)
+    }
+
+    #[test]
+    fn rfc6238() {
+        // Test vectors from RFC 6238, appendix B
+        let sha256 = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZA";
+        let sha512 = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNA";
+        #[rustfmt::skip]
+        let set = [
+            (RFC_SECRET_SHA1, "sha1", 59, "94287082"),
+            (RFC_SECRET_SHA1, "sha1", 1111111109, "07081804"),
+            (sha256, "sha256", 59, "46119246"),
+            (sha256, "sha256", 1111111109, "68084774"),
+            (sha512, "sha512", 59, "90693936"),
+            (sha512, "sha512", 1111111109, "25091201"),
+        ];
+
+        for (secret, algorithm, time, token) in set {
+            let plaintext =
+                format!("pass\ntotp: {secret}\ntotp-algorithm: {algorithm}\ntotp-digits: 8");
+            assert_eq!(token_at(&plaintext, time), token, "{algorithm} at {time}");
+        }
+    }
+
+    #[test]
+    fn steam() {
+        // Steam Guard tokens for the RFC 6238 SHA1 secret, base32 and base64 encoded. Expected
+        // tokens are computed with an independent implementation of the algorithm.
+        for secret in [RFC_SECRET_SHA1, "MTIzNDU2Nzg5MDEyMzQ1Njc4OTA="] {
+            let plaintext = format!("pass\ntotp: {secret}\ntotp-algorithm: steam");
+            assert_eq!(token_at(&plaintext, 59), "PV9M4");
+            assert_eq!(token_at(&plaintext, 1111111109), "PY4YB");
+            assert_eq!(token_at(&plaintext, 2000000000), "9N776");
+        }
+    }
+
+    #[test]
+    fn yandex() {
+        // Secret is "0123456789abcdef" followed by a checksum, which is not used. Expected tokens
+        // are computed with an independent implementation of the algorithm.
+        let totp = Totp::from_url(
+            "otpauth://totp/test?secret=GAYTEMZUGU3DOOBZMFRGGZDFMYAACAQDAQCQMBYIBE&algorithm=YANDEX&pin=5239",
+        )
+        .unwrap();
+        assert_eq!(totp.kind(), "yandex");
+        assert_eq!(totp.totp.secret, b"0123456789abcdef");
+        let Variant::Yandex(pin) = &totp.variant else {
+            panic!("not a Yandex.Key secret");
+        };
+
+        #[rustfmt::skip]
+        let set = [
+            (pin.unsecure_to_str().unwrap(), 1641559648, "fdmatffb"),
+            ("1234", 59, "flavipgp"),
+            // Derived key starts with zero byte, which is dropped
+            ("215", 59, "pfxxgjxf"),
+        ];
+
+        for (pin, time, token) in set {
+            let token_at = yandex_token(&totp.totp.secret, &pin.into(), time / totp.totp.step);
+            assert_eq!(
+                token_at.unsecure_to_str().unwrap(),
+                token,
+                "pin {pin} at {time}"
+            );
+        }
+    }
+
+    #[test]
+    fn motp() {
+        // Expected tokens are computed with an independent implementation of the algorithm
+        let totp =
+            Totp::from_url("otpauth://totp/test?secret=E3152AFEE62599C8&algorithm=MOTP&pin=1234")
+                .unwrap();
+        assert_eq!(totp.kind(), "motp");
+        assert_eq!(totp.totp.secret, b"e3152afee62599c8");
+        assert_eq!(totp.totp.step, MOTP_PERIOD);
+        let Variant::Motp(pin) = &totp.variant else {
+            panic!("not a Mobile-OTP secret");
+        };
+
+        for (time, token) in [(1165071969, "b2c0d2"), (1234567890, "49c5b4")] {
+            let token_at = motp_token(&totp.totp.secret, pin, time / totp.totp.step);
+            assert_eq!(token_at.unsecure_to_str().unwrap(), token, "at {time}");
+        }
+    }
 }
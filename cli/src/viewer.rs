@@ -41,6 +41,16 @@ pub(crate) fn viewer(
     matcher_main: &MainMatcher,
     query: Option<String>,
 ) -> Result<()> {
+    // Summarize binary secrets, never render them
+    let plaintext = if plaintext.is_binary() {
+        Plaintext::from(format!(
+            "[binary secret, {} bytes]",
+            plaintext.binary_size()
+        ))
+    } else {
+        plaintext
+    };
+
     // Use custom viewer when prs pager is configured
     if env::has_non_empty_env(ENV_VAR_PAGER) {
         return pager(plaintext, timeout, matcher_main);
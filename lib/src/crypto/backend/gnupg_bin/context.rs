@@ -1,12 +1,16 @@
 //! Provides GnuPG binary context adapter.
 
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::Duration;
+
 use anyhow::Result;
 use thiserror::Error;
 use version_compare::Version;
 
 use super::raw_cmd::gpg_stdout_ok;
 use super::{Config, raw};
-use crate::crypto::{Config as CryptoConfig, IsContext, Key, Proto, proto};
+use crate::crypto::{Config as CryptoConfig, GeneratedKey, IsContext, Key, Proto, proto};
 use crate::{Ciphertext, Plaintext, Recipients};
 
 /// Binary name.
@@ -54,10 +58,33 @@ impl IsContext for Context {
         raw::decrypt(&self.config, ciphertext)
     }
 
+    fn encrypt_file_stream(
+        &mut self,
+        recipients: &Recipients,
+        input: &mut dyn Read,
+        path: &Path,
+    ) -> Result<()> {
+        let fingerprints: Vec<String> = recipients
+            .keys()
+            .iter()
+            .map(|key| key.fingerprint(false))
+            .collect();
+        let fingerprints: Vec<&str> = fingerprints.iter().map(|fp| fp.as_str()).collect();
+        raw::encrypt_stream(&self.config, &fingerprints, input, path)
+    }
+
+    fn decrypt_file_stream(&mut self, path: &Path, output: &mut dyn Write) -> Result<()> {
+        raw::decrypt_stream(&self.config, path, output)
+    }
+
     fn can_decrypt(&mut self, ciphertext: Ciphertext) -> Result<bool> {
         raw::can_decrypt(&self.config, ciphertext)
     }
 
+    fn recipient_key_ids(&mut self, ciphertext: Ciphertext) -> Result<Vec<String>> {
+        raw::recipient_key_ids(&self.config, ciphertext)
+    }
+
     fn keys_public(&mut self) -> Result<Vec<Key>> {
         Ok(raw::public_keys(&self.config)?
             .into_iter()
@@ -65,6 +92,7 @@ impl IsContext for Context {
                 Key::Gpg(proto::gpg::Key {
                     fingerprint: key.0,
                     user_ids: key.1,
+                    meta: key.2,
                 })
             })
             .collect())
@@ -77,6 +105,7 @@ impl IsContext for Context {
                 Key::Gpg(proto::gpg::Key {
                     fingerprint: key.0,
                     user_ids: key.1,
+                    meta: key.2,
                 })
             })
             .collect())
@@ -86,10 +115,40 @@ impl IsContext for Context {
         raw::import_key(&self.config, key)
     }
 
+    fn read_keys(&mut self, key: &[u8]) -> Result<Vec<Key>> {
+        Ok(raw::show_keys(&self.config, key)?
+            .into_iter()
+            .map(|key| {
+                Key::Gpg(proto::gpg::Key {
+                    fingerprint: key.0,
+                    user_ids: key.1,
+                    meta: key.2,
+                })
+            })
+            .collect())
+    }
+
     fn export_key(&mut self, key: Key) -> Result<Vec<u8>> {
         raw::export_key(&self.config, &key.fingerprint(false))
     }
 
+    fn generate_key(
+        &mut self,
+        user_id: &str,
+        expires: Option<Duration>,
+        _passphrase: Option<Plaintext>,
+    ) -> Result<GeneratedKey> {
+        let fingerprint = raw::generate_key(&self.config, user_id, expires)?;
+        Ok(GeneratedKey {
+            key: self.get_private_key(&fingerprint)?,
+            secret_key: None,
+        })
+    }
+
+    fn keeps_secret_keys(&self) -> bool {
+        true
+    }
+
     fn supports_proto(&self, proto: Proto) -> bool {
         proto == Proto::Gpg
     }
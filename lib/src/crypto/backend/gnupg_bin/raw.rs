@@ -2,15 +2,21 @@
 //!
 //! This provides the most basic and bare functions to interface with a GnuPG backend binary.
 
-use std::collections::VecDeque;
+use std::ffi::OsStr;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::{Duration, UNIX_EPOCH};
 
 use anyhow::Result;
 use regex::Regex;
 use thiserror::Error;
 
 use super::Config;
-use super::raw_cmd::{gpg_stdin_output, gpg_stdin_stdout_ok_bin, gpg_stdout_ok, gpg_stdout_ok_bin};
-use crate::crypto::util;
+use super::raw_cmd::{
+    gpg_stdin_output, gpg_stdin_stdout_ok_bin, gpg_stdout_ok, gpg_stdout_ok_bin, gpg_stream_ok,
+};
+use crate::crypto::{Capabilities, KeyMeta, util};
 use crate::{Ciphertext, Plaintext};
 
 /// Partial output from gpg if the user does not own the secret key.
@@ -57,6 +63,77 @@ pub fn decrypt(config: &Config, ciphertext: Ciphertext) -> Result<Plaintext> {
     ))
 }
 
+/// Encrypt plaintext from a reader for the given recipients, write ciphertext to a file.
+///
+/// Streams data through gpg, the plaintext is never fully buffered in memory. The ciphertext is
+/// written to a temporary file first, and is moved into place on success.
+///
+/// - `config`: GPG config
+/// - `recipients`: list of recipient fingerprints to encrypt for
+/// - `input`: plaintext to encrypt
+/// - `path`: file to write ciphertext to
+///
+/// # Panics
+///
+/// Panics if list of recipients is empty.
+pub fn encrypt_stream(
+    config: &Config,
+    recipients: &[&str],
+    input: &mut dyn Read,
+    path: &Path,
+) -> Result<()> {
+    assert!(
+        !recipients.is_empty(),
+        "attempting to encrypt secret for empty list of recipients"
+    );
+
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    let tmp = Path::new(&tmp);
+
+    // Build argument list
+    let mut args: Vec<&OsStr> = ["--quiet", "--openpgp", "--trust-model", "always", "--yes"]
+        .iter()
+        .map(OsStr::new)
+        .collect();
+    for fp in recipients {
+        args.push("--recipient".as_ref());
+        args.push(fp.as_ref());
+    }
+    args.extend([
+        OsStr::new("--output"),
+        tmp.as_os_str(),
+        "--encrypt".as_ref(),
+    ]);
+
+    if let Err(err) = gpg_stream_ok(config, args, Some(input), None) {
+        let _ = fs::remove_file(tmp);
+        return Err(Err::Encrypt(err).into());
+    }
+    fs::rename(tmp, path).map_err(|err| Err::Encrypt(err.into()).into())
+}
+
+/// Decrypt ciphertext from a file, write plaintext to a writer.
+///
+/// Streams data through gpg, the plaintext is never fully buffered in memory.
+///
+/// - `config`: GPG config
+/// - `path`: file to decrypt
+/// - `output`: writer to write plaintext to
+pub fn decrypt_stream(config: &Config, path: &Path, output: &mut dyn Write) -> Result<()> {
+    gpg_stream_ok(
+        config,
+        [
+            OsStr::new("--quiet"),
+            "--decrypt".as_ref(),
+            path.as_os_str(),
+        ],
+        None,
+        Some(output),
+    )
+    .map_err(|err| Err::Decrypt(err).into())
+}
+
 /// Check whether we can decrypt ciphertext.
 ///
 /// This checks whether whether we own the secret key to decrypt the given ciphertext.
@@ -81,22 +158,65 @@ pub fn can_decrypt(config: &Config, ciphertext: Ciphertext) -> Result<bool> {
     }
 }
 
+/// Get recipient key IDs of ciphertext.
+///
+/// Lists the public key encrypted session key packets, without decrypting.
+///
+/// - `config`: GPG config
+/// - `ciphertext`: ciphertext to list recipients for
+pub fn recipient_key_ids(config: &Config, ciphertext: Ciphertext) -> Result<Vec<String>> {
+    let packets = gpg_stdin_stdout_ok_bin(
+        config,
+        ["--quiet", "--list-only", "--list-packets"],
+        ciphertext.unsecure_ref(),
+    )
+    .map_err(Err::Packets)?;
+    Ok(util::list_packets_key_ids(&String::from_utf8_lossy(
+        &packets,
+    )))
+}
+
 /// Get all public keys from keychain.
 ///
 /// - `config`: GPG config
 pub fn public_keys(config: &Config) -> Result<Vec<KeyId>> {
-    let list =
-        gpg_stdout_ok(config, ["--list-keys", "--keyid-format", "LONG"]).map_err(Err::Keys)?;
-    parse_key_list(list).ok_or_else(|| Err::UnexpectedOutput.into())
+    let list = gpg_stdout_ok(
+        config,
+        ["--list-keys", "--with-colons", "--fixed-list-mode"],
+    )
+    .map_err(Err::Keys)?;
+    parse_key_list(&list).ok_or_else(|| Err::UnexpectedOutput.into())
 }
 
 /// Get all private/secret keys from keychain.
 ///
 /// - `config`: GPG config
 pub fn private_keys(config: &Config) -> Result<Vec<KeyId>> {
-    let list = gpg_stdout_ok(config, ["--list-secret-keys", "--keyid-format", "LONG"])
-        .map_err(Err::Keys)?;
-    parse_key_list(list).ok_or_else(|| Err::UnexpectedOutput.into())
+    let list = gpg_stdout_ok(
+        config,
+        ["--list-secret-keys", "--with-colons", "--fixed-list-mode"],
+    )
+    .map_err(Err::Keys)?;
+    parse_key_list(&list).ok_or_else(|| Err::UnexpectedOutput.into())
+}
+
+/// Read keys from bytes, without importing them into the keychain.
+///
+/// - `config`: GPG config
+/// - `key`: key data to read
+pub fn show_keys(config: &Config, key: &[u8]) -> Result<Vec<KeyId>> {
+    let list = gpg_stdin_stdout_ok_bin(
+        config,
+        [
+            "--quiet",
+            "--show-keys",
+            "--with-colons",
+            "--fixed-list-mode",
+        ],
+        key,
+    )
+    .map_err(Err::Keys)?;
+    parse_key_list(&String::from_utf8_lossy(&list)).ok_or_else(|| Err::UnexpectedOutput.into())
 }
 
 /// Import given key from bytes into keychain.
@@ -149,80 +269,179 @@ pub fn export_key(config: &Config, fingerprint: &str) -> Result<Vec<u8>> {
     Ok(data)
 }
 
-/// A key identifier with a fingerprint and user IDs.
+/// Generate a new key pair in the keychain.
+///
+/// Generates an Ed25519 primary key for signing and certification, and adds a Cv25519 subkey for
+/// encryption. The passphrase is asked for through pinentry.
+///
+/// Returns the fingerprint of the new key.
+///
+/// - `config`: GPG config
+/// - `user_id`: user ID of the new key, such as `Name <email>`
+/// - `expires`: key expiry, or `None` to never expire
+pub fn generate_key(config: &Config, user_id: &str, expires: Option<Duration>) -> Result<String> {
+    let expire = match expires {
+        Some(expires) => format!("seconds={}", expires.as_secs()),
+        None => "never".into(),
+    };
+
+    // Generate primary key, find fingerprint in status output
+    let status = gpg_stdout_ok(
+        config,
+        [
+            "--quiet",
+            "--batch",
+            "--status-fd",
+            "1",
+            "--quick-generate-key",
+            user_id,
+            "ed25519",
+            "sign,cert",
+            &expire,
+        ],
+    )
+    .map_err(Err::Generate)?;
+    let fingerprint = status
+        .lines()
+        .find_map(|line| line.strip_prefix("[GNUPG:] KEY_CREATED "))
+        .and_then(|created| created.split_whitespace().nth(1))
+        .ok_or(Err::UnexpectedOutput)?
+        .to_string();
+
+    // Add encryption subkey
+    gpg_stdout_ok(
+        config,
+        [
+            "--quiet",
+            "--batch",
+            "--quick-add-key",
+            &fingerprint,
+            "cv25519",
+            "encr",
+            &expire,
+        ],
+    )
+    .map_err(Err::Generate)?;
+
+    Ok(fingerprint)
+}
+
+/// A key identifier with a fingerprint, user IDs and key metadata.
 #[derive(Clone)]
-pub struct KeyId(pub String, pub Vec<String>);
+pub struct KeyId(pub String, pub Vec<String>, pub KeyMeta);
 
-/// Parse key list output from gnupg.
+/// Parse colon delimited key list output from gnupg.
+///
+/// See `doc/DETAILS` in the GnuPG sources for a description of the format.
 // TODO: throw proper errors on parse failure
-fn parse_key_list(list: String) -> Option<Vec<KeyId>> {
-    // Return empty list if there's no key loaded
-    if list.trim().is_empty() {
-        return Some(vec![]);
-    }
-
-    let mut lines: VecDeque<_> = list.lines().collect();
-
-    // Second line must be a line
-    lines.pop_front()?;
-    if lines
-        .pop_front()?
-        .bytes()
-        .filter(|&b| b != b'-')
-        .take(1)
-        .count()
-        > 0
-    {
-        return None;
-    }
-
+fn parse_key_list(list: &str) -> Option<Vec<KeyId>> {
     let re_fingerprint = Regex::new(r"^[0-9A-F]{16,}$").unwrap();
-    let re_user_id = Regex::new(r"^uid\s*\[[a-z ]+\]\s*(.*)$").unwrap();
 
     // Walk through the list, collect list of keys
-    let mut keys = Vec::new();
-    while !lines.is_empty() {
-        match lines.pop_front()? {
+    let mut keys: Vec<KeyId> = Vec::new();
+    let mut primary = false;
+    for line in list.lines() {
+        let fields: Vec<&str> = line.split(':').collect();
+        match fields[0] {
             // Start reading a new key
-            l if l.starts_with("pub ") || l.starts_with("sec ") => {
-                // Get the fingerprint
-                let fingerprint = util::format_fingerprint(lines.pop_front()?.trim());
-                if !re_fingerprint.is_match(&fingerprint) {
-                    return None;
-                }
-
-                // Find and parse user IDs
-                let mut user_ids = Vec::new();
-                while !lines.is_empty() {
-                    match lines.pop_front()? {
-                        // Read user ID
-                        l if l.starts_with("uid ") => {
-                            let captures = re_user_id.captures(l)?;
-                            user_ids.push(captures[1].to_string());
-                        }
-
-                        // Finalize on empty line
-                        l if l.trim().is_empty() => break,
-
-                        _ => {}
-                    }
-                }
-
-                // Add read key to list
-                keys.push(KeyId(fingerprint, user_ids));
+            "pub" | "sec" => {
+                let mut meta = parse_key_meta(&fields)?;
+                meta.key_ids.push(fields.get(4)?.to_uppercase());
+                keys.push(KeyId(String::new(), vec![], meta));
+                primary = true;
             }
 
-            // Ignore empty lines
-            l if l.trim().is_empty() => {}
+            // Fingerprint of primary key, subkey fingerprints follow later
+            "fpr" if primary => {
+                keys.last_mut()?.0 = util::format_fingerprint(fields.get(9)?);
+                primary = false;
+            }
+
+            // Subkeys have their own key ID and fingerprint
+            "sub" | "ssb" => {
+                keys.last_mut()?
+                    .2
+                    .key_ids
+                    .push(fields.get(4)?.to_uppercase());
+                primary = false;
+            }
 
-            // Got something unexpected
-            _ => return None,
+            // Read user ID, skip revoked ones
+            "uid" if fields.get(1) != Some(&"r") => {
+                keys.last_mut()?.1.push(unescape_colons(fields.get(9)?));
+            }
+
+            _ => {}
         }
     }
 
+    // Every key must have a fingerprint
+    if keys.iter().any(|key| !re_fingerprint.is_match(&key.0)) {
+        return None;
+    }
+
     Some(keys)
 }
 
+/// Parse key metadata from the fields of a `pub` or `sec` record.
+fn parse_key_meta(fields: &[&str]) -> Option<KeyMeta> {
+    let time = |field: &str| {
+        field
+            .parse::<u64>()
+            .ok()
+            .filter(|&secs| secs > 0)
+            .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+    };
+
+    // Uppercase capabilities apply to the whole key, including subkeys
+    let capabilities = fields.get(11)?;
+    let algorithm = match (*fields.get(3)?, fields.get(16).copied().unwrap_or_default()) {
+        (_, curve) if !curve.is_empty() => Some(curve.to_string()),
+        ("1" | "2" | "3", _) => Some(format!("rsa{}", fields.get(2)?)),
+        ("16" | "20", _) => Some(format!("elg{}", fields.get(2)?)),
+        ("17", _) => Some(format!("dsa{}", fields.get(2)?)),
+        _ => None,
+    };
+
+    Some(KeyMeta {
+        created: time(fields.get(5)?),
+        expires: time(fields.get(6)?),
+        revoked: *fields.get(1)? == "r",
+        capabilities: Some(Capabilities {
+            encrypt: capabilities.contains('E'),
+            sign: capabilities.contains('S'),
+            certify: capabilities.contains('C'),
+            authenticate: capabilities.contains('A'),
+        }),
+        algorithm,
+        key_ids: vec![],
+    })
+}
+
+/// Unescape a field from colon delimited output.
+///
+/// Special characters are escaped as C-style `\xNN` sequences.
+fn unescape_colons(field: &str) -> String {
+    let mut bytes = Vec::with_capacity(field.len());
+    let mut rest = field.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'\\'
+            && tail.len() >= 3
+            && tail[0] == b'x'
+            && let Some(decoded) = std::str::from_utf8(&tail[1..3])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            bytes.push(decoded);
+            rest = &tail[3..];
+            continue;
+        }
+        bytes.push(byte);
+        rest = tail;
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
 /// GnuPG binary error.
 #[derive(Debug, Error)]
 pub enum Err {
@@ -235,6 +454,9 @@ pub enum Err {
     #[error("failed to decrypt ciphertext")]
     Decrypt(#[source] anyhow::Error),
 
+    #[error("failed to list ciphertext packets")]
+    Packets(#[source] anyhow::Error),
+
     #[error("failed to obtain keys from gpg keychain")]
     Keys(#[source] anyhow::Error),
 
@@ -243,4 +465,53 @@ pub enum Err {
 
     #[error("failed to export key from gpg keychain")]
     Export(#[source] anyhow::Error),
+
+    #[error("failed to generate key in gpg keychain")]
+    Generate(#[source] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key_list() {
+        let list = "\
+tru::1:1792361732:0:3:1:5
+pub:u:255:22:3B41CF8197074CBC:1792361732:::u:::scESC:::::ed25519:::0:
+fpr:::::::::A423B32B3CE9250155D0155E3B41CF8197074CBC:
+uid:u::::1792361732::F2E723F89EE13F7007A48DDACDB395768100ABC3::Test User <test@example.com>::::::::::0:
+uid:r::::1792361732::F2E723F89EE13F7007A48DDACDB395768100ABC4::Revoked <revoked@example.com>::::::::::0:
+sub:u:255:18:DEEB73EC4D7F6C09:1792361732::::::e:::::cv25519::
+fpr:::::::::E2D5351FD893CEE17DA461E3DEEB73EC4D7F6C09:
+pub:e:4096:1:6550C776BEE3BA80:1792366636:1792366637::-:::sc::::::::0:
+fpr:::::::::7545DC9D214448594A8516606550C776BEE3BA80:
+uid:e::::1792366636::554876D8D3B20279AF35EAD326E1F370D6BE03F3::Old \\x3a <old@example.com>::::::::::0:
+";
+        let keys = parse_key_list(list).unwrap();
+        assert_eq!(keys.len(), 2);
+
+        assert_eq!(keys[0].0, "A423B32B3CE9250155D0155E3B41CF8197074CBC");
+        assert_eq!(keys[0].1, vec!["Test User <test@example.com>"]);
+        let meta = &keys[0].2;
+        assert_eq!(meta.algorithm.as_deref(), Some("ed25519"));
+        assert_eq!(
+            meta.created,
+            Some(UNIX_EPOCH + Duration::from_secs(1792361732))
+        );
+        assert_eq!(meta.expires, None);
+        assert_eq!(meta.capabilities.unwrap().to_string(), "ESC");
+        assert!(meta.status().is_usable());
+        assert_eq!(meta.key_ids, vec!["3B41CF8197074CBC", "DEEB73EC4D7F6C09"]);
+
+        assert_eq!(keys[1].0, "7545DC9D214448594A8516606550C776BEE3BA80");
+        assert_eq!(keys[1].1, vec!["Old : <old@example.com>"]);
+        let meta = &keys[1].2;
+        assert_eq!(meta.algorithm.as_deref(), Some("rsa4096"));
+        assert!(meta.is_expired());
+        assert_eq!(meta.status(), crate::crypto::KeyStatus::Expired);
+
+        assert!(parse_key_list("").unwrap().is_empty());
+        assert!(parse_key_list("pub:u:255:22:3B41CF8197074CBC:1792361732:::u:::scESC:").is_none());
+    }
 }
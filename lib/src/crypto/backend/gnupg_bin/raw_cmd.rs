@@ -2,7 +2,7 @@
 
 use std::char::decode_utf16;
 use std::ffi::OsStr;
-use std::io::{self, Write};
+use std::io::{self, Read, Write};
 use std::process::{Command, Output, Stdio};
 
 use anyhow::Result;
@@ -30,19 +30,19 @@ where
 {
     let mut cmd = cmd_gpg(config, args);
 
-    // Pass stdin to child process
+    // Pass stdin to child process, from separate thread to not block on large output
     #[allow(clippy::zombie_processes)]
     let mut child = cmd.spawn().unwrap();
-    if let Err(err) = child.stdin.as_mut().unwrap().write_all(stdin) {
-        if let Err(err) = child.kill() {
-            eprintln!("failed to kill gpg process: {err}");
-        }
-        return Err(Err::System(err).into());
-    }
-
-    child
-        .wait_with_output()
-        .map_err(|err| Err::System(err).into())
+    let mut child_stdin = child.stdin.take().unwrap();
+    std::thread::scope(|s| {
+        let writer = s.spawn(move || child_stdin.write_all(stdin));
+        let output = child.wait_with_output().map_err(Err::System)?;
+        writer
+            .join()
+            .expect("gpg stdin writer panicked")
+            .map_err(Err::System)?;
+        Ok(output)
+    })
 }
 
 /// Invoke a gpg command with the given arguments, return stdout on success.
@@ -83,6 +83,79 @@ where
     Ok(output.stdout)
 }
 
+/// Invoke a gpg command, stream stdin and stdout, return on success.
+///
+/// Copies `stdin` into the gpg process and its output into `stdout`. Stdin or stdout is closed if
+/// not given.
+pub(super) fn gpg_stream_ok<I, S>(
+    config: &Config,
+    args: I,
+    stdin: Option<&mut dyn Read>,
+    stdout: Option<&mut dyn Write>,
+) -> Result<()>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    let mut cmd = cmd_gpg(config, args);
+    if stdin.is_none() {
+        cmd.stdin(Stdio::null());
+    }
+    if stdout.is_none() {
+        cmd.stdout(Stdio::null());
+    }
+
+    let mut child = cmd.spawn().map_err(Err::System)?;
+
+    // Drain stderr from separate thread to not block on large output
+    let mut child_stderr = child.stderr.take().unwrap();
+    std::thread::scope(|s| {
+        let reader = s.spawn(move || {
+            let mut stderr = vec![];
+            child_stderr.read_to_end(&mut stderr).map(|_| stderr)
+        });
+
+        // Stream stdin to child process, close it when done
+        let mut result = Ok(0);
+        if let Some(stdin) = stdin {
+            let mut child_stdin = child.stdin.take().unwrap();
+            result = io::copy(stdin, &mut child_stdin);
+        }
+
+        // Stream stdout from child process
+        if let Some(stdout) = stdout
+            && result.is_ok()
+        {
+            let mut child_stdout = child.stdout.take().unwrap();
+            result = io::copy(&mut child_stdout, stdout);
+        }
+
+        // Kill and reap child process on streaming failure
+        if let Err(err) = result {
+            if let Err(err) = child.kill() {
+                eprintln!("failed to kill gpg process: {err}");
+            }
+            let _ = child.wait();
+            let _ = reader.join();
+            return Err(Err::System(err).into());
+        }
+
+        let status = child.wait().map_err(Err::System)?;
+        let stderr = reader
+            .join()
+            .expect("gpg stderr reader panicked")
+            .map_err(Err::System)?;
+        cmd_assert_status(
+            config,
+            &Output {
+                status,
+                stdout: vec![],
+                stderr,
+            },
+        )
+    })
+}
+
 /// Build a gpg command to run.
 fn cmd_gpg<I, S>(config: &Config, args: I) -> Command
 where
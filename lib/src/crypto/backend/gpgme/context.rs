@@ -1,13 +1,14 @@
 //! Provides GPGME binary context adapter.
 
 use std::env;
+use std::time::Duration;
 
 use anyhow::Result;
 use gpgme::{Context as GpgmeContext, PinentryMode, Protocol};
 use thiserror::Error;
 
 use super::raw;
-use crate::crypto::{Config, IsContext, Key, Proto, proto};
+use crate::crypto::{Config, GeneratedKey, IsContext, Key, Proto, proto};
 use crate::{Ciphertext, Plaintext, Recipients, util};
 
 /// Protocol to use.
@@ -67,6 +68,10 @@ impl IsContext for Context {
         raw::can_decrypt(&mut self.context, ciphertext)
     }
 
+    fn recipient_key_ids(&mut self, ciphertext: Ciphertext) -> Result<Vec<String>> {
+        raw::recipient_key_ids(&mut self.context, ciphertext)
+    }
+
     fn keys_public(&mut self) -> Result<Vec<Key>> {
         Ok(raw::public_keys(&mut self.context)?
             .into_iter()
@@ -74,6 +79,7 @@ impl IsContext for Context {
                 Key::Gpg(proto::gpg::Key {
                     fingerprint: key.0,
                     user_ids: key.1,
+                    meta: key.2,
                 })
             })
             .collect())
@@ -86,6 +92,7 @@ impl IsContext for Context {
                 Key::Gpg(proto::gpg::Key {
                     fingerprint: key.0,
                     user_ids: key.1,
+                    meta: key.2,
                 })
             })
             .collect())
@@ -95,10 +102,40 @@ impl IsContext for Context {
         raw::import_key(&mut self.context, key)
     }
 
+    fn read_keys(&mut self, key: &[u8]) -> Result<Vec<Key>> {
+        Ok(raw::read_keys(&mut self.context, key)?
+            .into_iter()
+            .map(|key| {
+                Key::Gpg(proto::gpg::Key {
+                    fingerprint: key.0,
+                    user_ids: key.1,
+                    meta: key.2,
+                })
+            })
+            .collect())
+    }
+
     fn export_key(&mut self, key: Key) -> Result<Vec<u8>> {
         raw::export_key(&mut self.context, &key.fingerprint(false))
     }
 
+    fn generate_key(
+        &mut self,
+        user_id: &str,
+        expires: Option<Duration>,
+        _passphrase: Option<Plaintext>,
+    ) -> Result<GeneratedKey> {
+        let fingerprint = raw::generate_key(&mut self.context, user_id, expires)?;
+        Ok(GeneratedKey {
+            key: self.get_private_key(&fingerprint)?,
+            secret_key: None,
+        })
+    }
+
+    fn keeps_secret_keys(&self) -> bool {
+        true
+    }
+
     fn supports_proto(&self, proto: Proto) -> bool {
         proto == Proto::Gpg
     }
@@ -2,11 +2,16 @@
 //!
 //! This provides the most basic and bare functions to interface with the GPGME backend.
 
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
 use anyhow::Result;
-use gpgme::{Context, EncryptFlags, Key};
+use gpgme::{Context, CreateKeyFlags, EncryptFlags, Key};
 use thiserror::Error;
 use zeroize::Zeroize;
 
+use crate::crypto::{Capabilities, KeyMeta, util};
 use crate::{Ciphertext, Plaintext};
 
 /// GPGME encryption flags.
@@ -78,6 +83,46 @@ pub fn can_decrypt(context: &mut Context, ciphertext: Ciphertext) -> Result<bool
     }
 }
 
+/// Get recipient key IDs of ciphertext.
+///
+/// GPGME cannot list recipients without decrypting. Instead, this lists the public key encrypted
+/// session key packets with the gpg binary and home directory of the GPGME engine.
+///
+/// - `context`: GPGME context
+/// - `ciphertext`: ciphertext to list recipients for
+pub fn recipient_key_ids(context: &mut Context, ciphertext: Ciphertext) -> Result<Vec<String>> {
+    let engine = context.engine_info();
+    let mut cmd = Command::new(engine.path().map_err(|_| Err::Engine)?);
+    if let Ok(home_dir) = engine.home_dir() {
+        cmd.arg("--homedir").arg(home_dir);
+    }
+    let mut child = cmd
+        .args(["--batch", "--quiet", "--list-only", "--list-packets"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(Err::Packets)?;
+
+    // Pass stdin to child process, from separate thread to not block on large output
+    let mut child_stdin = child.stdin.take().unwrap();
+    let data = ciphertext.unsecure_ref();
+    let output = std::thread::scope(|s| {
+        let writer = s.spawn(move || child_stdin.write_all(data));
+        let output = child.wait_with_output()?;
+        writer.join().expect("gpg stdin writer panicked")?;
+        Ok::<_, io::Error>(output)
+    })
+    .map_err(Err::Packets)?;
+    if !output.status.success() {
+        return Err(Err::PacketsStatus.into());
+    }
+
+    Ok(util::list_packets_key_ids(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
 /// Get all public keys from keychain.
 ///
 /// - `context`: GPGME context
@@ -85,7 +130,6 @@ pub fn public_keys(context: &mut Context) -> Result<Vec<KeyId>> {
     Ok(context
         .keys()?
         .filter_map(|k| k.ok())
-        .filter(|k| k.can_encrypt())
         .map(|k| k.into())
         .collect())
 }
@@ -102,6 +146,18 @@ pub fn private_keys(context: &mut Context) -> Result<Vec<KeyId>> {
         .collect())
 }
 
+/// Read keys from bytes, without importing them into the keychain.
+///
+/// - `context`: GPGME context
+/// - `key`: key data to read
+pub fn read_keys(context: &mut Context, key: &[u8]) -> Result<Vec<KeyId>> {
+    Ok(context
+        .read_keys(key)?
+        .filter_map(|k| k.ok())
+        .map(|k| k.into())
+        .collect())
+}
+
 /// Import given key from bytes into keychain.
 ///
 /// - `context`: GPGME context
@@ -128,6 +184,53 @@ pub fn import_key(context: &mut Context, key: &[u8]) -> Result<()> {
         .map_err(|err| Err::Import(err.into()).into())
 }
 
+/// Generate a new key pair in the keychain.
+///
+/// Generates an Ed25519 primary key for signing and certification, and adds a Cv25519 subkey for
+/// encryption. The passphrase is asked for through pinentry.
+///
+/// Returns the fingerprint of the new key.
+///
+/// - `context`: GPGME context
+/// - `user_id`: user ID of the new key, such as `Name <email>`
+/// - `expires`: key expiry, or `None` to never expire
+pub fn generate_key(
+    context: &mut Context,
+    user_id: &str,
+    expires: Option<Duration>,
+) -> Result<String> {
+    // GPGME uses a default expiry for zero, unless explicitly told not to expire
+    let flags = match expires {
+        Some(_) => CreateKeyFlags::empty(),
+        None => CreateKeyFlags::NOEXPIRE,
+    };
+    let expires = expires.unwrap_or_default();
+
+    // Generate primary key
+    let result = context
+        .create_key_with_flags(
+            user_id,
+            "ed25519",
+            expires,
+            flags | CreateKeyFlags::SIGN | CreateKeyFlags::CERT,
+        )
+        .map_err(Err::Generate)?;
+    let fingerprint = result
+        .fingerprint()
+        .map_err(|_| Err::GenerateFingerprint)?
+        .to_string();
+
+    // Add encryption subkey
+    let key = context
+        .get_secret_key(&fingerprint)
+        .map_err(Err::UnknownFingerprint)?;
+    context
+        .create_subkey_with_flags(&key, "cv25519", expires, flags | CreateKeyFlags::ENCR)
+        .map_err(Err::Generate)?;
+
+    Ok(fingerprint)
+}
+
 /// Export the given key as bytes.
 ///
 /// # Panics
@@ -161,9 +264,9 @@ pub fn export_key(context: &mut Context, fingerprint: &str) -> Result<Vec<u8>> {
     Ok(data)
 }
 
-/// A key identifier with a fingerprint and user IDs.
+/// A key identifier with a fingerprint, user IDs and key metadata.
 #[derive(Clone)]
-pub struct KeyId(pub String, pub Vec<String>);
+pub struct KeyId(pub String, pub Vec<String>, pub KeyMeta);
 
 impl From<Key> for KeyId {
     fn from(key: Key) -> Self {
@@ -192,10 +295,32 @@ impl From<Key> for KeyId {
                     parts.join(" ")
                 })
                 .collect(),
+            key_meta(&key),
         )
     }
 }
 
+/// Get key metadata from GPGME key.
+fn key_meta(key: &Key) -> KeyMeta {
+    let primary = key.primary_key();
+    KeyMeta {
+        created: primary.as_ref().and_then(|k| k.creation_time()),
+        expires: primary.as_ref().and_then(|k| k.expiration_time()),
+        revoked: key.is_revoked(),
+        capabilities: Some(Capabilities {
+            encrypt: key.can_encrypt(),
+            sign: key.can_sign(),
+            certify: key.can_certify(),
+            authenticate: key.can_authenticate(),
+        }),
+        algorithm: primary.and_then(|k| k.algorithm_name().ok()),
+        key_ids: key
+            .subkeys()
+            .filter_map(|k| k.id().ok().map(str::to_uppercase))
+            .collect(),
+    }
+}
+
 /// Transform fingerprints into GPGME keys.
 ///
 /// Errors if a fingerprint does not match a public key.
@@ -223,6 +348,15 @@ pub enum Err {
     #[error("failed to decrypt ciphertext")]
     Decrypt(#[source] gpgme::Error),
 
+    #[error("failed to find gpg binary of GPGME engine")]
+    Engine,
+
+    #[error("failed to list ciphertext packets")]
+    Packets(#[source] io::Error),
+
+    #[error("failed to list ciphertext packets, gpg exited with error")]
+    PacketsStatus,
+
     #[error("failed to import key")]
     Import(#[source] anyhow::Error),
 
@@ -231,4 +365,10 @@ pub enum Err {
 
     #[error("fingerprint does not match public key in keychain")]
     UnknownFingerprint(#[source] gpgme::Error),
+
+    #[error("failed to generate key")]
+    Generate(#[source] gpgme::Error),
+
+    #[error("failed to generate key, could not read fingerprint of new key")]
+    GenerateFingerprint,
 }
@@ -1,11 +1,12 @@
 //! Provides GPGME binary context adapter.
 
 use std::ops::Deref;
+use std::time::Duration;
 
 use anyhow::Result;
 use thiserror::Error;
 
-use crate::crypto::{Config, IsContext, Key, Proto};
+use crate::crypto::{Config, GeneratedKey, IsContext, Key, Proto, util};
 use crate::{Ciphertext, Plaintext, Recipients};
 use rpgpie_certificate_store::{Error as StoreError, Store};
 
@@ -52,6 +53,10 @@ impl IsContext for Context {
         }
     }
 
+    fn recipient_key_ids(&mut self, ciphertext: Ciphertext) -> Result<Vec<String>> {
+        util::pkesk_key_ids(ciphertext.unsecure_ref())
+    }
+
     fn keys_public(&mut self) -> Result<Vec<Key>> {
         let certs = self.store.search_like_user_id("%")?;
 
@@ -100,6 +105,15 @@ impl IsContext for Context {
         Ok(())
     }
 
+    fn read_keys(&mut self, key: &[u8]) -> Result<Vec<Key>> {
+        Ok(
+            rpgpie::certificate::Certificate::load(&mut std::io::Cursor::new(key))?
+                .iter()
+                .map(raw::metadata_for_cert)
+                .collect(),
+        )
+    }
+
     fn export_key(&mut self, key: Key) -> Result<Vec<u8>> {
         let mut certs = self
             .store
@@ -116,6 +130,35 @@ impl IsContext for Context {
         }
     }
 
+    fn generate_key(
+        &mut self,
+        user_id: &str,
+        expires: Option<Duration>,
+        passphrase: Option<Plaintext>,
+    ) -> Result<GeneratedKey> {
+        // Only the certificate is kept, there is no store for secret key material
+        let passphrase = passphrase.ok_or(Error::NoPassphrase)?;
+        let passphrase = passphrase
+            .unsecure_to_str()
+            .map_err(|err| Error::Any(err.into()))?;
+        let (public_key, secret_key) = raw::generate_key(user_id, expires, passphrase)?;
+        self.import_key(&public_key)?;
+        let key = self
+            .read_keys(&public_key)?
+            .into_iter()
+            .next()
+            .ok_or(Error::CertificateMissing)?;
+
+        Ok(GeneratedKey {
+            key,
+            secret_key: Some(secret_key.into()),
+        })
+    }
+
+    fn keeps_secret_keys(&self) -> bool {
+        false
+    }
+
     fn supports_proto(&self, proto: Proto) -> bool {
         proto == Proto::Gpg
     }
@@ -143,6 +186,8 @@ pub enum Error {
     NoUsablePublicKeys,
     #[error("Certificate missing from key store")]
     CertificateMissing,
+    #[error("No passphrase to protect generated secret key with")]
+    NoPassphrase,
     #[error("{0}")]
     Any(#[from] anyhow::Error),
 }
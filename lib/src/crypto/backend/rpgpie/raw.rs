@@ -1,12 +1,17 @@
 use std::fmt::Write;
+use std::time::{Duration, SystemTime};
 
 use pgp::{
-    Deserializable,
-    crypto::{aead::AeadAlgorithm, sym::SymmetricKeyAlgorithm},
+    Deserializable, KeyType, SecretKeyParamsBuilder, SignedPublicKey, SubkeyParamsBuilder,
+    crypto::{
+        aead::AeadAlgorithm, ecc_curve::ECCCurve, hash::HashAlgorithm, sym::SymmetricKeyAlgorithm,
+    },
+    types::{CompressionAlgorithm, PublicKeyTrait, SecretKeyTrait},
 };
 use rpgpie::policy::Seipd;
 
 use super::context::{Context, Error};
+use crate::crypto::{Capabilities, KeyMeta};
 use crate::{Ciphertext, Key, Plaintext, crypto::proto};
 use anyhow::Result;
 
@@ -40,12 +45,93 @@ pub(super) fn metadata_for_cert(cert: &rpgpie::certificate::Certificate) -> Key
         .map(|u| u.id.to_string())
         .collect();
 
+    // Expired or revoked keys have no valid component keys, and thus no capabilities
+    let primary = ccert.primary_key();
+    let meta = KeyMeta {
+        created: Some(SystemTime::from(*ccert.primary_creation_time())),
+        expires: cert_expires_at(cert),
+        revoked: ccert.revoked_at(&chrono::Utc::now()),
+        capabilities: Some(Capabilities {
+            encrypt: !ccert.valid_encryption_capable_component_keys().is_empty(),
+            sign: !ccert.valid_signing_capable_component_keys().is_empty(),
+            ..Default::default()
+        }),
+        algorithm: Some(primary.algorithm_name())
+            .filter(|name| name != "TODO")
+            .map(|name| name.to_lowercase().replace(' ', "")),
+        key_ids: std::iter::once(primary)
+            .chain(ccert.subkeys())
+            .map(|key| format!("{:X}", key.key_id()))
+            .collect(),
+    };
+
     Key::Gpg(proto::gpg::Key {
         fingerprint: fp,
         user_ids,
+        meta,
     })
 }
 
+/// Get the expiry time of a certificate, `None` if it does not expire.
+///
+/// rpgpie does not expose this, the certificate is parsed as public key to find it.
+fn cert_expires_at(cert: &rpgpie::certificate::Certificate) -> Option<SystemTime> {
+    let mut data = Vec::new();
+    cert.save(false, &mut data).ok()?;
+    SignedPublicKey::from_bytes(data.as_slice())
+        .ok()?
+        .expires_at()
+        .map(SystemTime::from)
+}
+
+/// Generate a new key pair.
+///
+/// Generates an Ed25519 primary key for signing and certification, with a Cv25519 subkey for
+/// encryption. The secret key is protected by the given passphrase.
+///
+/// Returns the ASCII armored public and secret key.
+pub(super) fn generate_key(
+    user_id: &str,
+    expires: Option<Duration>,
+    passphrase: &str,
+) -> std::result::Result<(Vec<u8>, Vec<u8>), Error> {
+    let mut rng = rand_08::thread_rng();
+
+    let subkey = SubkeyParamsBuilder::default()
+        .key_type(KeyType::ECDH(ECCCurve::Curve25519))
+        .can_encrypt(true)
+        .passphrase(Some(passphrase.into()))
+        .build()
+        .map_err(|err| Error::Any(anyhow::Error::msg(err.to_string())))?;
+    let params = SecretKeyParamsBuilder::default()
+        .key_type(KeyType::EdDSALegacy)
+        .can_certify(true)
+        .can_sign(true)
+        .primary_user_id(user_id.into())
+        .preferred_symmetric_algorithms(
+            vec![SymmetricKeyAlgorithm::AES256, SymmetricKeyAlgorithm::AES128].into(),
+        )
+        .preferred_hash_algorithms(vec![HashAlgorithm::SHA2_512, HashAlgorithm::SHA2_256].into())
+        .preferred_compression_algorithms(vec![CompressionAlgorithm::Uncompressed].into())
+        .expiration(expires)
+        .passphrase(Some(passphrase.into()))
+        .subkeys(vec![subkey])
+        .build()
+        .map_err(|err| Error::Any(anyhow::Error::msg(err.to_string())))?;
+
+    let secret_key = params
+        .generate(&mut rng)?
+        .sign(&mut rng, || passphrase.into())?;
+    let public_key = secret_key
+        .public_key()
+        .sign(&mut rng, &secret_key, || passphrase.into())?;
+
+    Ok((
+        public_key.to_armored_bytes(None.into())?,
+        secret_key.to_armored_bytes(None.into())?,
+    ))
+}
+
 fn touch_prompt() {
     eprintln!("Please touch the card");
 }
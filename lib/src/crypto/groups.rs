@@ -0,0 +1,290 @@
+//! Recipient groups.
+//!
+//! Groups map a name to a list of recipient fingerprints, and are stored in a plain text file in
+//! the store root. A `.gpg-id` file references a group with an `@name` line, which is expanded
+//! into the group members when loading recipients.
+//!
+//! Group references are specific to `prs`. Other `pass` clients don't understand them, and fail
+//! to encrypt secrets for a `.gpg-id` file that has one.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Result, ensure};
+use thiserror::Error;
+
+use super::util;
+use crate::Store;
+
+/// Name of the recipient groups file in the store root.
+pub const GROUPS_FILE: &str = ".gpg-groups";
+
+/// Prefix of a group reference in a `.gpg-id` file.
+pub const GROUP_PREFIX: char = '@';
+
+/// Header written at the top of the groups file.
+const GROUPS_HEADER: &str = "# prs recipient groups: [group] followed by member fingerprints";
+
+/// Recipient groups.
+#[derive(Clone, Debug)]
+pub struct Groups {
+    /// Path to the groups file.
+    path: PathBuf,
+
+    /// Member fingerprints by group name.
+    groups: BTreeMap<String, Vec<String>>,
+}
+
+impl Groups {
+    /// Load the recipient groups for the given store.
+    ///
+    /// Returns no groups if the store has no groups file.
+    pub fn load(store: &Store) -> Result<Self> {
+        let path = store.root.join(GROUPS_FILE);
+        let groups = if path.is_file() {
+            parse(&fs::read_to_string(&path).map_err(Err::Read)?)
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Self { path, groups })
+    }
+
+    /// Save the recipient groups.
+    ///
+    /// The groups file is removed if there are no groups.
+    pub fn save(&self) -> Result<()> {
+        if self.groups.is_empty() {
+            if self.path.is_file() {
+                fs::remove_file(&self.path).map_err(Err::Write)?;
+            }
+            return Ok(());
+        }
+
+        fs::write(&self.path, format(&self.groups)).map_err(Err::Write)?;
+        Ok(())
+    }
+
+    /// Get all group names, sorted.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.groups.keys().map(|name| name.as_str())
+    }
+
+    /// Get member fingerprints of the given group.
+    pub fn members(&self, name: &str) -> Option<&[String]> {
+        self.groups.get(&normalize(name)).map(|m| m.as_slice())
+    }
+
+    /// Get names of all groups the given fingerprint is a member of.
+    pub fn groups_with(&self, fingerprint: &str) -> Vec<&str> {
+        self.groups
+            .iter()
+            .filter(|(_, members)| {
+                members
+                    .iter()
+                    .any(|member| util::fingerprints_equal(member, fingerprint))
+            })
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Add a member to a group, the group is created if it doesn't exist.
+    ///
+    /// Returns `true` if added, `false` if the fingerprint already was a member.
+    pub fn add(&mut self, name: &str, fingerprint: &str) -> Result<bool> {
+        validate(name)?;
+        let members = self.groups.entry(normalize(name)).or_default();
+        if members
+            .iter()
+            .any(|member| util::fingerprints_equal(member, fingerprint))
+        {
+            return Ok(false);
+        }
+        members.push(util::format_fingerprint(fingerprint));
+        Ok(true)
+    }
+
+    /// Remove a member from a group, the group is removed if it becomes empty.
+    ///
+    /// Returns `true` if removed, `false` if the fingerprint wasn't a member.
+    pub fn remove(&mut self, name: &str, fingerprint: &str) -> bool {
+        let name = normalize(name);
+        let Some(members) = self.groups.get_mut(&name) else {
+            return false;
+        };
+        let len = members.len();
+        members.retain(|member| !util::fingerprints_equal(member, fingerprint));
+        let removed = members.len() < len;
+        if members.is_empty() {
+            self.groups.remove(&name);
+        }
+        removed
+    }
+
+    /// Expand group references in the given `.gpg-id` entries into member fingerprints.
+    ///
+    /// Fingerprints are kept in order, duplicates are removed. Errors if a referenced group does
+    /// not exist.
+    pub fn expand<S: AsRef<str>>(&self, entries: &[S]) -> Result<Vec<String>> {
+        let mut fingerprints: Vec<String> = Vec::new();
+        for entry in entries {
+            let members = match reference_name(entry.as_ref()) {
+                Some(name) => self
+                    .members(name)
+                    .ok_or_else(|| Err::UnknownGroup(name.into()))?
+                    .to_vec(),
+                None => vec![entry.as_ref().to_string()],
+            };
+            for member in members {
+                if !fingerprints
+                    .iter()
+                    .any(|fp| util::fingerprints_equal(fp, &member))
+                {
+                    fingerprints.push(member);
+                }
+            }
+        }
+        Ok(fingerprints)
+    }
+}
+
+/// Get the group name if the given `.gpg-id` entry is a group reference.
+pub fn reference_name(entry: &str) -> Option<&str> {
+    entry.strip_prefix(GROUP_PREFIX)
+}
+
+/// Format a group reference for a `.gpg-id` file.
+pub fn reference(name: &str) -> String {
+    format!("{GROUP_PREFIX}{}", normalize(name))
+}
+
+/// Validate the given group name.
+///
+/// Names must not be empty, and may only contain alphanumeric characters, `-`, `_` and `.`.
+pub fn validate(name: &str) -> Result<()> {
+    let name = name.strip_prefix(GROUP_PREFIX).unwrap_or(name);
+    ensure!(
+        !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.')),
+        Err::InvalidName(name.into())
+    );
+    Ok(())
+}
+
+/// Normalize the given group name, group names are case insensitive.
+///
+/// An `@` prefix is stripped.
+pub fn normalize(name: &str) -> String {
+    name.trim()
+        .strip_prefix(GROUP_PREFIX)
+        .unwrap_or(name.trim())
+        .to_lowercase()
+}
+
+/// Parse a groups file.
+///
+/// Empty lines, comments and members outside of a group are ignored.
+fn parse(data: &str) -> BTreeMap<String, Vec<String>> {
+    let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut group = None;
+    for line in data.lines().map(|line| line.trim()) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            group = Some(normalize(name));
+            continue;
+        }
+        if let Some(group) = &group {
+            groups
+                .entry(group.clone())
+                .or_default()
+                .push(util::normalize_fingerprint(line));
+        }
+    }
+    groups.retain(|_, members| !members.is_empty());
+    groups
+}
+
+/// Format a groups file.
+fn format(groups: &BTreeMap<String, Vec<String>>) -> String {
+    let mut data = format!("{GROUPS_HEADER}\n");
+    for (name, members) in groups {
+        data.push_str(&format!("\n[{name}]\n"));
+        for member in members {
+            data.push_str(member);
+            data.push('\n');
+        }
+    }
+    data
+}
+
+/// Recipient groups error.
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to read recipient groups file")]
+    Read(#[source] std::io::Error),
+
+    #[error("failed to write recipient groups file")]
+    Write(#[source] std::io::Error),
+
+    #[error("invalid group name '{0}', must only contain alphanumeric characters, - _ or .")]
+    InvalidName(String),
+
+    #[error("recipient group '@{0}' does not exist")]
+    UnknownGroup(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FP_A: &str = "A423B32B3CE9250155D0155E3B41CF8197074CBC";
+    const FP_B: &str = "BF66D044ACB376DEC8235BFEFC97A01D8E805806";
+
+    fn groups(data: &str) -> Groups {
+        Groups {
+            path: PathBuf::new(),
+            groups: parse(data),
+        }
+    }
+
+    #[test]
+    fn parse_format() {
+        let data = format!("{GROUPS_HEADER}\n{FP_B}\n[Ops]\n{FP_A}\n  0x{FP_B}  \n\n[empty]\n");
+        let groups = groups(&data);
+        assert_eq!(groups.names().collect::<Vec<_>>(), vec!["ops"]);
+        assert_eq!(groups.members("@OPS").unwrap(), &[FP_A, FP_B]);
+        assert_eq!(
+            format(&groups.groups),
+            format!("{GROUPS_HEADER}\n\n[ops]\n{FP_A}\n{FP_B}\n"),
+        );
+    }
+
+    #[test]
+    fn add_remove() {
+        let mut groups = groups("");
+        assert!(groups.add("ops", FP_A).unwrap());
+        assert!(!groups.add("@Ops", &FP_A[24..]).unwrap());
+        assert!(groups.add("dev", FP_A).unwrap());
+        assert!(groups.add("my group", FP_A).is_err());
+        assert_eq!(groups.groups_with(FP_A), vec!["dev", "ops"]);
+        assert!(groups.remove("ops", FP_A));
+        assert!(!groups.remove("ops", FP_A));
+        assert_eq!(groups.names().collect::<Vec<_>>(), vec!["dev"]);
+    }
+
+    #[test]
+    fn expand() {
+        let groups = groups(&format!("[ops]\n{FP_A}\n{FP_B}\n"));
+        assert_eq!(
+            groups
+                .expand(&[FP_B.to_string(), reference("ops")])
+                .unwrap(),
+            vec![FP_B, FP_A],
+        );
+        assert!(groups.expand(&["@dev"]).is_err());
+    }
+}
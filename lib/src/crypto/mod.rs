@@ -6,15 +6,25 @@
 //! GnuPG). The list of supported protocols and backends may be extended in the future.
 
 pub mod backend;
+pub mod groups;
 pub mod proto;
 pub mod recipients;
+pub mod shamir;
 pub mod store;
+pub mod trust;
 pub mod util;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, hash_map::Entry};
 use std::fmt;
 use std::fs;
+use std::io::{Read, Write};
 use std::path::Path;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    mpsc,
+};
+use std::thread;
+use std::time::{Duration, SystemTime};
 
 use anyhow::Result;
 use thiserror::Error;
@@ -94,6 +104,14 @@ impl Key {
         }
     }
 
+    /// Key user IDs.
+    pub fn user_ids(&self) -> &[String] {
+        match self {
+            #[cfg(feature = "_crypto-gpg")]
+            Key::Gpg(key) => &key.user_ids,
+        }
+    }
+
     /// Display string for user.
     pub fn display(&self) -> String {
         match self {
@@ -101,6 +119,14 @@ impl Key {
             Key::Gpg(key) => key.display_user(),
         }
     }
+
+    /// Key metadata.
+    pub fn meta(&self) -> &KeyMeta {
+        match self {
+            #[cfg(feature = "_crypto-gpg")]
+            Key::Gpg(key) => &key.meta,
+        }
+    }
 }
 
 impl fmt::Display for Key {
@@ -115,6 +141,161 @@ impl fmt::Display for Key {
     }
 }
 
+/// Key metadata.
+///
+/// Backends fill in what they know about a key, fields are left empty otherwise.
+#[derive(Clone, Debug, Default)]
+pub struct KeyMeta {
+    /// Key creation time.
+    pub created: Option<SystemTime>,
+
+    /// Key expiry time, `None` if the key does not expire or if unknown.
+    pub expires: Option<SystemTime>,
+
+    /// Whether the key is revoked.
+    pub revoked: bool,
+
+    /// Usable capabilities of the key and its subkeys, `None` if unknown.
+    pub capabilities: Option<Capabilities>,
+
+    /// Public key algorithm, such as `ed25519` or `rsa4096`.
+    pub algorithm: Option<String>,
+
+    /// Key IDs of the primary key and its subkeys, uppercase hexadecimal. Empty if unknown.
+    pub key_ids: Vec<String>,
+}
+
+impl KeyMeta {
+    /// Check whether the key is expired.
+    pub fn is_expired(&self) -> bool {
+        self.expires
+            .is_some_and(|expires| expires <= SystemTime::now())
+    }
+
+    /// Check whether the key expires within the given duration.
+    ///
+    /// Returns `false` if the key is already expired.
+    pub fn expires_within(&self, duration: Duration) -> bool {
+        !self.is_expired()
+            && self
+                .expires
+                .is_some_and(|expires| expires <= SystemTime::now() + duration)
+    }
+
+    /// Get the key status.
+    pub fn status(&self) -> KeyStatus {
+        if self.revoked {
+            KeyStatus::Revoked
+        } else if self.is_expired() {
+            KeyStatus::Expired
+        } else if self.capabilities.is_some_and(|caps| !caps.encrypt) {
+            KeyStatus::NoEncrypt
+        } else {
+            KeyStatus::Usable
+        }
+    }
+}
+
+/// Key capabilities.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Can encrypt.
+    pub encrypt: bool,
+
+    /// Can sign.
+    pub sign: bool,
+
+    /// Can certify other keys.
+    pub certify: bool,
+
+    /// Can authenticate.
+    pub authenticate: bool,
+}
+
+impl fmt::Display for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (has, flag) in [
+            (self.encrypt, 'E'),
+            (self.sign, 'S'),
+            (self.certify, 'C'),
+            (self.authenticate, 'A'),
+        ] {
+            if has {
+                write!(f, "{flag}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Key status, whether a key can be used for encryption.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum KeyStatus {
+    /// Key can be used.
+    Usable,
+
+    /// Key is revoked.
+    Revoked,
+
+    /// Key is expired.
+    Expired,
+
+    /// Key has no usable encryption (sub)key.
+    NoEncrypt,
+}
+
+impl KeyStatus {
+    /// Check whether the key can be used for encryption.
+    pub fn is_usable(self) -> bool {
+        self == KeyStatus::Usable
+    }
+
+    /// Short status name.
+    pub fn name(self) -> &'static str {
+        match self {
+            KeyStatus::Usable => "usable",
+            KeyStatus::Revoked => "revoked",
+            KeyStatus::Expired => "expired",
+            KeyStatus::NoEncrypt => "no-encrypt",
+        }
+    }
+}
+
+impl fmt::Display for KeyStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A newly generated key pair.
+pub struct GeneratedKey {
+    /// The generated key, its public key is in the keychain.
+    pub key: Key,
+
+    /// ASCII armored secret key, if the backend cannot keep it in its keychain.
+    ///
+    /// The caller is responsible for storing it safely, it is not kept anywhere else.
+    pub secret_key: Option<Plaintext>,
+}
+
+/// Ensure all recipient keys can be used for encryption.
+///
+/// Fails on the first revoked, expired or non-encrypting key, rather than failing with an opaque
+/// error deep inside the backend.
+fn check_recipients(recipients: &Recipients) -> Result<(), Err> {
+    match recipients
+        .keys()
+        .iter()
+        .find(|key| !key.meta().status().is_usable())
+    {
+        Some(key) => Err(Err::UnusableRecipient(
+            key.fingerprint(false),
+            key.meta().status(),
+        )),
+        None => Ok(()),
+    }
+}
+
 /// Get crypto context for given proto type at runtime.
 ///
 /// This selects a compatible crypto context at runtime.
@@ -163,6 +344,7 @@ impl Context {
 
 impl IsContext for Context {
     fn encrypt(&mut self, recipients: &Recipients, plaintext: Plaintext) -> Result<Ciphertext> {
+        check_recipients(recipients)?;
         self.context.encrypt(recipients, plaintext)
     }
 
@@ -170,10 +352,28 @@ impl IsContext for Context {
         self.context.decrypt(ciphertext)
     }
 
+    fn encrypt_file_stream(
+        &mut self,
+        recipients: &Recipients,
+        input: &mut dyn Read,
+        path: &Path,
+    ) -> Result<()> {
+        check_recipients(recipients)?;
+        self.context.encrypt_file_stream(recipients, input, path)
+    }
+
+    fn decrypt_file_stream(&mut self, path: &Path, output: &mut dyn Write) -> Result<()> {
+        self.context.decrypt_file_stream(path, output)
+    }
+
     fn can_decrypt(&mut self, ciphertext: Ciphertext) -> Result<bool> {
         self.context.can_decrypt(ciphertext)
     }
 
+    fn recipient_key_ids(&mut self, ciphertext: Ciphertext) -> Result<Vec<String>> {
+        self.context.recipient_key_ids(ciphertext)
+    }
+
     fn keys_public(&mut self) -> Result<Vec<Key>> {
         self.context.keys_public()
     }
@@ -186,10 +386,27 @@ impl IsContext for Context {
         self.context.import_key(key)
     }
 
+    fn read_keys(&mut self, key: &[u8]) -> Result<Vec<Key>> {
+        self.context.read_keys(key)
+    }
+
     fn export_key(&mut self, key: Key) -> Result<Vec<u8>> {
         self.context.export_key(key)
     }
 
+    fn generate_key(
+        &mut self,
+        user_id: &str,
+        expires: Option<Duration>,
+        passphrase: Option<Plaintext>,
+    ) -> Result<GeneratedKey> {
+        self.context.generate_key(user_id, expires, passphrase)
+    }
+
+    fn keeps_secret_keys(&self) -> bool {
+        self.context.keeps_secret_keys()
+    }
+
     fn supports_proto(&self, proto: Proto) -> bool {
         self.context.supports_proto(proto)
     }
@@ -222,6 +439,32 @@ pub trait IsContext {
         self.decrypt(fs::read(path).map_err(Err::ReadFile)?.into())
     }
 
+    /// Encrypt plaintext from a reader and write it to the file.
+    ///
+    /// Meant for large, possibly binary, payloads. Backends may override this to stream data
+    /// without buffering all of it in memory. The default implementation buffers.
+    fn encrypt_file_stream(
+        &mut self,
+        recipients: &Recipients,
+        input: &mut dyn Read,
+        path: &Path,
+    ) -> Result<()> {
+        let mut data = vec![];
+        input.read_to_end(&mut data).map_err(Err::ReadStream)?;
+        self.encrypt_file(recipients, data.into(), path)
+    }
+
+    /// Decrypt ciphertext from file and write the plaintext to the writer.
+    ///
+    /// Counterpart of [`encrypt_file_stream`](Self::encrypt_file_stream). The default
+    /// implementation buffers.
+    fn decrypt_file_stream(&mut self, path: &Path, output: &mut dyn Write) -> Result<()> {
+        let plaintext = self.decrypt_file(path)?;
+        output
+            .write_all(plaintext.unsecure_ref())
+            .map_err(|err| Err::WriteStream(err).into())
+    }
+
     /// Check whether we can decrypt ciphertext.
     fn can_decrypt(&mut self, ciphertext: Ciphertext) -> Result<bool>;
 
@@ -230,6 +473,17 @@ pub trait IsContext {
         self.can_decrypt(fs::read(path).map_err(Err::ReadFile)?.into())
     }
 
+    /// Get key IDs of the recipients ciphertext is encrypted for, without decrypting.
+    ///
+    /// These are read from the public key encrypted session key packets, and are usually IDs of
+    /// encryption subkeys rather than primary key fingerprints.
+    fn recipient_key_ids(&mut self, ciphertext: Ciphertext) -> Result<Vec<String>>;
+
+    /// Get key IDs of the recipients ciphertext from file is encrypted for.
+    fn recipient_key_ids_file(&mut self, path: &Path) -> Result<Vec<String>> {
+        self.recipient_key_ids(fs::read(path).map_err(Err::ReadFile)?.into())
+    }
+
     /// Obtain all public keys from keychain.
     fn keys_public(&mut self) -> Result<Vec<Key>>;
 
@@ -244,6 +498,14 @@ pub trait IsContext {
             .ok_or_else(|| Err::UnknownFingerprint.into())
     }
 
+    /// Obtain a private key from keychain for fingerprint.
+    fn get_private_key(&mut self, fingerprint: &str) -> Result<Key> {
+        self.keys_private()?
+            .into_iter()
+            .find(|key| util::fingerprints_equal(key.fingerprint(false), fingerprint))
+            .ok_or_else(|| Err::UnknownFingerprint.into())
+    }
+
     /// Find public keys from keychain for fingerprints.
     ///
     /// Skips fingerprints no key is found for.
@@ -267,6 +529,14 @@ pub trait IsContext {
         self.import_key(&fs::read(path).map_err(Err::ReadFile)?)
     }
 
+    /// Read the keys in the given bytes, without importing them into the keychain.
+    fn read_keys(&mut self, key: &[u8]) -> Result<Vec<Key>>;
+
+    /// Read the keys in the given file, without importing them into the keychain.
+    fn read_keys_file(&mut self, path: &Path) -> Result<Vec<Key>> {
+        self.read_keys(&fs::read(path).map_err(Err::ReadFile)?)
+    }
+
     /// Export the given key from the keychain as bytes.
     fn export_key(&mut self, key: Key) -> Result<Vec<u8>>;
 
@@ -275,6 +545,25 @@ pub trait IsContext {
         fs::write(path, self.export_key(key)?).map_err(|err| Err::WriteFile(err).into())
     }
 
+    /// Generate a new key pair, and add it to the keychain.
+    ///
+    /// Generates an Ed25519 primary key for signing and certification, with a Cv25519 subkey for
+    /// encryption. Both expire after `expires`, or never if `None`.
+    ///
+    /// Backends that do not keep secret keys protect the returned secret key with `passphrase`,
+    /// which is then required. Other backends prompt for a passphrase themselves and ignore it.
+    fn generate_key(
+        &mut self,
+        user_id: &str,
+        expires: Option<Duration>,
+        passphrase: Option<Plaintext>,
+    ) -> Result<GeneratedKey>;
+
+    /// Check whether this context keeps secret keys of generated key pairs in its keychain.
+    ///
+    /// If not, the secret key is returned by [`generate_key`](Self::generate_key) instead.
+    fn keeps_secret_keys(&self) -> bool;
+
     /// Check whether this context supports the given protocol.
     fn supports_proto(&self, proto: Proto) -> bool;
 }
@@ -301,13 +590,59 @@ impl ContextPool {
     /// This will initialize the context if no context is loaded for the given proto yet. This
     /// may error..
     pub fn get_mut<'a>(&'a mut self, config: &'a Config) -> Result<&'a mut Context> {
-        Ok(self
-            .contexts
-            .entry(config.proto)
-            .or_insert(context(config)?))
+        Ok(match self.contexts.entry(config.proto) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(context(config)?),
+        })
     }
 }
 
+/// Process items in parallel using a pool of workers.
+///
+/// Spawns up to `jobs` worker threads, each with its own `ContextPool`, calling `f` for each item.
+/// Results are passed to `handle` on the calling thread as soon as they're available, in
+/// completion order. Returns when all items are processed.
+///
+/// Contexts are not shared between threads. If creating a context for a worker fails, the error
+/// is reported as result for the items that worker picks up.
+pub fn parallel<'a, T, R, F, H>(config: &Config, jobs: usize, items: &'a [T], f: F, mut handle: H)
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&mut Context, &T) -> Result<R> + Sync,
+    H: FnMut(&'a T, Result<R>),
+{
+    let jobs = jobs.clamp(1, items.len().max(1));
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let (tx, next, f) = (tx.clone(), &next, &f);
+            scope.spawn(move || {
+                let mut pool = ContextPool::empty();
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(item) = items.get(i) else {
+                        break;
+                    };
+                    let result = pool.get_mut(config).and_then(|context| f(context, item));
+
+                    // Stop if receiver is gone
+                    if tx.send((i, result)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(tx);
+
+        for (i, result) in rx {
+            handle(&items[i], result);
+        }
+    });
+}
+
 /// Crypto error.
 #[derive(Debug, Error)]
 pub enum Err {
@@ -323,11 +658,68 @@ pub enum Err {
     #[error("failed to read from file")]
     ReadFile(#[source] std::io::Error),
 
+    #[error("failed to read from input stream")]
+    ReadStream(#[source] std::io::Error),
+
+    #[error("failed to write to output stream")]
+    WriteStream(#[source] std::io::Error),
+
     #[error("fingerprint does not match public key in keychain")]
     UnknownFingerprint,
+
+    #[error("recipient key {0} cannot be used for encryption, key status is {1}")]
+    UnusableRecipient(String, KeyStatus),
 }
 
 /// Prelude for common crypto traits.
 pub mod prelude {
     pub use super::{IsContext, store::StoreRecipients};
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_meta_status() {
+        let day = Duration::from_secs(24 * 60 * 60);
+        let now = SystemTime::now();
+
+        let meta = KeyMeta::default();
+        assert_eq!(meta.status(), KeyStatus::Usable);
+        assert!(!meta.expires_within(day));
+
+        let meta = KeyMeta {
+            expires: Some(now + day),
+            ..Default::default()
+        };
+        assert_eq!(meta.status(), KeyStatus::Usable);
+        assert!(meta.expires_within(2 * day));
+        assert!(!meta.expires_within(day / 2));
+
+        let meta = KeyMeta {
+            expires: Some(now - day),
+            ..Default::default()
+        };
+        assert_eq!(meta.status(), KeyStatus::Expired);
+        assert!(!meta.expires_within(2 * day));
+
+        let meta = KeyMeta {
+            revoked: true,
+            expires: Some(now - day),
+            ..Default::default()
+        };
+        assert_eq!(meta.status(), KeyStatus::Revoked);
+
+        let meta = KeyMeta {
+            capabilities: Some(Capabilities {
+                sign: true,
+                certify: true,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert_eq!(meta.status(), KeyStatus::NoEncrypt);
+        assert_eq!(meta.capabilities.unwrap().to_string(), "SC");
+    }
+}
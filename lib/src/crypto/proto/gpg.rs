@@ -11,6 +11,9 @@ pub struct Key {
 
     /// Displayable user ID strings.
     pub user_ids: Vec<String>,
+
+    /// Key metadata.
+    pub meta: crate::crypto::KeyMeta,
 }
 
 impl Key {
@@ -2,7 +2,8 @@
 
 use anyhow::Result;
 
-use super::Key;
+use super::{Context, Key};
+use crate::Plaintext;
 use crate::crypto::{self, prelude::*, util};
 
 /// A list of recipients.
@@ -75,6 +76,38 @@ pub fn contains_own_secret_key(recipients: &Recipients) -> Result<bool> {
         .any(|k| secrets.has_fingerprint(&k.fingerprint(false))))
 }
 
+/// Check whether the given key can be used as recipient.
+///
+/// Encrypts a test message to just this key, which fails if the key is expired, revoked or has no
+/// key capable of encryption.
+pub fn is_usable(context: &mut Context, key: &Key) -> bool {
+    let recipients = Recipients::from(vec![key.clone()]);
+    context
+        .encrypt(&recipients, Plaintext::from("prs recipient test"))
+        .is_ok()
+}
+
+/// Get the key IDs ciphertext for the given key is encrypted for.
+///
+/// See [`recipients_key_ids`].
+pub fn key_ids(context: &mut Context, key: &Key) -> Result<Vec<String>> {
+    recipients_key_ids(context, &Recipients::from(vec![key.clone()]))
+}
+
+/// Get the key IDs ciphertext for the given recipients is encrypted for.
+///
+/// These are the IDs of the encryption subkeys currently used for the keys, as listed by
+/// [`IsContext::recipient_key_ids`](crypto::IsContext::recipient_key_ids). Encrypts a test
+/// message to find them, which fails if any key is not usable. Key IDs are sorted and
+/// deduplicated.
+pub fn recipients_key_ids(context: &mut Context, recipients: &Recipients) -> Result<Vec<String>> {
+    let ciphertext = context.encrypt(recipients, Plaintext::from("prs recipient test"))?;
+    let mut key_ids = context.recipient_key_ids(ciphertext)?;
+    key_ids.sort_unstable();
+    key_ids.dedup();
+    Ok(key_ids)
+}
+
 /// Check if given keys all use same proto.
 ///
 /// Succeeds if no key is given.
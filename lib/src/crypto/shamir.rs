@@ -0,0 +1,458 @@
+//! Shamir's secret sharing.
+//!
+//! Splits a secret into shares, of which any threshold number of shares reconstructs the secret.
+//! Fewer shares reveal nothing about it. Each byte of the secret is split separately over
+//! GF(2^8), the share index is the x coordinate. A SHA-256 checksum is appended to the secret
+//! before splitting, to verify the reconstructed secret without revealing a hash of it in any
+//! single share.
+//!
+//! Shares are formatted as plaintext secret, with the share data on the first line followed by
+//! properties describing the split. In a store, each share is kept next to its secret as
+//! `<secret>.share-N.gpg`, encrypted for a single recipient.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+use crate::{Plaintext, store::SECRET_SUFFIX};
+
+/// Infix between the secret name and share index in share file names.
+const SHARE_INFIX: &str = ".share-";
+
+/// Property holding the share index and number of shares, such as `2/5`.
+const PROPERTY_SHARE: &str = "share";
+
+/// Property holding the number of shares needed to reconstruct.
+const PROPERTY_THRESHOLD: &str = "threshold";
+
+/// Property holding the split identifier.
+const PROPERTY_ID: &str = "split-id";
+
+/// Number of random bytes in a split identifier.
+const ID_BYTES: usize = 8;
+
+/// Number of checksum bytes appended to the secret before splitting.
+const CHECKSUM_BYTES: usize = 32;
+
+/// A share of a secret.
+pub struct Share {
+    /// Share index, the x coordinate. Never zero.
+    pub index: u8,
+
+    /// Total number of shares in the split.
+    pub shares: u8,
+
+    /// Number of shares needed to reconstruct the secret.
+    pub threshold: u8,
+
+    /// Identifier of the split, the same for all its shares.
+    pub id: String,
+
+    /// Share data.
+    pub data: Plaintext,
+}
+
+impl Share {
+    /// Format this share as plaintext.
+    pub fn to_plaintext(&self) -> Plaintext {
+        let mut text = Zeroizing::new(String::new());
+        for byte in self.data.unsecure_ref() {
+            write!(text, "{byte:02x}").unwrap();
+        }
+        write!(
+            text,
+            "\n{PROPERTY_SHARE}: {}/{}\n{PROPERTY_THRESHOLD}: {}\n{PROPERTY_ID}: {}\n",
+            self.index, self.shares, self.threshold, self.id,
+        )
+        .unwrap();
+        text.as_str().into()
+    }
+
+    /// Parse a share from plaintext, as formatted by [`to_plaintext`](Self::to_plaintext).
+    pub fn from_plaintext(plaintext: &Plaintext) -> Result<Self> {
+        let property = |name| -> Result<String> {
+            Ok(plaintext
+                .property(name)
+                .map_err(|_| Err::Malformed)?
+                .unsecure_to_str()
+                .map_err(|_| Err::Malformed)?
+                .to_string())
+        };
+
+        let share = property(PROPERTY_SHARE)?;
+        let (index, shares) = share.split_once('/').ok_or(Err::Malformed)?;
+        let index = index.trim().parse().map_err(|_| Err::Malformed)?;
+        let shares = shares.trim().parse().map_err(|_| Err::Malformed)?;
+        let threshold = property(PROPERTY_THRESHOLD)?
+            .parse()
+            .map_err(|_| Err::Malformed)?;
+        if index == 0 || threshold == 0 || threshold > shares {
+            return Err(Err::Malformed.into());
+        }
+
+        let first_line = plaintext.first_line()?;
+        let hex = first_line.unsecure_to_str().map_err(|_| Err::Malformed)?;
+        if hex.is_empty() || hex.len() % 2 != 0 {
+            return Err(Err::Malformed.into());
+        }
+        let data = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+            .collect::<Option<Vec<u8>>>()
+            .ok_or(Err::Malformed)?;
+
+        Ok(Self {
+            index,
+            shares,
+            threshold,
+            id: property(PROPERTY_ID)?,
+            data: data.into(),
+        })
+    }
+}
+
+/// Get the path of a share of the secret at the given path.
+pub fn share_path(secret: &Path, index: u8) -> PathBuf {
+    let name = secret.file_name().unwrap_or_default().to_string_lossy();
+    let name = name.strip_suffix(SECRET_SUFFIX).unwrap_or(&name);
+    secret.with_file_name(format!("{name}{SHARE_INFIX}{index}{SECRET_SUFFIX}"))
+}
+
+/// Find the share files of the secret at the given path, ordered by index.
+pub fn find_shares(secret: &Path) -> Vec<(u8, PathBuf)> {
+    let Some(dir) = secret.parent() else {
+        return vec![];
+    };
+    let mut shares: Vec<(u8, PathBuf)> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let (_, index) = share_index(&path)?;
+            (share_path(secret, index) == path).then_some((index, path))
+        })
+        .collect();
+    shares.sort_unstable_by_key(|(index, _)| *index);
+    shares
+}
+
+/// Check whether the given path is a share file of a split secret.
+///
+/// A share file is named after its secret, which must exist next to it. Other files named like a
+/// share are ordinary secrets. Shares are encrypted for a single recipient on purpose, and must
+/// never be re-encrypted for the store recipients.
+pub fn is_share(path: &Path) -> bool {
+    share_index(path).is_some_and(|(secret, _)| secret.is_file())
+}
+
+/// Get the secret path and share index from a share file path.
+fn share_index(path: &Path) -> Option<(PathBuf, u8)> {
+    let name = path.file_name()?.to_str()?.strip_suffix(SECRET_SUFFIX)?;
+    let (secret, index) = name.rsplit_once(SHARE_INFIX)?;
+    let index = index.parse().ok().filter(|index| *index > 0)?;
+    Some((
+        path.with_file_name(format!("{secret}{SECRET_SUFFIX}")),
+        index,
+    ))
+}
+
+/// Split a secret into shares.
+///
+/// Any `threshold` of the `shares` shares reconstruct the secret. The threshold must be at least
+/// 2, and at most the number of shares.
+pub fn split(secret: &Plaintext, shares: u8, threshold: u8) -> Result<Vec<Share>> {
+    if threshold < 2 || threshold > shares {
+        return Err(Err::Threshold(threshold, shares).into());
+    }
+    if secret.unsecure_ref().is_empty() {
+        return Err(Err::Empty.into());
+    }
+    let mut secret = Zeroizing::new(secret.unsecure_ref().to_vec());
+    let checksum = Sha256::digest(&*secret);
+    secret.extend_from_slice(&checksum);
+
+    let mut rng = rand::rng();
+    let mut id = [0; ID_BYTES];
+    rng.fill_bytes(&mut id);
+    let id: String = id.iter().map(|b| format!("{b:02x}")).collect();
+
+    // Random polynomial for each secret byte, the constant term being the byte itself
+    let mut coefficients = Zeroizing::new(vec![0; threshold as usize]);
+    let mut data: Vec<Zeroizing<Vec<u8>>> = (0..shares)
+        .map(|_| Zeroizing::new(Vec::with_capacity(secret.len())))
+        .collect();
+    for byte in secret.iter() {
+        coefficients[0] = *byte;
+        rng.fill_bytes(&mut coefficients[1..]);
+        for (x, data) in (1..=shares).zip(data.iter_mut()) {
+            data.push(evaluate(&coefficients, x));
+        }
+    }
+
+    Ok((1..=shares)
+        .zip(data)
+        .map(|(index, data)| Share {
+            index,
+            shares,
+            threshold,
+            id: id.clone(),
+            data: data.to_vec().into(),
+        })
+        .collect())
+}
+
+/// Reconstruct a secret from its shares.
+///
+/// All shares must be from the same split, and at least its threshold number of distinct shares
+/// must be given. Extra shares are ignored. Fails if the reconstructed secret does not match its
+/// checksum, which happens if a share is corrupted.
+pub fn combine(shares: &[Share]) -> Result<Plaintext> {
+    let first = shares.first().ok_or(Err::NoShares)?;
+    if shares.iter().any(|share| {
+        share.id != first.id
+            || share.threshold != first.threshold
+            || share.data.unsecure_ref().len() != first.data.unsecure_ref().len()
+    }) {
+        return Err(Err::Mismatch.into());
+    }
+
+    // Take threshold number of distinct shares
+    let mut used: Vec<&Share> = Vec::with_capacity(first.threshold as usize);
+    for share in shares {
+        if used.len() < first.threshold as usize && used.iter().all(|s| s.index != share.index) {
+            used.push(share);
+        }
+    }
+    if used.len() < first.threshold as usize {
+        return Err(Err::NotEnough(used.len(), first.threshold).into());
+    }
+
+    // Interpolate polynomial at x = 0 for each byte
+    let xs: Vec<u8> = used.iter().map(|share| share.index).collect();
+    let weights: Vec<u8> = xs
+        .iter()
+        .map(|&xj| {
+            xs.iter()
+                .filter(|&&xm| xm != xj)
+                .fold(1, |weight, &xm| mul(weight, div(xm, xm ^ xj)))
+        })
+        .collect();
+    let secret: Zeroizing<Vec<u8>> = Zeroizing::new(
+        (0..first.data.unsecure_ref().len())
+            .map(|i| {
+                used.iter().zip(&weights).fold(0, |byte, (share, &weight)| {
+                    byte ^ mul(share.data.unsecure_ref()[i], weight)
+                })
+            })
+            .collect(),
+    );
+
+    // Verify and strip the checksum
+    let (secret, checksum) = secret
+        .len()
+        .checked_sub(CHECKSUM_BYTES)
+        .filter(|len| *len > 0)
+        .map(|len| secret.split_at(len))
+        .ok_or(Err::Checksum)?;
+    if Sha256::digest(secret).as_slice() != checksum {
+        return Err(Err::Checksum.into());
+    }
+
+    Ok(secret.to_vec().into())
+}
+
+/// Evaluate the polynomial with the given coefficients at `x`, in GF(2^8).
+fn evaluate(coefficients: &[u8], x: u8) -> u8 {
+    coefficients
+        .iter()
+        .rev()
+        .fold(0, |result, coefficient| mul(result, x) ^ coefficient)
+}
+
+/// Multiply in GF(2^8), with the AES reduction polynomial.
+fn mul(mut a: u8, mut b: u8) -> u8 {
+    let mut result = 0;
+    for _ in 0..8 {
+        result ^= a & 0u8.wrapping_sub(b & 1);
+        let carry = 0u8.wrapping_sub(a >> 7);
+        a = (a << 1) ^ (0x1b & carry);
+        b >>= 1;
+    }
+    result
+}
+
+/// Divide in GF(2^8).
+///
+/// # Panics
+///
+/// Panics if dividing by zero.
+fn div(a: u8, b: u8) -> u8 {
+    assert_ne!(b, 0, "division by zero in GF(2^8)");
+
+    // Inverse of b is b^254
+    let mut inverse = 1;
+    let mut power = b;
+    for bit in 0..8 {
+        if 254 & (1 << bit) != 0 {
+            inverse = mul(inverse, power);
+        }
+        power = mul(power, power);
+    }
+    mul(a, inverse)
+}
+
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error(
+        "invalid threshold {0} for {1} shares, must be at least 2 and at most the number of shares"
+    )]
+    Threshold(u8, u8),
+
+    #[error("cannot split empty secret")]
+    Empty,
+
+    #[error("no shares to reconstruct secret from")]
+    NoShares,
+
+    #[error("not enough shares to reconstruct secret, got {0} of {1}")]
+    NotEnough(usize, u8),
+
+    #[error("shares are not from the same split")]
+    Mismatch,
+
+    #[error("malformed secret share")]
+    Malformed,
+
+    #[error("reconstructed secret does not match its checksum, a share may be corrupted")]
+    Checksum,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gf256() {
+        // Known values from the AES field
+        assert_eq!(mul(0x57, 0x83), 0xc1);
+        assert_eq!(mul(0x57, 0x13), 0xfe);
+        for a in 1..=255 {
+            assert_eq!(mul(a, div(1, a)), 1);
+        }
+    }
+
+    #[test]
+    fn test_split_combine() {
+        let secret = Plaintext::from("correct horse battery staple\nuser: root");
+        let shares = split(&secret, 5, 3).unwrap();
+        assert_eq!(shares.len(), 5);
+
+        // Any 3 shares reconstruct the secret
+        for (a, b, c) in [(0, 1, 2), (4, 2, 0), (1, 3, 4)] {
+            let subset =
+                [a, b, c].map(|i| Share::from_plaintext(&shares[i].to_plaintext()).unwrap());
+            assert_eq!(
+                combine(&subset).unwrap().unsecure_ref(),
+                secret.unsecure_ref()
+            );
+        }
+
+        // A corrupted share fails the checksum
+        let mut corrupted =
+            [0, 1, 2].map(|i| Share::from_plaintext(&shares[i].to_plaintext()).unwrap());
+        let mut data = corrupted[1].data.unsecure_ref().to_vec();
+        data[0] ^= 1;
+        corrupted[1].data = data.into();
+        assert!(combine(&corrupted).is_err());
+
+        // Two shares, or a duplicate share, are not enough
+        let subset = [0, 1, 1].map(|i| Share::from_plaintext(&shares[i].to_plaintext()).unwrap());
+        assert!(combine(&subset).is_err());
+
+        // Shares from different splits don't combine
+        let other = split(&secret, 5, 3).unwrap();
+        let mixed = [
+            Share::from_plaintext(&shares[0].to_plaintext()).unwrap(),
+            Share::from_plaintext(&shares[1].to_plaintext()).unwrap(),
+            Share::from_plaintext(&other[2].to_plaintext()).unwrap(),
+        ];
+        assert!(combine(&mixed).is_err());
+
+        assert!(split(&secret, 5, 1).is_err());
+        assert!(split(&secret, 3, 4).is_err());
+    }
+
+    #[test]
+    fn test_share_path() {
+        let root = std::env::temp_dir().join(format!("prs-test-{}-shares", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        for name in [
+            "db.gpg",
+            "db.share-3.gpg",
+            "db.share-0.gpg",
+            "router.share-1.gpg",
+        ] {
+            fs::write(root.join(name), "").unwrap();
+        }
+
+        let path = share_path(&root.join("db.gpg"), 3);
+        assert_eq!(path, root.join("db.share-3.gpg"));
+        assert!(is_share(&path));
+        assert!(!is_share(&root.join("db.gpg")));
+        assert!(!is_share(&root.join("db.share-0.gpg")));
+        assert!(!is_share(&root.join("db.share-x.gpg")));
+
+        // Ordinary secret named like a share, without a secret next to it
+        assert!(!is_share(&root.join("router.share-1.gpg")));
+        let mut names: Vec<String> = crate::Store::open(root.to_str().unwrap())
+            .unwrap()
+            .secrets(None)
+            .into_iter()
+            .map(|secret| secret.name)
+            .collect();
+        names.sort();
+        assert_eq!(names, ["db", "db.share-0", "router.share-1"]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_share_plaintext() {
+        let share = Share::from_plaintext(&Plaintext::from(
+            "00ff10\nshare: 2/5\nthreshold: 3\nsplit-id: abcd\n",
+        ))
+        .unwrap();
+        assert_eq!(share.index, 2);
+        assert_eq!(share.shares, 5);
+        assert_eq!(share.threshold, 3);
+        assert_eq!(share.id, "abcd");
+        assert_eq!(share.data.unsecure_ref(), &[0x00, 0xff, 0x10]);
+
+        assert!(
+            Share::from_plaintext(&Plaintext::from(
+                "00ff1\nshare: 2/5\nthreshold: 3\nsplit-id: a"
+            ))
+            .is_err()
+        );
+        assert!(
+            Share::from_plaintext(&Plaintext::from(
+                "00ff10\nshare: 0/5\nthreshold: 3\nsplit-id: a"
+            ))
+            .is_err()
+        );
+        assert!(
+            Share::from_plaintext(&Plaintext::from(
+                "00ff10\nshare: 2/5\nthreshold: 6\nsplit-id: a"
+            ))
+            .is_err()
+        );
+        assert!(Share::from_plaintext(&Plaintext::from("not a share")).is_err());
+    }
+}
@@ -1,16 +1,25 @@
 //! Helpers to use recipients with password store.
 
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use thiserror::Error;
-
-use super::{Config, ContextPool, Key, Proto, prelude::*, recipients::Recipients, util};
-use crate::Store;
+use walkdir::WalkDir;
+
+use super::{
+    Config, ContextPool, Key, Proto,
+    groups::{self, Groups},
+    prelude::*,
+    recipients::Recipients,
+    trust::{self, Trust},
+    util,
+};
+use crate::{Secret, Store, sync::Commit};
 
 /// Password store GPG IDs file.
-const STORE_GPG_IDS_FILE: &str = ".gpg-id";
+pub const STORE_GPG_IDS_FILE: &str = ".gpg-id";
 
 /// Password store public key directory.
 const STORE_PUB_KEY_DIR: &str = ".public-keys/";
@@ -25,9 +34,52 @@ pub fn store_public_keys_dir(store: &Store) -> PathBuf {
     store.root.join(STORE_PUB_KEY_DIR)
 }
 
-/// Read GPG fingerprints from store.
-pub fn store_read_gpg_fingerprints(store: &Store) -> Result<Vec<String>> {
-    let path = store_gpg_ids_file(store);
+/// Get the GPG IDs file that applies to the given secret path in a store.
+///
+/// Like `pass`, this is the `.gpg-id` file in the closest parent directory. Falls back to the
+/// file in the store root.
+pub fn store_gpg_ids_file_for(store: &Store, path: &Path) -> PathBuf {
+    path.ancestors()
+        .skip(1)
+        .take_while(|dir| dir.starts_with(&store.root))
+        .map(|dir| dir.join(STORE_GPG_IDS_FILE))
+        .find(|file| file.is_file())
+        .unwrap_or_else(|| store_gpg_ids_file(store))
+}
+
+/// Find all GPG IDs files in a store, the root file first.
+///
+/// Hidden directories are skipped.
+pub fn store_gpg_ids_files(store: &Store) -> Vec<PathBuf> {
+    let root = store_gpg_ids_file(store);
+    let mut nested: Vec<PathBuf> = WalkDir::new(&store.root)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|e| {
+            !e.file_type().is_dir()
+                || e.file_name()
+                    .to_str()
+                    .is_some_and(|name| !name.starts_with('.') && name != "lost+found")
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.depth() > 1 && e.file_type().is_file())
+        .filter(|e| e.file_name() == STORE_GPG_IDS_FILE)
+        .map(|e| e.into_path())
+        .collect();
+    nested.sort();
+
+    root.is_file()
+        .then_some(root)
+        .into_iter()
+        .chain(nested)
+        .collect()
+}
+
+/// Read GPG IDs file entries from the given file.
+///
+/// Entries are fingerprints or recipient group references, see [`groups`]. Returns no entries if
+/// the file does not exist.
+pub fn store_read_gpg_ids(path: &Path) -> Result<Vec<String>> {
     if path.is_file() {
         read_fingerprints(path)
     } else {
@@ -35,6 +87,145 @@ pub fn store_read_gpg_fingerprints(store: &Store) -> Result<Vec<String>> {
     }
 }
 
+/// Read GPG fingerprints from store.
+///
+/// Recipient groups are expanded into their members.
+pub fn store_read_gpg_fingerprints(store: &Store) -> Result<Vec<String>> {
+    Groups::load(store)?.expand(&store_read_gpg_ids(&store_gpg_ids_file(store))?)
+}
+
+/// Read GPG fingerprints from all GPG IDs files and recipient groups in a store.
+pub fn store_read_all_gpg_fingerprints(store: &Store) -> Result<Vec<String>> {
+    let groups = Groups::load(store)?;
+    let mut entries = Vec::new();
+    for path in store_gpg_ids_files(store) {
+        entries.extend(store_read_gpg_ids(&path)?);
+    }
+    entries.extend(groups.names().map(groups::reference));
+    groups.expand(&entries)
+}
+
+/// Find all secrets in a store that are encrypted for the given recipient group.
+///
+/// These are the secrets that apply to a GPG IDs file referencing the group.
+pub fn store_secrets_with_group(store: &Store, name: &str) -> Result<Vec<Secret>> {
+    let reference = groups::reference(name);
+    let mut files: HashMap<PathBuf, bool> = HashMap::new();
+    let mut secrets = Vec::new();
+    for secret in store.secret_iter() {
+        let path = store_gpg_ids_file_for(store, &secret.path);
+        let has_group = match files.get(&path) {
+            Some(has_group) => *has_group,
+            None => {
+                let has_group = store_read_gpg_ids(&path)?.contains(&reference);
+                files.insert(path, has_group);
+                has_group
+            }
+        };
+        if has_group {
+            secrets.push(secret);
+        }
+    }
+    Ok(secrets)
+}
+
+/// Find all GPG IDs files in a store that reference the given recipient group.
+pub fn store_gpg_ids_files_with_group(store: &Store, name: &str) -> Result<Vec<PathBuf>> {
+    let reference = groups::reference(name);
+    let mut files = Vec::new();
+    for path in store_gpg_ids_files(store) {
+        if store_read_gpg_ids(&path)?.contains(&reference) {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+/// Add a recipient group reference to the GPG IDs file in the store root.
+///
+/// The group must exist. Returns `false` if the group was already referenced.
+pub fn store_add_group_reference(store: &Store, name: &str) -> Result<bool> {
+    let groups = Groups::load(store)?;
+    if groups.members(name).is_none() {
+        return Err(groups::Err::UnknownGroup(groups::normalize(name)).into());
+    }
+
+    let reference = groups::reference(name);
+    let mut entries = store_read_gpg_ids(&store_gpg_ids_file(store))?;
+    if entries.contains(&reference) {
+        return Ok(false);
+    }
+    entries.push(reference);
+    store_write_gpg_fingerprints(store, &entries)?;
+    Ok(true)
+}
+
+/// Remove a recipient group reference from the GPG IDs file in the store root.
+///
+/// Returns `false` if the group was not referenced.
+pub fn store_remove_group_reference(store: &Store, name: &str) -> Result<bool> {
+    let reference = groups::reference(name);
+    let mut entries = store_read_gpg_ids(&store_gpg_ids_file(store))?;
+    let len = entries.len();
+    entries.retain(|entry| entry != &reference);
+    if entries.len() == len {
+        return Ok(false);
+    }
+    store_write_gpg_fingerprints(store, &entries)?;
+    Ok(true)
+}
+
+/// Replace a recipient fingerprint with another one in a store.
+///
+/// Replaces the fingerprint in all GPG IDs files, including nested ones, and in all recipient
+/// groups. The new fingerprint is not added twice if it already was a recipient. Returns the
+/// number of GPG IDs files and groups that changed.
+pub fn store_replace_fingerprint(store: &Store, from: &str, to: &str) -> Result<usize> {
+    let to = util::normalize_fingerprint(to);
+    let mut changed = 0;
+
+    for path in store_gpg_ids_files(store) {
+        let entries = store_read_gpg_ids(&path)?;
+        if !entries
+            .iter()
+            .any(|entry| util::fingerprints_equal(entry, from))
+        {
+            continue;
+        }
+
+        let mut replaced: Vec<String> = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let entry = if util::fingerprints_equal(&entry, from) {
+                to.clone()
+            } else {
+                entry
+            };
+            if !replaced.contains(&entry) {
+                replaced.push(entry);
+            }
+        }
+        write_fingerprints(&path, &replaced)?;
+        changed += 1;
+    }
+
+    let mut groups = Groups::load(store)?;
+    let names: Vec<String> = groups
+        .groups_with(from)
+        .into_iter()
+        .map(|name| name.to_string())
+        .collect();
+    for name in &names {
+        groups.add(name, &to)?;
+        groups.remove(name, from);
+    }
+    if !names.is_empty() {
+        groups.save()?;
+        changed += names.len();
+    }
+
+    Ok(changed)
+}
+
 /// Write GPG fingerprints to a store.
 ///
 /// Overwrites any existing file.
@@ -47,12 +238,16 @@ pub fn store_write_gpg_fingerprints<S: AsRef<str>>(
 
 /// Read fingerprints from the given file.
 ///
-/// Normalizes each fingerprint, see [`normalize_fingerprint`].
+/// Normalizes each fingerprint, see [`normalize_fingerprint`]. Recipient group references are
+/// normalized as well.
 fn read_fingerprints<P: AsRef<Path>>(path: P) -> Result<Vec<String>> {
     Ok(fs::read_to_string(path)
         .map_err(Err::ReadFile)?
         .lines()
-        .map(util::normalize_fingerprint)
+        .map(|line| match groups::reference_name(line.trim()) {
+            Some(name) => groups::reference(name),
+            None => util::normalize_fingerprint(line),
+        })
         .filter(|fp| !fp.is_empty())
         .collect())
 }
@@ -83,18 +278,38 @@ pub fn store_load_keys(store: &Store) -> Result<Vec<Key>> {
     // Load GPG keys
     // TODO: do not crash here if GPG ids file is not found!
     let fingerprints = store_read_gpg_fingerprints(store)?;
-
-    if !fingerprints.is_empty() {
-        let mut context = super::context(&crate::CONFIG)?;
-        let fingerprints: Vec<_> = fingerprints.iter().map(|fp| fp.as_str()).collect();
-        keys.extend(context.find_public_keys(&fingerprints)?);
-    }
+    keys.extend(load_gpg_keys(&fingerprints)?);
 
     // NEWPROTO: if a new proto is added, keys for a store should be loaded here
 
     Ok(keys)
 }
 
+/// Load the keys that apply to the given secret path in a store.
+///
+/// See [`store_gpg_ids_file_for`].
+pub fn store_load_keys_for(store: &Store, path: &Path) -> Result<Vec<Key>> {
+    let entries = store_read_gpg_ids(&store_gpg_ids_file_for(store, path))?;
+    load_gpg_keys(&Groups::load(store)?.expand(&entries)?)
+}
+
+/// Load the keys of all GPG IDs files and recipient groups in a store.
+pub fn store_load_all_keys(store: &Store) -> Result<Vec<Key>> {
+    load_gpg_keys(&store_read_all_gpg_fingerprints(store)?)
+}
+
+/// Load GPG keys for the given fingerprints from the keychain.
+///
+/// Fingerprints no key is found for are skipped.
+fn load_gpg_keys(fingerprints: &[String]) -> Result<Vec<Key>> {
+    if fingerprints.is_empty() {
+        return Ok(vec![]);
+    }
+    let mut context = super::context(&crate::CONFIG)?;
+    let fingerprints: Vec<_> = fingerprints.iter().map(|fp| fp.as_str()).collect();
+    context.find_public_keys(&fingerprints)
+}
+
 /// Load the recipients for the given store.
 ///
 /// This will try to load the recipient keys for all configured protocols, and errors if it fails.
@@ -102,20 +317,41 @@ pub fn store_load_recipients(store: &Store) -> Result<Recipients> {
     Ok(Recipients::from(store_load_keys(store)?))
 }
 
+/// Load the recipients that apply to the given secret path in a store.
+///
+/// See [`store_gpg_ids_file_for`].
+pub fn store_load_recipients_for(store: &Store, path: &Path) -> Result<Recipients> {
+    Ok(Recipients::from(store_load_keys_for(store, path)?))
+}
+
 /// Save the keys for the given store.
 ///
-/// This overwrites any existing recipient keys.
+/// This overwrites any existing recipient keys. Recipient group references are kept, keys that
+/// are a member of a referenced group are not written separately.
 pub fn store_save_keys(store: &Store, keys: &[Key]) -> Result<()> {
-    // Save GPG keys
-    let gpg_fingerprints: Vec<_> = keys
-        .iter()
-        .filter(|key| key.proto() == Proto::Gpg)
-        .map(|key| key.fingerprint(false))
+    // Save GPG keys, keep group references
+    let groups = Groups::load(store)?;
+    let mut entries: Vec<String> = store_read_gpg_ids(&store_gpg_ids_file(store))?
+        .into_iter()
+        .filter(|entry| groups::reference_name(entry).is_some())
         .collect();
-    store_write_gpg_fingerprints(store, &gpg_fingerprints)?;
-
-    // Sync public keys for all proto's
-    store_sync_public_key_files(store, keys)?;
+    let grouped = groups.expand(&entries)?;
+    entries.extend(
+        keys.iter()
+            .filter(|key| key.proto() == Proto::Gpg)
+            .map(|key| key.fingerprint(false))
+            .filter(|fp| !grouped.iter().any(|g| util::fingerprints_equal(fp, g))),
+    );
+    store_write_gpg_fingerprints(store, &entries)?;
+
+    // Sync public keys for all proto's, including keys of nested GPG IDs files and groups
+    let mut keys = keys.to_vec();
+    for key in store_load_all_keys(store)? {
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+    store_sync_public_key_files(store, &keys)?;
 
     // TODO: import missing keys to system?
 
@@ -155,20 +391,16 @@ pub fn store_sync_public_key_files(store: &Store, keys: &[Key]) -> Result<()> {
         })
         .collect();
 
-    // List finger prints in .gpg-id file
+    // List finger prints in .gpg-id files and recipient groups
     let store_gpg_fingerprints =
-        store_read_gpg_fingerprints(store).context("failed to read .gpg-id file")?;
+        store_read_all_gpg_fingerprints(store).context("failed to read .gpg-id file")?;
 
     // Remove unused keys
-    for (path, _) in files.iter().filter(|(_, fp)| {
-        // Don't delete if key is in keychain
-        if !util::keys_contain_fingerprint(keys, fp) {
-            return false;
-        }
-
-        // Don't delete if key is in store fingerprints file
-        !store_gpg_fingerprints.contains(fp)
-    }) {
+    let recipients: Vec<String> = keys.iter().map(|key| key.fingerprint(false)).collect();
+    for (path, _) in files
+        .iter()
+        .filter(|(_, fp)| is_obsolete_key_file(fp, &recipients, &store_gpg_fingerprints))
+    {
         fs::remove_file(path).map_err(Err::SyncKeyFiles)?;
     }
 
@@ -194,10 +426,29 @@ pub fn store_sync_public_key_files(store: &Store, keys: &[Key]) -> Result<()> {
     Ok(())
 }
 
+/// Check whether the public key file with the given fingerprint is obsolete.
+///
+/// A key file is obsolete if its key is neither a selected recipient, nor listed in any `.gpg-id`
+/// file or recipient group of the store. Key files of recipients must be kept, whether or not
+/// their key is in the keychain, so others can import them.
+fn is_obsolete_key_file(fp: &str, recipients: &[String], store_fingerprints: &[String]) -> bool {
+    !recipients
+        .iter()
+        .chain(store_fingerprints)
+        .any(|other| util::fingerprints_equal(fp, other))
+}
+
 /// Import keys from store that are missing in the keychain.
+///
+/// The confirm callback is called with details of each key before importing it. Keys pinned as
+/// trusted are imported without confirmation, see [`Trust`]. Imported keys are pinned, and keys
+/// already in the keychain are pinned if they weren't yet, so changed key files can be detected.
+///
+/// Key files that changed since they were pinned are never imported or pinned again, they are
+/// reported as [`ImportResult::Changed`]. The recipient must be trusted again explicitly.
 pub fn import_missing_keys_from_store(
     store: &Store,
-    confirm_callback: impl Fn(String) -> bool,
+    confirm_callback: impl Fn(&KeyImport) -> bool,
 ) -> Result<Vec<ImportResult>> {
     // Get public keys directory, ensure it exists
     let dir = store_public_keys_dir(store);
@@ -208,31 +459,106 @@ pub fn import_missing_keys_from_store(
     // Cache protocol contexts
     let mut contexts = ContextPool::empty();
     let mut results = Vec::new();
+    let mut trust = Trust::load(store)?;
+    let mut trust_changed = false;
+    let sync = store.sync();
 
     // Check for missing GPG keys based on fingerprint, import them
-    let gpg_fingerprints = store_read_gpg_fingerprints(store)?;
+    let gpg_fingerprints = store_read_all_gpg_fingerprints(store)?;
     for fingerprint in gpg_fingerprints {
         let context = contexts.get_mut(&crate::CONFIG)?;
-        if context.get_public_key(&fingerprint).is_err() {
-            let path = &store_public_keys_dir(store).join(&fingerprint);
-            if path.is_file() {
-                if confirm_callback(fingerprint.clone()) {
-                    context.import_key_file(path)?;
-                    results.push(ImportResult::Imported(fingerprint));
-                } else {
-                    results.push(ImportResult::Rejected(fingerprint));
-                }
-            } else {
+        let in_keychain = context.get_public_key(&fingerprint).is_ok();
+        let path = dir.join(&fingerprint);
+        if !path.is_file() {
+            if !in_keychain {
                 results.push(ImportResult::Unavailable(fingerprint));
             }
+            continue;
+        }
+
+        // Compare key file against pinned hash
+        let data = fs::read(&path).map_err(Err::ReadFile)?;
+        let hash = trust::hash(&data);
+        let changed = trust
+            .hash(&fingerprint)
+            .is_some_and(|pinned| pinned != hash);
+        if changed {
+            results.push(ImportResult::Changed(fingerprint));
+            continue;
+        }
+        if in_keychain {
+            if trust.hash(&fingerprint).is_none() {
+                trust.pin(&fingerprint, Some(hash));
+                trust_changed = true;
+            }
+            continue;
+        }
+
+        // Key file must hold the key it is named after
+        let keys = if util::is_armored_public_key(&data) {
+            context.read_keys(&data).unwrap_or_default()
+        } else {
+            vec![]
+        };
+        if !keys
+            .iter()
+            .any(|key| util::fingerprints_equal(key.fingerprint(false), &fingerprint))
+        {
+            results.push(ImportResult::Invalid(fingerprint));
+            continue;
+        }
+
+        let import = KeyImport {
+            added_by: sync.added_by(&path).unwrap_or(None),
+            pinned: trust.is_pinned(&fingerprint),
+            fingerprint,
+            keys,
+        };
+        if import.pinned || confirm_callback(&import) {
+            context.import_key(&data)?;
+            trust.pin(&import.fingerprint, Some(hash));
+            trust_changed = true;
+            results.push(ImportResult::Imported(import.fingerprint));
+        } else {
+            results.push(ImportResult::Rejected(import.fingerprint));
         }
     }
 
     // NEWPROTO: if a new proto is added, import missing keys here
 
+    if trust_changed {
+        trust.save()?;
+    }
+
     Ok(results)
 }
 
+/// Key from the store that is missing in the keychain.
+///
+/// Describes the key before it is imported.
+pub struct KeyImport {
+    /// Fingerprint of the recipient, the key file is named after it.
+    pub fingerprint: String,
+
+    /// Keys in the key file, read without importing them.
+    pub keys: Vec<Key>,
+
+    /// Commit that added the key file to the store, if known.
+    pub added_by: Option<Commit>,
+
+    /// Whether the fingerprint is pinned as trusted.
+    pub pinned: bool,
+}
+
+impl KeyImport {
+    /// Get the key the key file is named after.
+    pub fn key(&self) -> Option<&Key> {
+        self.keys
+            .iter()
+            .find(|key| util::fingerprints_equal(key.fingerprint(false), &self.fingerprint))
+    }
+}
+
 /// Missing key import results.
 pub enum ImportResult {
     /// Key with given fingerprint was imported into keychain.
@@ -243,6 +569,12 @@ pub enum ImportResult {
 
     /// Key with given fingerprint was rejected by the user.
     Rejected(String),
+
+    /// Key file of given fingerprint is invalid or holds a different key, it was not imported.
+    Invalid(String),
+
+    /// Key file of given fingerprint changed since it was pinned, it was not imported.
+    Changed(String),
 }
 
 /// Recipients extension for store functionality.
@@ -278,3 +610,103 @@ pub enum Err {
     #[error("failed to sync public key files")]
     SyncKeyFiles(#[source] std::io::Error),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Create an empty store in a temporary directory with the given name.
+    fn store(name: &str) -> Store {
+        let root = std::env::temp_dir().join(format!("prs-test-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        Store { root }
+    }
+
+    /// Write a GPG IDs file in the given store directory.
+    fn write_gpg_ids(store: &Store, dir: &str, contents: &str) -> PathBuf {
+        let dir = store.root.join(dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(STORE_GPG_IDS_FILE);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn gpg_ids_file_for_closest_parent() {
+        let store = store("gpg-ids-file-for");
+        let root = write_gpg_ids(&store, "", "AAAA\n");
+        let team = write_gpg_ids(&store, "team", "BBBB\n");
+        fs::create_dir_all(store.root.join("team/sub")).unwrap();
+
+        let secret = |name: &str| store.root.join(name);
+        assert_eq!(store_gpg_ids_file_for(&store, &secret("a.gpg")), root);
+        assert_eq!(store_gpg_ids_file_for(&store, &secret("other/a.gpg")), root);
+        assert_eq!(store_gpg_ids_file_for(&store, &secret("team/a.gpg")), team);
+        assert_eq!(
+            store_gpg_ids_file_for(&store, &secret("team/sub/a.gpg")),
+            team
+        );
+
+        fs::remove_dir_all(&store.root).unwrap();
+    }
+
+    #[test]
+    fn gpg_ids_file_for_without_root_file() {
+        let store = store("gpg-ids-file-for-no-root");
+        assert_eq!(
+            store_gpg_ids_file_for(&store, &store.root.join("a/b.gpg")),
+            store_gpg_ids_file(&store),
+        );
+        fs::remove_dir_all(&store.root).unwrap();
+    }
+
+    #[test]
+    fn gpg_ids_files_root_first() {
+        let store = store("gpg-ids-files");
+        let b = write_gpg_ids(&store, "b", "BBBB\n");
+        let a = write_gpg_ids(&store, "a/nested", "CCCC\n");
+        let root = write_gpg_ids(&store, "", "AAAA\n");
+        write_gpg_ids(&store, ".git", "DDDD\n");
+
+        assert_eq!(store_gpg_ids_files(&store), vec![root, a, b]);
+        assert_eq!(
+            store_read_all_gpg_fingerprints(&store).unwrap(),
+            vec!["AAAA", "CCCC", "BBBB"],
+        );
+
+        fs::remove_dir_all(&store.root).unwrap();
+    }
+
+    #[test]
+    fn obsolete_key_file() {
+        let recipients = vec!["E4B4B2A9E3F9B58C4A5C7B6D5A4F3E2D1C0B9A87".to_string()];
+        let store_fps = vec!["0x1234ABCD5678EF90".to_string()];
+
+        // Keep key files of selected recipients, even if not in any .gpg-id file yet
+        assert!(!is_obsolete_key_file(
+            "E4B4B2A9E3F9B58C4A5C7B6D5A4F3E2D1C0B9A87",
+            &recipients,
+            &[],
+        ));
+
+        // Keep key files listed in .gpg-id files, in any fingerprint format
+        assert!(!is_obsolete_key_file(
+            "0000000000000000000000001234ABCD5678EF90",
+            &[],
+            &store_fps,
+        ));
+
+        // Remove key files of anything else
+        assert!(is_obsolete_key_file(
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+            &recipients,
+            &store_fps,
+        ));
+        assert!(is_obsolete_key_file(
+            "E4B4B2A9E3F9B58C4A5C7B6D5A4F3E2D1C0B9A87",
+            &[],
+            &[],
+        ));
+    }
+}
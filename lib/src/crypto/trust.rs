@@ -0,0 +1,217 @@
+//! Trusted recipient keys.
+//!
+//! Pins fingerprints of recipient keys known to be good, along with a hash of their public key
+//! file in the store. This is used to import pinned keys without asking, and to detect public key
+//! files that change for an already known fingerprint.
+//!
+//! The trust file is local to this machine and is never synced. It is kept in the git directory
+//! of the store, so it cannot be changed through a commit.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use super::util;
+use crate::{Store, sync::STORE_GIT_DIR};
+
+/// Name of the trust file, in the store git directory.
+pub const TRUST_FILE: &str = "prs-trusted-keys";
+
+/// Name of the trust file in the store root, if the store does not use git.
+pub const TRUST_FILE_NO_GIT: &str = ".gpg-trusted-keys";
+
+/// Header written at the top of the trust file.
+const TRUST_HEADER: &str =
+    "# prs trusted recipient keys: fingerprint, optionally followed by SHA-256 of its key file";
+
+/// Trusted recipient keys.
+#[derive(Clone, Debug)]
+pub struct Trust {
+    /// Path to the trust file.
+    path: PathBuf,
+
+    /// Pinned fingerprints with the hash of their public key file, if known.
+    keys: Vec<(String, Option<String>)>,
+}
+
+impl Trust {
+    /// Load the trusted keys for the given store.
+    ///
+    /// Returns no keys if the store has no trust file.
+    pub fn load(store: &Store) -> Result<Self> {
+        let git_dir = store.root.join(STORE_GIT_DIR);
+        let path = if git_dir.is_dir() {
+            git_dir.join(TRUST_FILE)
+        } else {
+            store.root.join(TRUST_FILE_NO_GIT)
+        };
+        let keys = if path.is_file() {
+            parse(&fs::read_to_string(&path).map_err(Err::Read)?)
+        } else {
+            vec![]
+        };
+        Ok(Self { path, keys })
+    }
+
+    /// Save the trusted keys.
+    pub fn save(&self) -> Result<()> {
+        fs::write(&self.path, format(&self.keys)).map_err(Err::Write)?;
+        Ok(())
+    }
+
+    /// Path to the trust file.
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Get all pinned fingerprints with their key file hash, if known.
+    pub fn keys(&self) -> &[(String, Option<String>)] {
+        &self.keys
+    }
+
+    /// Check whether the given fingerprint is pinned.
+    pub fn is_pinned(&self, fingerprint: &str) -> bool {
+        self.find(fingerprint).is_some()
+    }
+
+    /// Get the pinned key file hash of the given fingerprint, if known.
+    pub fn hash(&self, fingerprint: &str) -> Option<&str> {
+        self.find(fingerprint).and_then(|(_, hash)| hash.as_deref())
+    }
+
+    /// Pin the given fingerprint, with the hash of its key file if known.
+    ///
+    /// Replaces the hash if the fingerprint was already pinned.
+    pub fn pin(&mut self, fingerprint: &str, hash: Option<String>) {
+        match self
+            .keys
+            .iter_mut()
+            .find(|(fp, _)| util::fingerprints_equal(fp, fingerprint))
+        {
+            Some((_, existing)) => *existing = hash,
+            None => self
+                .keys
+                .push((util::format_fingerprint(fingerprint), hash)),
+        }
+    }
+
+    /// Unpin the given fingerprint.
+    ///
+    /// Returns `false` if the fingerprint wasn't pinned.
+    pub fn unpin(&mut self, fingerprint: &str) -> bool {
+        let len = self.keys.len();
+        self.keys
+            .retain(|(fp, _)| !util::fingerprints_equal(fp, fingerprint));
+        self.keys.len() < len
+    }
+
+    /// Find the pinned entry for the given fingerprint.
+    fn find(&self, fingerprint: &str) -> Option<&(String, Option<String>)> {
+        self.keys
+            .iter()
+            .find(|(fp, _)| util::fingerprints_equal(fp, fingerprint))
+    }
+}
+
+/// Get the SHA-256 hash of key file contents, hex encoded.
+pub fn hash(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Parse a trust file.
+///
+/// Empty lines and comments are ignored.
+fn parse(data: &str) -> Vec<(String, Option<String>)> {
+    data.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let fingerprint = util::format_fingerprint(parts.next()?);
+            Some((fingerprint, parts.next().map(|hash| hash.to_lowercase())))
+        })
+        .collect()
+}
+
+/// Format a trust file.
+fn format(keys: &[(String, Option<String>)]) -> String {
+    let mut data = format!("{TRUST_HEADER}\n");
+    for (fingerprint, hash) in keys {
+        match hash {
+            Some(hash) => data.push_str(&format!("{fingerprint} {hash}\n")),
+            None => data.push_str(&format!("{fingerprint}\n")),
+        }
+    }
+    data
+}
+
+/// Trusted keys error.
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to read trusted keys file")]
+    Read(#[source] std::io::Error),
+
+    #[error("failed to write trusted keys file")]
+    Write(#[source] std::io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_format() {
+        let data = "\
+# comment
+
+A423B32B3CE9250155D0155E3B41CF8197074CBC 9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08
+0xBF66D044ACB376DEC8235BFEFC97A01D8E805806
+";
+        let keys = parse(data);
+        assert_eq!(
+            keys,
+            vec![
+                (
+                    "A423B32B3CE9250155D0155E3B41CF8197074CBC".to_string(),
+                    Some(
+                        "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+                            .to_string()
+                    ),
+                ),
+                ("BF66D044ACB376DEC8235BFEFC97A01D8E805806".to_string(), None),
+            ],
+        );
+        assert_eq!(parse(&format(&keys)), keys);
+    }
+
+    #[test]
+    fn test_pin() {
+        let mut trust = Trust {
+            path: PathBuf::new(),
+            keys: vec![],
+        };
+        trust.pin("A423B32B3CE9250155D0155E3B41CF8197074CBC", None);
+        assert!(trust.is_pinned("0xa423b32b3ce9250155d0155e3b41cf8197074cbc"));
+        assert_eq!(trust.hash("A423B32B3CE9250155D0155E3B41CF8197074CBC"), None);
+
+        trust.pin(
+            "A423B32B3CE9250155D0155E3B41CF8197074CBC",
+            Some(hash(b"test")),
+        );
+        assert_eq!(trust.keys().len(), 1);
+        assert_eq!(
+            trust.hash("A423B32B3CE9250155D0155E3B41CF8197074CBC"),
+            Some("9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"),
+        );
+
+        assert!(trust.unpin("A423B32B3CE9250155D0155E3B41CF8197074CBC"));
+        assert!(!trust.unpin("A423B32B3CE9250155D0155E3B41CF8197074CBC"));
+        assert!(!trust.is_pinned("A423B32B3CE9250155D0155E3B41CF8197074CBC"));
+    }
+}
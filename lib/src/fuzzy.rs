@@ -0,0 +1,218 @@
+//! Fuzzy secret name matching.
+//!
+//! Query characters must appear in order in a secret name, but not necessarily adjacent. Matches
+//! are scored so that the best match can be picked deterministically: characters at the start of
+//! path segments or words, consecutive characters and whole path segments score higher, gaps and
+//! long names score lower. Recently changed secrets get a small boost to break near ties.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use crate::Secret;
+
+/// Score for each matched character.
+const SCORE_MATCH: i64 = 16;
+
+/// Bonus for a match at the start of the name or a path segment.
+const BONUS_SEGMENT_START: i64 = 12;
+
+/// Bonus for a match at the start of a word, after a separator such as `-` or `_`.
+const BONUS_WORD_START: i64 = 8;
+
+/// Bonus for a match directly following the previous match.
+const BONUS_CONSECUTIVE: i64 = 8;
+
+/// Bonus for a match in the last path segment, the secret basename.
+const BONUS_BASENAME: i64 = 2;
+
+/// Bonus if the last query segment equals a path segment of the name.
+const BONUS_WHOLE_SEGMENT: i64 = 32;
+
+/// Maximum bonus for recently changed secrets, decays linearly over `RECENT`.
+const BONUS_RECENT: i64 = 12;
+
+/// Penalty for each skipped character between two matches.
+const PENALTY_GAP: i64 = 2;
+
+/// Penalty for each unmatched character in the name, divided by this number.
+const PENALTY_LENGTH_DIV: i64 = 4;
+
+/// Period in which recently changed secrets are boosted.
+pub(crate) const RECENT: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Score how well the query fuzzy matches the given secret name.
+///
+/// Matching is case insensitive. Returns `None` if not all query characters appear in order.
+/// A higher score is a better match.
+pub fn score(query: &str, name: &str) -> Option<i64> {
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+    if query.is_empty() || query.len() > name.len() {
+        return None;
+    }
+
+    // Bonus for matching each character in the name
+    let basename_start = name.iter().rposition(|c| *c == '/').map_or(0, |i| i + 1);
+    let bonus: Vec<i64> = (0..name.len())
+        .map(|i| {
+            let boundary = match i.checked_sub(1).map(|i| name[i]) {
+                None | Some('/') => BONUS_SEGMENT_START,
+                Some('-' | '_' | '.' | ' ') => BONUS_WORD_START,
+                Some(_) => 0,
+            };
+            let basename = if i >= basename_start {
+                BONUS_BASENAME
+            } else {
+                0
+            };
+            boundary + basename
+        })
+        .collect();
+
+    // Best score matching query up to i, with query[i] matched at name[j]
+    let mut prev: Vec<Option<i64>> = vec![None; name.len()];
+    for (i, q) in query.iter().enumerate() {
+        let mut cur = vec![None; name.len()];
+
+        // Best previous score followed by a gap, penalized by gap length
+        let mut gap: Option<i64> = None;
+        for j in 0..name.len() {
+            if j >= 2 {
+                gap = gap.max(prev[j - 2]).map(|s| s - PENALTY_GAP);
+            }
+            if name[j] != *q {
+                continue;
+            }
+
+            let base = if i == 0 {
+                Some(0)
+            } else {
+                let consecutive = j
+                    .checked_sub(1)
+                    .and_then(|j| prev[j])
+                    .map(|s| s + BONUS_CONSECUTIVE);
+                consecutive.max(gap)
+            };
+            cur[j] = base.map(|s| s + SCORE_MATCH + bonus[j]);
+        }
+
+        prev = cur;
+    }
+    let mut score = prev.into_iter().flatten().max()?;
+
+    // Prefer names having the queried segment as a whole
+    let query: String = query.into_iter().collect();
+    let name: String = name.into_iter().collect();
+    let last = query.rsplit('/').next().unwrap_or(&query);
+    if !last.is_empty() && name.split('/').any(|segment| segment == last) {
+        score += BONUS_WHOLE_SEGMENT;
+    }
+
+    // Prefer shorter names
+    score -= (name.chars().count() - query.chars().count()) as i64 / PENALTY_LENGTH_DIV;
+
+    Some(score)
+}
+
+/// Boost score for a secret last changed at the given time.
+fn recency_boost(changed: Option<&SystemTime>, now: SystemTime) -> i64 {
+    let Some(age) = changed.and_then(|changed| now.duration_since(*changed).ok()) else {
+        return 0;
+    };
+    if age >= RECENT {
+        return 0;
+    }
+    BONUS_RECENT - (BONUS_RECENT * age.as_secs() as i64) / RECENT.as_secs() as i64
+}
+
+/// Rank secrets by how well they fuzzy match the given query.
+///
+/// Secrets not matching the query are dropped. `changed` maps secret paths to the time they were
+/// last changed, used to boost recently changed secrets. Returns best match first, secrets with
+/// an equal score are sorted by name.
+pub fn rank(
+    secrets: Vec<Secret>,
+    query: &str,
+    changed: &HashMap<PathBuf, SystemTime>,
+) -> Vec<Secret> {
+    let now = SystemTime::now();
+    let mut ranked: Vec<(i64, Secret)> = secrets
+        .into_iter()
+        .filter_map(|secret| {
+            let score = score(query, &secret.name)?;
+            Some((
+                score + recency_boost(changed.get(&secret.path), now),
+                secret,
+            ))
+        })
+        .collect();
+    ranked.sort_by(|(a, a_secret), (b, b_secret)| {
+        b.cmp(a).then_with(|| a_secret.name.cmp(&b_secret.name))
+    });
+    ranked.into_iter().map(|(_, secret)| secret).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secret(name: &str) -> Secret {
+        Secret {
+            name: name.into(),
+            path: PathBuf::from(format!("/store/{name}.gpg")),
+        }
+    }
+
+    fn names(secrets: Vec<Secret>) -> Vec<String> {
+        secrets.into_iter().map(|secret| secret.name).collect()
+    }
+
+    #[test]
+    fn score_subsequence() {
+        assert!(score("gthb", "social/github").is_some());
+        assert!(score("GTHB", "social/GitHub").is_some());
+        assert!(score("ghtb", "social/github").is_none());
+        assert!(score("", "social/github").is_none());
+        assert!(score("github.com", "github").is_none());
+    }
+
+    #[test]
+    fn rank_best_first() {
+        let secrets = vec![
+            secret("git/authorbot"),
+            secret("website"),
+            secret("web/site2"),
+            secret("social/github"),
+            secret("web/site"),
+        ];
+        let changed = HashMap::new();
+        assert_eq!(
+            names(rank(secrets.clone(), "gthb", &changed)),
+            vec!["social/github", "git/authorbot"],
+        );
+        assert_eq!(
+            names(rank(secrets.clone(), "site", &changed)),
+            vec!["web/site", "web/site2", "website"],
+        );
+        assert_eq!(
+            names(rank(secrets, "w/s", &changed))[..2],
+            ["web/site", "web/site2"],
+        );
+    }
+
+    #[test]
+    fn rank_recent() {
+        let secrets = vec![secret("mail/work"), secret("mail/home")];
+        let mut changed = HashMap::new();
+        assert_eq!(
+            names(rank(secrets.clone(), "mail", &changed)),
+            vec!["mail/home", "mail/work"],
+        );
+        changed.insert(PathBuf::from("/store/mail/work.gpg"), SystemTime::now());
+        assert_eq!(
+            names(rank(secrets, "mail", &changed)),
+            vec!["mail/work", "mail/home"],
+        );
+    }
+}
@@ -1,7 +1,8 @@
+use std::collections::HashMap;
 use std::ffi::OsStr;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, ExitStatus, Output};
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use anyhow::Result;
 use thiserror::Error;
@@ -59,6 +60,25 @@ pub fn git_commit(repo: &Path, msg: &str, commit_empty: bool) -> Result<()> {
     git(repo, &args, false)
 }
 
+/// Invoke git commit to create an empty commit.
+///
+/// Staged changes are not included.
+pub fn git_commit_empty(repo: &Path, msg: &str) -> Result<()> {
+    git(
+        repo,
+        [
+            "commit",
+            "-q",
+            "--no-edit",
+            "--allow-empty",
+            "--only",
+            "-m",
+            msg,
+        ],
+        false,
+    )
+}
+
 /// Git hard reset all changes.
 pub fn git_reset_hard(repo: &Path) -> Result<()> {
     git(repo, ["reset", "--hard", "-q"], false)
@@ -262,6 +282,105 @@ pub fn git_last_pull_time(repo: &Path) -> Result<SystemTime> {
         .map_err(Err::Other)?)
 }
 
+/// Get the time of the last commit that changed the given file.
+///
+/// Returns `None` if the file was never committed.
+pub fn git_last_commit_time(repo: &Path, path: &Path) -> Result<Option<SystemTime>> {
+    let time = git_stdout_ok(
+        repo,
+        [
+            OsStr::new("log"),
+            OsStr::new("-1"),
+            OsStr::new("--format=%ct"),
+            OsStr::new("--"),
+            path.as_os_str(),
+        ],
+        false,
+    )?;
+    if time.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(parse_unix_time(&time)?))
+}
+
+/// Get the last commit that added the given file.
+///
+/// Returns the abbreviated commit hash, author and commit time. Returns `None` if the file was
+/// never committed.
+pub fn git_added_by(repo: &Path, path: &Path) -> Result<Option<(String, String, SystemTime)>> {
+    let log = git_stdout_ok(
+        repo,
+        [
+            OsStr::new("log"),
+            OsStr::new("-1"),
+            OsStr::new("--diff-filter=A"),
+            OsStr::new("--format=%h%x00%an <%ae>%x00%ct"),
+            OsStr::new("--"),
+            path.as_os_str(),
+        ],
+        false,
+    )?;
+    let mut parts = log.trim().splitn(3, '\0');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(hash), Some(author), Some(time)) if !hash.is_empty() => {
+            Ok(Some((hash.into(), author.into(), parse_unix_time(time)?)))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Get the time of the last commit that changed each file in the repository.
+///
+/// Paths are relative to the repository root. Files that were never committed are not included.
+/// If `since` is given, only commits after that time are walked.
+pub fn git_last_commit_times(
+    repo: &Path,
+    since: Option<SystemTime>,
+) -> Result<HashMap<PathBuf, SystemTime>> {
+    // Commit lines start with a NUL byte, which is never part of a file name
+    let mut args = vec![
+        "-c".to_string(),
+        "core.quotePath=false".into(),
+        "log".into(),
+        "--format=%x00%ct".into(),
+        "--name-only".into(),
+        "--no-renames".into(),
+    ];
+    if let Some(since) = since {
+        let secs = since
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        args.push(format!("--since=@{secs}"));
+    }
+    let log = git_stdout_ok(repo, args, false)?;
+
+    // Walk from newest to oldest commit, keep first time seen for each file
+    let mut times = HashMap::new();
+    let mut time = None;
+    for line in log.lines().filter(|line| !line.is_empty()) {
+        match line.strip_prefix('\0') {
+            Some(commit_time) => time = Some(parse_unix_time(commit_time)?),
+            None => {
+                if let Some(time) = time {
+                    times.entry(PathBuf::from(line)).or_insert(time);
+                }
+            }
+        }
+    }
+
+    Ok(times)
+}
+
+/// Parse a UNIX timestamp as printed by git.
+fn parse_unix_time(time: &str) -> Result<SystemTime> {
+    let secs: u64 = time
+        .trim()
+        .parse()
+        .map_err(|_| Err::GitCli(anyhow::anyhow!("invalid commit time: {time}")))?;
+    Ok(UNIX_EPOCH + Duration::from_secs(secs))
+}
+
 /// Invoke a git command with the given arguments.
 ///
 /// The command will take over the user console for in/output.
@@ -1,9 +1,12 @@
 pub mod crypto;
+pub mod fuzzy;
 pub(crate) mod git;
+pub mod search;
 pub mod store;
 pub mod sync;
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 pub mod systemd_bin;
+pub mod tags;
 #[cfg(all(feature = "tomb", target_os = "linux"))]
 pub mod tomb;
 #[cfg(all(feature = "tomb", target_os = "linux"))]
@@ -22,7 +25,7 @@ extern crate lazy_static;
 // Re-exports
 pub use crypto::{Key, recipients::Recipients};
 pub use store::{Secret, Store};
-pub use types::{Ciphertext, Plaintext};
+pub use types::{Ciphertext, FieldKind, Plaintext, Structured};
 
 use crate::crypto::{Config, Proto};
 
@@ -0,0 +1,459 @@
+//! Encrypted full-text search index.
+//!
+//! The index holds the plaintext of secrets in a single file encrypted to the store recipients,
+//! so searching requires just one decryption. Only secrets encrypted for the root `.gpg-id` file
+//! are indexed, secrets in subdirectories with their own `.gpg-id` file may have recipients the
+//! index is not encrypted for.
+//!
+//! Each entry is bound to the ciphertext it was built from by its SHA-256 hash. Entries that
+//! don't match the secret on disk anymore are stale, and must be read from the secret itself.
+//!
+//! The index is local to this machine, and is excluded from git.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use crate::{
+    Plaintext, Secret, Store,
+    crypto::{self, Context, prelude::*},
+};
+
+/// Name of the search index file in the store root.
+pub const INDEX_FILE: &str = ".search-index.gpg";
+
+/// Header of the search index format, includes the version.
+const INDEX_HEADER: &[u8] = b"prs-search-index 1\n";
+
+/// Encrypted search index.
+#[derive(Default)]
+pub struct SearchIndex {
+    /// Index entries by secret name.
+    entries: BTreeMap<String, Entry>,
+}
+
+/// Search index entry.
+#[derive(Clone)]
+pub struct Entry {
+    /// SHA-256 hash of the secret ciphertext this entry was built from.
+    hash: String,
+
+    /// Secret plaintext, empty for binary secrets.
+    pub plaintext: Plaintext,
+
+    /// Whether the secret is binary.
+    pub binary: bool,
+}
+
+impl SearchIndex {
+    /// Construct a new empty search index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the path of the search index file for the given store.
+    pub fn path(store: &Store) -> PathBuf {
+        store.root.join(INDEX_FILE)
+    }
+
+    /// Check whether the given store has a search index.
+    pub fn exists(store: &Store) -> bool {
+        Self::path(store).is_file()
+    }
+
+    /// Check whether the given secret may be indexed.
+    ///
+    /// Only secrets encrypted for the same recipients as the index are, which are those using the
+    /// `.gpg-id` file in the store root.
+    pub fn indexes(store: &Store, secret: &Secret) -> bool {
+        crypto::store::store_gpg_ids_file_for(store, &secret.path)
+            == crypto::store::store_gpg_ids_file(store)
+    }
+
+    /// Load and decrypt the search index of the given store.
+    ///
+    /// Errors if the index is missing, cannot be decrypted, or is corrupt.
+    pub fn load(store: &Store, context: &mut Context) -> Result<Self> {
+        let plaintext = context
+            .decrypt_file(&Self::path(store))
+            .map_err(Err::Decrypt)?;
+        Ok(Self {
+            entries: parse(plaintext.unsecure_ref())?,
+        })
+    }
+
+    /// Encrypt and save the search index for the given store.
+    ///
+    /// The index is encrypted to the store recipients, and is excluded from git. Entries of
+    /// secrets that may not be indexed are never written.
+    pub fn save(&self, store: &Store, context: &mut Context) -> Result<()> {
+        let path = Self::path(store);
+        let plaintext = self.serialize(|name| {
+            store
+                .find_at(name)
+                .is_some_and(|secret| Self::indexes(store, &secret))
+        });
+        context
+            .encrypt_file(&store.recipients()?, plaintext, &path)
+            .map_err(Err::Encrypt)?;
+        exclude_from_git(store).map_err(Err::Exclude)?;
+        Ok(())
+    }
+
+    /// Remove the search index file of the given store, if it exists.
+    pub fn delete(store: &Store) -> Result<()> {
+        let path = Self::path(store);
+        if path.is_file() {
+            fs::remove_file(path).map_err(Err::Delete)?;
+        }
+        Ok(())
+    }
+
+    /// Number of entries in the index.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check whether the index is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get the index entry for the given secret if it is up-to-date.
+    ///
+    /// Returns `None` if the secret is not indexed, or if the entry is stale.
+    pub fn get(&self, secret: &Secret) -> Option<&Entry> {
+        let entry = self.entries.get(&secret.name)?;
+        let hash = hash_file(secret).ok()?;
+        (entry.hash == hash).then_some(entry)
+    }
+
+    /// Update the index entry for the given secret.
+    ///
+    /// The `plaintext` must be the current plaintext of the secret on disk.
+    pub fn update(&mut self, secret: &Secret, plaintext: &Plaintext) -> Result<()> {
+        let binary = plaintext.is_binary();
+        let entry = Entry {
+            hash: hash_file(secret).map_err(Err::Hash)?,
+            plaintext: if binary {
+                Plaintext::empty()
+            } else {
+                plaintext.clone()
+            },
+            binary,
+        };
+        self.entries.insert(secret.name.clone(), entry);
+        Ok(())
+    }
+
+    /// Remove the index entry of the given secret.
+    ///
+    /// Returns `true` if the index changed.
+    pub fn remove(&mut self, secret: &Secret) -> bool {
+        self.entries.remove(&secret.name).is_some()
+    }
+
+    /// Move the index entry of a secret, such as when it is moved.
+    ///
+    /// Returns `true` if the index changed.
+    pub fn rename(&mut self, from: &Secret, to: &Secret) -> bool {
+        match self.entries.remove(&from.name) {
+            Some(entry) => {
+                self.entries.insert(to.name.clone(), entry);
+                true
+            }
+            None => self.entries.remove(&to.name).is_some(),
+        }
+    }
+
+    /// Copy the index entry of a secret, such as when it is duplicated.
+    ///
+    /// Returns `true` if the index changed.
+    pub fn copy(&mut self, from: &Secret, to: &Secret) -> bool {
+        match self.entries.get(&from.name).cloned() {
+            Some(entry) => {
+                self.entries.insert(to.name.clone(), entry);
+                true
+            }
+            None => self.entries.remove(&to.name).is_some(),
+        }
+    }
+
+    /// Remove entries of secrets that don't exist anymore in the given store.
+    ///
+    /// Returns `true` if the index changed.
+    pub fn prune(&mut self, store: &Store) -> bool {
+        let len = self.entries.len();
+        self.entries.retain(|name, _| store.find_at(name).is_some());
+        len != self.entries.len()
+    }
+
+    /// Check the index against the secrets in the given store.
+    ///
+    /// Reports secrets missing from the index, stale entries, and entries for secrets that don't
+    /// exist anymore. Secrets that may not be indexed are skipped. Nothing is decrypted.
+    pub fn verify(&self, store: &Store) -> Vec<Problem> {
+        let mut problems = vec![];
+        let mut secrets: Vec<Secret> = store
+            .secret_iter()
+            .filter(|secret| Self::indexes(store, secret))
+            .collect();
+        secrets.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+
+        for secret in &secrets {
+            match self.entries.get(&secret.name) {
+                None => problems.push(Problem::Missing(secret.name.clone())),
+                Some(_) if self.get(secret).is_none() => {
+                    problems.push(Problem::Stale(secret.name.clone()))
+                }
+                Some(_) => {}
+            }
+        }
+
+        problems.extend(
+            self.entries
+                .keys()
+                .filter(|name| !secrets.iter().any(|secret| &secret.name == *name))
+                .map(|name| Problem::Orphaned(name.clone())),
+        );
+
+        problems
+    }
+
+    /// Serialize the index into plaintext.
+    ///
+    /// Each entry is a header line with the name, hash, kind and content length, followed by the
+    /// content and a newline. Only entries whose name matches the filter are included.
+    fn serialize<F>(&self, filter: F) -> Plaintext
+    where
+        F: Fn(&str) -> bool,
+    {
+        let entries: Vec<(&String, &Entry)> = self
+            .entries
+            .iter()
+            .filter(|(name, _)| filter(name))
+            .collect();
+        let headers: Vec<String> = entries
+            .iter()
+            .map(|(name, entry)| {
+                format!(
+                    "{}\t{}\t{}\t{}\n",
+                    name,
+                    entry.hash,
+                    if entry.binary { "b" } else { "t" },
+                    entry.plaintext.unsecure_ref().len(),
+                )
+            })
+            .collect();
+
+        // Allocate all at once, the buffer must not reallocate to not leave copies of secrets
+        let size = INDEX_HEADER.len()
+            + headers.iter().map(|h| h.len()).sum::<usize>()
+            + entries
+                .iter()
+                .map(|(_, e)| e.plaintext.unsecure_ref().len() + 1)
+                .sum::<usize>();
+        let mut data = Vec::with_capacity(size);
+
+        data.extend_from_slice(INDEX_HEADER);
+        for (header, (_, entry)) in headers.iter().zip(&entries) {
+            data.extend_from_slice(header.as_bytes());
+            data.extend_from_slice(entry.plaintext.unsecure_ref());
+            data.push(b'\n');
+        }
+
+        data.into()
+    }
+}
+
+/// Parse a serialized search index.
+fn parse(mut data: &[u8]) -> Result<BTreeMap<String, Entry>, Err> {
+    data = data.strip_prefix(INDEX_HEADER).ok_or(Err::Corrupt)?;
+
+    let mut entries = BTreeMap::new();
+    while !data.is_empty() {
+        // Parse entry header line
+        let end = data.iter().position(|b| *b == b'\n').ok_or(Err::Corrupt)?;
+        let header = std::str::from_utf8(&data[..end]).map_err(|_| Err::Corrupt)?;
+        let mut parts = header.rsplitn(4, '\t');
+        let (len, kind, hash, name) = (
+            parts.next().and_then(|len| len.parse::<usize>().ok()),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+        );
+        let (Some(len), Some(kind), Some(hash), Some(name)) = (len, kind, hash, name) else {
+            return Err(Err::Corrupt);
+        };
+        data = &data[end + 1..];
+
+        // Read content, must be followed by newline
+        if data.len() < len + 1 || data[len] != b'\n' {
+            return Err(Err::Corrupt);
+        }
+        let entry = Entry {
+            hash: hash.into(),
+            plaintext: data[..len].to_vec().into(),
+            binary: kind == "b",
+        };
+        entries.insert(name.into(), entry);
+        data = &data[len + 1..];
+    }
+
+    Ok(entries)
+}
+
+/// Get the SHA-256 hash of the secret ciphertext file, hex encoded.
+fn hash_file(secret: &Secret) -> io::Result<String> {
+    let mut file = fs::File::open(&secret.path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = [0u8; 8 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect())
+}
+
+/// Exclude the search index from git in the store repository, locally.
+///
+/// Does nothing if the store is not a git repository.
+fn exclude_from_git(store: &Store) -> io::Result<()> {
+    let git = store.root.join(".git");
+    if !git.is_dir() {
+        return Ok(());
+    }
+
+    let path = git.join("info").join("exclude");
+    let entry = format!("/{INDEX_FILE}");
+    if fs::read_to_string(&path)
+        .map(|data| data.lines().any(|line| line.trim() == entry))
+        .unwrap_or(false)
+    {
+        return Ok(());
+    }
+
+    fs::create_dir_all(git.join("info"))?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{entry}")
+}
+
+/// A search index integrity problem.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// The secret is not in the index.
+    Missing(String),
+
+    /// The index entry of the secret is outdated.
+    Stale(String),
+
+    /// The index has an entry for a secret that doesn't exist.
+    Orphaned(String),
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing(name) => write!(f, "not indexed: {name}"),
+            Self::Stale(name) => write!(f, "outdated: {name}"),
+            Self::Orphaned(name) => write!(f, "removed secret still indexed: {name}"),
+        }
+    }
+}
+
+/// Search index error.
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to decrypt search index")]
+    Decrypt(#[source] anyhow::Error),
+
+    #[error("failed to encrypt search index")]
+    Encrypt(#[source] anyhow::Error),
+
+    #[error("search index is corrupt")]
+    Corrupt,
+
+    #[error("failed to hash secret ciphertext")]
+    Hash(#[source] io::Error),
+
+    #[error("failed to exclude search index from git")]
+    Exclude(#[source] io::Error),
+
+    #[error("failed to delete search index")]
+    Delete(#[source] io::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(hash: &str, plaintext: &str) -> Entry {
+        Entry {
+            hash: hash.into(),
+            plaintext: plaintext.into(),
+            binary: false,
+        }
+    }
+
+    #[test]
+    fn serialize_parse() {
+        let mut index = SearchIndex::new();
+        index
+            .entries
+            .insert("web/my\tsite".into(), entry("aa", "pass\nuser: a\n"));
+        index.entries.insert("empty".into(), entry("bb", ""));
+        index.entries.insert(
+            "file".into(),
+            Entry {
+                hash: "cc".into(),
+                plaintext: Plaintext::empty(),
+                binary: true,
+            },
+        );
+
+        let data = index.serialize(|_| true);
+        let entries = parse(data.unsecure_ref()).unwrap();
+        assert_eq!(entries.len(), 3);
+        let site = &entries["web/my\tsite"];
+        assert_eq!(site.hash, "aa");
+        assert_eq!(site.plaintext.unsecure_to_str().unwrap(), "pass\nuser: a\n");
+        assert!(!site.binary);
+        assert!(entries["file"].binary);
+        assert!(entries["empty"].plaintext.unsecure_ref().is_empty());
+    }
+
+    #[test]
+    fn serialize_filter() {
+        let mut index = SearchIndex::new();
+        index.entries.insert("root".into(), entry("aa", "a"));
+        index.entries.insert("team/secret".into(), entry("bb", "b"));
+
+        let data = index.serialize(|name| !name.starts_with("team/"));
+        let entries = parse(data.unsecure_ref()).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert!(entries.contains_key("root"));
+    }
+
+    #[test]
+    fn parse_corrupt() {
+        assert!(parse(b"").is_err());
+        assert!(parse(b"prs-search-index 2\n").is_err());
+        assert!(parse(b"prs-search-index 1\n").unwrap().is_empty());
+        assert!(parse(b"prs-search-index 1\nname\taa\tt\t10\nshort\n").is_err());
+        assert!(parse(b"prs-search-index 1\nname\taa\tt\t2\nabc\n").is_err());
+        assert!(parse(b"prs-search-index 1\nname\taa\tt\t3\nabc\n").is_ok());
+    }
+}
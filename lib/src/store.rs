@@ -1,8 +1,10 @@
 //! Interface to a password store and its secrets.
 
+use std::collections::HashMap;
 use std::ffi::OsString;
 use std::fs;
 use std::path::{self, Path, PathBuf};
+use std::time::SystemTime;
 
 use anyhow::{Result, ensure};
 use thiserror::Error;
@@ -13,7 +15,9 @@ use crate::tomb::Tomb;
 use crate::{
     Recipients,
     crypto::{self, prelude::*},
+    fuzzy,
     sync::Sync,
+    tags::Tags,
 };
 
 /// Password store secret file suffix.
@@ -50,6 +54,13 @@ impl Store {
         Recipients::load(self)
     }
 
+    /// Get the recipient keys for the given secret in this store.
+    ///
+    /// These are the recipients of the closest `.gpg-id` file, which may be in a subdirectory.
+    pub fn recipients_for(&self, secret: &Secret) -> Result<Recipients> {
+        crypto::store::store_load_recipients_for(self, &secret.path)
+    }
+
     /// Get a sync helper for this store.
     pub fn sync(&self) -> Sync<'_> {
         Sync::new(self)
@@ -61,6 +72,11 @@ impl Store {
         Tomb::new(self, quiet, verbose, force)
     }
 
+    /// Load the secret tags index for this store.
+    pub fn tags(&self) -> Result<Tags> {
+        Tags::load(self)
+    }
+
     /// Create secret iterator for this store.
     pub fn secret_iter(&self) -> SecretIter {
         self.secret_iter_config(SecretIterConfig::default())
@@ -100,8 +116,43 @@ impl Store {
     /// Try to find matching secrets for given query.
     ///
     /// If secret is found at exact query path, `FindSecret::Found` is returned.
-    /// Otherwise any number of closely matching secrets is returned as `FindSecret::Many`.
+    /// Otherwise any number of closely matching secrets is returned as `FindSecret::Many`, ranked
+    /// best match first. Secrets containing the query are matched, or if there are none, secrets
+    /// fuzzy matching the query. See [`fuzzy`](crate::fuzzy) for ranking.
     pub fn find(&self, query: Option<String>) -> FindSecret {
+        let query = match query {
+            Some(query) if !query.trim().is_empty() => query,
+            query => return self.find_exact(query),
+        };
+
+        // Try to find exact secret match
+        if let Some(secret) = self.find_at(&query) {
+            return FindSecret::Exact(secret);
+        }
+
+        // Find all closely matching, fall back to fuzzy matching
+        let mut secrets = self.secrets(Some(query.clone()));
+        if secrets.is_empty() {
+            secrets = self.secrets(None);
+        }
+
+        // Rank, boost recently changed secrets, only walk recent history if there's a choice
+        let changed = if secrets.len() > 1 {
+            let since = SystemTime::now() - fuzzy::RECENT;
+            self.sync()
+                .last_commit_times(Some(since))
+                .unwrap_or_default()
+        } else {
+            HashMap::new()
+        };
+        FindSecret::Many(fuzzy::rank(secrets, &query, &changed))
+    }
+
+    /// Try to find matching secrets for given query, without fuzzy matching.
+    ///
+    /// If secret is found at exact query path, `FindSecret::Found` is returned.
+    /// Otherwise all secrets containing the query are returned as `FindSecret::Many`.
+    pub fn find_exact(&self, query: Option<String>) -> FindSecret {
         // Try to find exact secret match
         if let Some(query) = &query
             && let Some(secret) = self.find_at(query)
@@ -188,7 +239,7 @@ pub enum FindSecret {
     /// Found exact secret match.
     Exact(Secret),
 
-    /// Found any number of non-exact secret matches.
+    /// Found any number of non-exact secret matches, best match first if ranked.
     Many(Vec<Secret>),
 }
 
@@ -330,6 +381,8 @@ fn is_hidden_subdir(entry: &DirEntry) -> bool {
 }
 
 /// Check if given WalkDir DirEntry is a secret file.
+///
+/// Shares of split secrets are not secrets on their own, and are skipped.
 fn is_secret_file(entry: &DirEntry) -> bool {
     entry.file_type().is_file()
         && entry
@@ -337,6 +390,7 @@ fn is_secret_file(entry: &DirEntry) -> bool {
             .to_str()
             .map(|s| s.ends_with(SECRET_SUFFIX))
             .unwrap_or(false)
+        && !crypto::shamir::is_share(entry.path())
 }
 
 /// Check if given WalkDir DirEntry passes the configuration.
@@ -1,7 +1,8 @@
 //! Password store synchronization functionality.
 
-use std::path::Path;
-use std::time::Duration;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
 use anyhow::Result;
 
@@ -128,6 +129,19 @@ impl<'a> Sync<'a> {
             self.commit_all(msg, false)?;
         }
 
+        self.finalize_push()
+    }
+
+    /// Finalize the store by pushing committed changes, without committing anything.
+    ///
+    /// - If sync is not initialized, it does nothing.
+    /// - If sync remote is set, it pushes changes.
+    pub fn finalize_push(&self) -> Result<()> {
+        // Skip if no sync
+        if !self.is_init() {
+            return Ok(());
+        }
+
         // Do not push  if no remote or not out of sync
         if !self.has_remote()? || !safe_need_to_push(self.path()) {
             return Ok(());
@@ -246,6 +260,49 @@ impl<'a> Sync<'a> {
         git::git_push(self.path(), set_branch, set_upstream)
     }
 
+    /// Get the time of the last commit that changed the given file.
+    ///
+    /// Returns `None` if sync is not initialized or if the file was never committed.
+    pub fn last_commit_time(&self, path: &Path) -> Result<Option<SystemTime>> {
+        if !self.is_init() {
+            return Ok(None);
+        }
+        git::git_last_commit_time(self.path(), path)
+    }
+
+    /// Get the last commit that added the given file.
+    ///
+    /// Returns `None` if sync is not initialized or if the file was never committed.
+    pub fn added_by(&self, path: &Path) -> Result<Option<Commit>> {
+        if !self.is_init() {
+            return Ok(None);
+        }
+        Ok(
+            git::git_added_by(self.path(), path)?.map(|(id, author, time)| Commit {
+                id,
+                author,
+                time,
+            }),
+        )
+    }
+
+    /// Get the time of the last commit that changed each file in the store.
+    ///
+    /// Paths are absolute. Returns an empty map if sync is not initialized. If `since` is given,
+    /// only files changed after that time are included.
+    pub fn last_commit_times(
+        &self,
+        since: Option<SystemTime>,
+    ) -> Result<HashMap<PathBuf, SystemTime>> {
+        if !self.is_init() {
+            return Ok(HashMap::new());
+        }
+        Ok(git::git_last_commit_times(self.path(), since)?
+            .into_iter()
+            .map(|(path, time)| (self.path().join(path), time))
+            .collect())
+    }
+
     /// Add all changes and commit them.
     pub fn commit_all<M: AsRef<str>>(&self, msg: M, commit_empty: bool) -> Result<()> {
         let path = self.path();
@@ -253,6 +310,11 @@ impl<'a> Sync<'a> {
         git::git_commit(path, msg.as_ref(), commit_empty)
     }
 
+    /// Create an empty commit, without adding or committing any changes.
+    pub fn commit_empty<M: AsRef<str>>(&self, msg: M) -> Result<()> {
+        git::git_commit_empty(self.path(), msg.as_ref())
+    }
+
     /// Hard reset all changes.
     pub fn reset_hard_all(&self) -> Result<()> {
         let path = self.path();
@@ -304,6 +366,19 @@ impl Readyness {
     }
 }
 
+/// A commit in the store repository.
+#[derive(Clone, Debug)]
+pub struct Commit {
+    /// Abbreviated commit hash.
+    pub id: String,
+
+    /// Commit author name and email.
+    pub author: String,
+
+    /// Commit time.
+    pub time: SystemTime,
+}
+
 /// Check if repository is dirty.
 ///
 /// Repository is dirty if it has any uncommitted changed.
@@ -0,0 +1,289 @@
+//! Secret tags index.
+//!
+//! Tags are stored in a plain text sidecar file in the store root, mapping secret names to tags.
+//! This allows filtering secrets by tag without decrypting anything. The index is not encrypted,
+//! tags should therefore not contain sensitive information, just like secret names.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Result, ensure};
+use thiserror::Error;
+
+use crate::{Secret, Store};
+
+/// Name of the tags index file in the store root.
+pub const TAGS_FILE: &str = ".tags";
+
+/// Header written at the top of the tags index file.
+const TAGS_HEADER: &str = "# prs secret tags: <secret name><tab><space separated tags>";
+
+/// Secret tags index.
+#[derive(Clone, Debug)]
+pub struct Tags {
+    /// Path to the tags index file.
+    path: PathBuf,
+
+    /// Tags by secret name.
+    tags: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Tags {
+    /// Load the tags index for the given store.
+    ///
+    /// Returns an empty index if the store has no tags file.
+    pub fn load(store: &Store) -> Result<Self> {
+        let path = store.root.join(TAGS_FILE);
+        let tags = if path.is_file() {
+            parse(&fs::read_to_string(&path).map_err(Err::Read)?)
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Self { path, tags })
+    }
+
+    /// Save the tags index.
+    ///
+    /// The tags file is removed if there are no tags.
+    pub fn save(&self) -> Result<()> {
+        if self.tags.is_empty() {
+            if self.path.is_file() {
+                fs::remove_file(&self.path).map_err(Err::Write)?;
+            }
+            return Ok(());
+        }
+
+        fs::write(&self.path, format(&self.tags)).map_err(Err::Write)?;
+        Ok(())
+    }
+
+    /// Get the sorted tags for the given secret.
+    pub fn get(&self, secret: &Secret) -> Vec<&str> {
+        self.tags
+            .get(&secret.name)
+            .map(|tags| tags.iter().map(|tag| tag.as_str()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Check whether the given secret has all the given tags.
+    ///
+    /// Always true if no tags are given.
+    pub fn has_all<S: AsRef<str>>(&self, secret: &Secret, tags: &[S]) -> bool {
+        let secret_tags = self.tags.get(&secret.name);
+        tags.iter()
+            .all(|tag| secret_tags.is_some_and(|t| t.contains(&normalize(tag.as_ref()))))
+    }
+
+    /// Get all tags used in this index, sorted.
+    pub fn all(&self) -> BTreeSet<&str> {
+        self.tags
+            .values()
+            .flat_map(|tags| tags.iter().map(|tag| tag.as_str()))
+            .collect()
+    }
+
+    /// Get names of all secrets having the given tag, sorted.
+    pub fn secrets_with(&self, tag: &str) -> Vec<&str> {
+        let tag = normalize(tag);
+        self.tags
+            .iter()
+            .filter(|(_, tags)| tags.contains(&tag))
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Add a tag to the given secret.
+    ///
+    /// Returns `true` if the tag was added, `false` if the secret already had it.
+    pub fn add(&mut self, secret: &Secret, tag: &str) -> Result<bool> {
+        validate(tag)?;
+        ensure!(!secret.name.contains(['\t', '\n']), Err::InvalidName);
+        Ok(self
+            .tags
+            .entry(secret.name.clone())
+            .or_default()
+            .insert(normalize(tag)))
+    }
+
+    /// Remove a tag from the given secret.
+    ///
+    /// Returns `true` if the tag was removed, `false` if the secret didn't have it.
+    pub fn remove(&mut self, secret: &Secret, tag: &str) -> bool {
+        let Some(tags) = self.tags.get_mut(&secret.name) else {
+            return false;
+        };
+        let removed = tags.remove(&normalize(tag));
+        if tags.is_empty() {
+            self.tags.remove(&secret.name);
+        }
+        removed
+    }
+
+    /// Move tags of a secret to a new secret, such as when it is moved.
+    ///
+    /// Existing tags of `to` are replaced. Returns `true` if the index changed.
+    pub fn move_secret(&mut self, from: &Secret, to: &Secret) -> bool {
+        match self.tags.remove(&from.name) {
+            Some(tags) => {
+                self.tags.insert(to.name.clone(), tags);
+                true
+            }
+            None => self.tags.remove(&to.name).is_some(),
+        }
+    }
+
+    /// Copy tags of a secret to a new secret, such as when it is duplicated.
+    ///
+    /// Existing tags of `to` are replaced. Returns `true` if the index changed.
+    pub fn copy_secret(&mut self, from: &Secret, to: &Secret) -> bool {
+        match self.tags.get(&from.name).cloned() {
+            Some(tags) => self.tags.insert(to.name.clone(), tags.clone()) != Some(tags),
+            None => self.tags.remove(&to.name).is_some(),
+        }
+    }
+
+    /// Remove all tags of the given secret, such as when it is removed.
+    ///
+    /// Returns `true` if the index changed.
+    pub fn remove_secret(&mut self, secret: &Secret) -> bool {
+        self.tags.remove(&secret.name).is_some()
+    }
+
+    /// Remove tags of secrets that don't exist anymore in the given store.
+    ///
+    /// Returns the names of the secrets that were dropped from the index.
+    pub fn prune(&mut self, store: &Store) -> Vec<String> {
+        let stale: Vec<String> = self
+            .tags
+            .keys()
+            .filter(|name| store.find_at(name).is_none())
+            .cloned()
+            .collect();
+        stale.iter().for_each(|name| {
+            self.tags.remove(name);
+        });
+        stale
+    }
+}
+
+/// Validate the given tag.
+///
+/// Tags must not be empty, and must not contain whitespace.
+pub fn validate(tag: &str) -> Result<()> {
+    ensure!(
+        !tag.is_empty() && !tag.starts_with('#') && !tag.contains(char::is_whitespace),
+        Err::InvalidTag(tag.into())
+    );
+    Ok(())
+}
+
+/// Normalize the given tag, tags are case insensitive.
+fn normalize(tag: &str) -> String {
+    tag.to_lowercase()
+}
+
+/// Parse a tags index file.
+///
+/// Empty lines, comments and malformed lines are ignored.
+fn parse(data: &str) -> BTreeMap<String, BTreeSet<String>> {
+    data.lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.rsplit_once('\t'))
+        .map(|(name, tags)| {
+            let tags: BTreeSet<String> = tags.split_whitespace().map(normalize).collect();
+            (name.to_string(), tags)
+        })
+        .filter(|(name, tags)| !name.is_empty() && !tags.is_empty())
+        .collect()
+}
+
+/// Format a tags index file.
+fn format(tags: &BTreeMap<String, BTreeSet<String>>) -> String {
+    let mut data = format!("{TAGS_HEADER}\n");
+    for (name, tags) in tags {
+        data.push_str(name);
+        data.push('\t');
+        data.push_str(&tags.iter().cloned().collect::<Vec<_>>().join(" "));
+        data.push('\n');
+    }
+    data
+}
+
+/// Tags index error.
+#[derive(Debug, Error)]
+pub enum Err {
+    #[error("failed to read tags index file")]
+    Read(#[source] std::io::Error),
+
+    #[error("failed to write tags index file")]
+    Write(#[source] std::io::Error),
+
+    #[error("invalid tag '{0}', must not be empty or contain whitespace")]
+    InvalidTag(String),
+
+    #[error("cannot tag secret, name contains a tab or newline")]
+    InvalidName,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secret(name: &str) -> Secret {
+        Secret {
+            name: name.into(),
+            path: PathBuf::from(format!("/store/{name}.gpg")),
+        }
+    }
+
+    fn tags(data: &str) -> Tags {
+        Tags {
+            path: PathBuf::new(),
+            tags: parse(data),
+        }
+    }
+
+    #[test]
+    fn parse_format() {
+        let data = format!("{TAGS_HEADER}\nweb/my site\taws Prod\nbroken line\n\nssh\tprod\n");
+        let tags = tags(&data);
+        assert_eq!(tags.get(&secret("web/my site")), vec!["aws", "prod"]);
+        assert_eq!(tags.get(&secret("ssh")), vec!["prod"]);
+        assert_eq!(tags.secrets_with("PROD"), vec!["ssh", "web/my site"]);
+        assert_eq!(
+            format(&tags.tags),
+            format!("{TAGS_HEADER}\nssh\tprod\nweb/my site\taws prod\n"),
+        );
+    }
+
+    #[test]
+    fn add_remove() {
+        let mut tags = tags("");
+        let a = secret("a");
+        assert!(tags.add(&a, "prod").unwrap());
+        assert!(!tags.add(&a, "Prod").unwrap());
+        assert!(tags.add(&a, "my tag").is_err());
+        assert!(tags.add(&a, "").is_err());
+        assert!(tags.has_all(&a, &["prod"]));
+        assert!(!tags.has_all(&a, &["prod", "aws"]));
+        assert!(tags.remove(&a, "PROD"));
+        assert!(!tags.remove(&a, "prod"));
+        assert!(tags.tags.is_empty());
+    }
+
+    #[test]
+    fn move_copy_remove_secret() {
+        let mut tags = tags("a\tprod\nb\ttest\n");
+        let (a, b, c) = (secret("a"), secret("b"), secret("c"));
+        assert!(tags.copy_secret(&a, &c));
+        assert!(!tags.copy_secret(&a, &c));
+        assert!(tags.move_secret(&a, &b));
+        assert_eq!(tags.get(&a), Vec::<&str>::new());
+        assert_eq!(tags.get(&b), vec!["prod"]);
+        assert_eq!(tags.get(&c), vec!["prod"]);
+        assert!(tags.remove_secret(&b));
+        assert!(!tags.remove_secret(&b));
+        assert_eq!(tags.all(), BTreeSet::from(["prod"]));
+    }
+}
@@ -1,5 +1,7 @@
 //! Secret plaintext and ciphertext types.
 
+use std::ops::Range;
+
 use anyhow::Result;
 use secstr::SecVec;
 use thiserror::Error;
@@ -8,6 +10,12 @@ use zeroize::Zeroize;
 /// Delimiter for properties.
 const PROPERTY_DELIMITER: char = ':';
 
+/// Marker at the start of binary secret plaintext, such as attached files.
+///
+/// Starts with a NUL byte, which never appears in textual secrets, so it cannot be mistaken for
+/// text. The marker is not part of the binary payload itself.
+pub const BINARY_MARKER: &[u8] = b"\0prs-binary\n";
+
 /// Newline character(s) on this platform.
 #[cfg(not(windows))]
 pub const NEWLINE: &str = "\n";
@@ -124,20 +132,112 @@ impl Plaintext {
     ///
     /// This will never return the first line being the password.
     pub fn property(&self, property: &str) -> Result<Plaintext> {
-        let property = property.trim().to_uppercase();
         self.unsecure_to_str()
             .map_err(Err::Utf8)?
             .lines()
             .skip(1)
             .find_map(|line| {
                 let mut parts = line.splitn(2, PROPERTY_DELIMITER);
-                if parts.next().unwrap().trim().to_uppercase() == property {
+                if property_matches(parts.next().unwrap(), property) {
                     Some(parts.next().map(|value| value.trim()).unwrap_or("").into())
                 } else {
                     None
                 }
             })
-            .ok_or_else(|| Err::Property(property.to_lowercase()).into())
+            .ok_or_else(|| Err::Property(property.trim().to_lowercase()).into())
+    }
+
+    /// Set the value of the given property.
+    ///
+    /// Replaces the value of the first line with the given property, matched the same way as
+    /// [`property`](Self::property). The property is appended as new line if it does not exist.
+    ///
+    /// This will never change the first line being the password. Fails for binary plaintext.
+    pub fn set_property(&mut self, property: &str, value: Plaintext) -> Result<()> {
+        if self.is_binary() {
+            return Err(Err::Binary.into());
+        }
+        let property = property.trim();
+        if property.is_empty() || property.contains(PROPERTY_DELIMITER) || property.contains('\n') {
+            return Err(Err::PropertyName(property.into()).into());
+        }
+        let value = value.unsecure_to_str().map_err(Err::Utf8)?;
+        if value.contains(['\n', '\r']) {
+            return Err(Err::PropertyValue.into());
+        }
+
+        let text = self.unsecure_to_str().map_err(Err::Utf8)?;
+        let mut data = Vec::with_capacity(text.len() + property.len() + value.len() + 4);
+        let mut found = false;
+        for (i, line) in text.split_inclusive('\n').enumerate() {
+            let content = line.trim_end_matches(['\r', '\n']);
+            let key = content.split(PROPERTY_DELIMITER).next().unwrap();
+            if i > 0 && !found && property_matches(key, property) {
+                // Keep existing key and line ending
+                found = true;
+                data.extend_from_slice(key.trim_end().as_bytes());
+                data.extend_from_slice(format!("{PROPERTY_DELIMITER} ").as_bytes());
+                data.extend_from_slice(value.as_bytes());
+                data.extend_from_slice(&line.as_bytes()[content.len()..]);
+            } else {
+                data.extend_from_slice(line.as_bytes());
+            }
+        }
+
+        if !found {
+            if !data.ends_with(b"\n") {
+                data.extend_from_slice(NEWLINE.as_bytes());
+            }
+            data.extend_from_slice(format!("{property}{PROPERTY_DELIMITER} ").as_bytes());
+            data.extend_from_slice(value.as_bytes());
+            if text.lines().count() > 1 && text.ends_with('\n') {
+                data.extend_from_slice(NEWLINE.as_bytes());
+            }
+        }
+
+        self.0 = data.into();
+        Ok(())
+    }
+
+    /// Remove the given property.
+    ///
+    /// Removes all lines with the given property, matched the same way as
+    /// [`property`](Self::property). Returns an error if the property does not exist.
+    ///
+    /// This will never remove the first line being the password. Fails for binary plaintext.
+    pub fn remove_property(&mut self, property: &str) -> Result<()> {
+        if self.is_binary() {
+            return Err(Err::Binary.into());
+        }
+        let property = property.trim();
+        let text = self.unsecure_to_str().map_err(Err::Utf8)?;
+        let mut data = Vec::with_capacity(text.len());
+        let mut found = false;
+        for (i, line) in text.split_inclusive('\n').enumerate() {
+            let content = line.trim_end_matches(['\r', '\n']);
+            let key = content.split(PROPERTY_DELIMITER).next().unwrap();
+            if i > 0 && property_matches(key, property) {
+                found = true;
+            } else {
+                data.extend_from_slice(line.as_bytes());
+            }
+        }
+
+        if !found {
+            data.zeroize();
+            return Err(Err::Property(property.to_lowercase()).into());
+        }
+
+        // Do not leave dangling newline if we removed the last line
+        if !text.ends_with('\n') && data.ends_with(b"\n") {
+            data.pop();
+            if data.ends_with(b"\r") {
+                data.pop();
+            }
+        }
+
+        self.0 = data.into();
+        Ok(())
     }
 
     /// Append other plaintext.
@@ -152,6 +252,23 @@ impl Plaintext {
         self.0 = data.into();
     }
 
+    /// Check whether this plaintext is binary.
+    ///
+    /// Binary secrets, such as attached files, start with [`BINARY_MARKER`]. Plaintext that is not
+    /// valid UTF-8 cannot be handled as text and is considered binary as well. Binary secrets
+    /// should not be printed.
+    pub fn is_binary(&self) -> bool {
+        self.unsecure_ref().starts_with(BINARY_MARKER) || self.unsecure_to_str().is_err()
+    }
+
+    /// Get the size of the binary payload in bytes.
+    ///
+    /// This excludes the [`BINARY_MARKER`] if present.
+    pub fn binary_size(&self) -> usize {
+        let data = self.unsecure_ref();
+        data.strip_prefix(BINARY_MARKER).unwrap_or(data).len()
+    }
+
     /// Check whether this plaintext is empty.
     ///
     /// - Empty if 0 bytes
@@ -188,6 +305,680 @@ impl From<&str> for Plaintext {
     }
 }
 
+/// Check whether a property line key matches the given property name.
+///
+/// Properties are matched case insensitively, surrounding whitespace is ignored.
+fn property_matches(key: &str, property: &str) -> bool {
+    key.trim().to_uppercase() == property.trim().to_uppercase()
+}
+
+/// Separator line between the password and structured body, gopass compatible.
+const STRUCTURED_SEPARATOR: &str = "---";
+
+/// Indentation used for block values we serialize.
+const STRUCTURED_INDENT: &str = "  ";
+
+/// Structured secret.
+///
+/// An opt-in gopass compatible secret format. The first line holds the password, followed by a
+/// `---` separator line and a YAML body with fields:
+///
+/// ```text
+/// password
+/// ---
+/// url: https://example.com
+/// username: alice
+/// notes: |
+///   Multi-line
+///   notes
+/// ```
+///
+/// Only a subset of YAML is supported: a top-level mapping with plain keys, holding plain, quoted
+/// or block scalar values. Nested blocks are kept as raw text. Fields are edited in place, so
+/// comments, ordering and formatting of all other fields are preserved.
+#[derive(Clone)]
+pub struct Structured {
+    /// The full plaintext.
+    plaintext: Plaintext,
+
+    /// Byte offset at which the YAML body starts, just after the separator line.
+    body: usize,
+
+    /// Fields in order of appearance.
+    fields: Vec<FieldSpan>,
+}
+
+/// Location of a field in a structured secret.
+#[derive(Clone, Debug)]
+struct FieldSpan {
+    /// Field key.
+    key: String,
+
+    /// Byte range of all lines of this field, including the last line terminator.
+    range: Range<usize>,
+}
+
+impl Structured {
+    /// Parse a structured secret.
+    ///
+    /// Returns an error if the plaintext is not structured, or if the body cannot be parsed.
+    pub fn parse(plaintext: Plaintext) -> Result<Self> {
+        if plaintext.is_binary() {
+            return Err(Err::Binary.into());
+        }
+        let text = plaintext.unsecure_to_str().map_err(Err::Utf8)?;
+        let lines = lines(text);
+        let separator = lines.get(1).ok_or(Err::NotStructured)?;
+        if separator.text.trim_end() != STRUCTURED_SEPARATOR {
+            return Err(Err::NotStructured.into());
+        }
+
+        let body = separator.end;
+        let fields = parse_fields(&lines[2..])?;
+        Ok(Self {
+            plaintext,
+            body,
+            fields,
+        })
+    }
+
+    /// Parse a structured secret, convert it if it is not structured yet.
+    ///
+    /// A plain secret is converted by inserting the separator after the password line. This only
+    /// succeeds if the remaining lines are valid fields, such as `key: value` properties, so no
+    /// content is lost.
+    pub fn parse_or_convert(plaintext: Plaintext) -> Result<Self> {
+        if plaintext.is_binary() {
+            return Err(Err::Binary.into());
+        }
+        if Self::is_structured(&plaintext) {
+            return Self::parse(plaintext);
+        }
+
+        let text = plaintext.unsecure_to_str().map_err(Err::Utf8)?;
+        let lines = lines(text);
+        if lines.len() > 1 {
+            parse_fields(&lines[1..]).map_err(|_| Err::Convert)?;
+        }
+
+        // Insert separator after password line, which is terminated if necessary
+        let split = lines.first().map(|l| l.end).unwrap_or(0);
+        let mut data = Vec::with_capacity(text.len() + STRUCTURED_SEPARATOR.len() + 4);
+        data.extend_from_slice(&text.as_bytes()[..split]);
+        if !text[..split].ends_with('\n') {
+            data.extend_from_slice(NEWLINE.as_bytes());
+        }
+        data.extend_from_slice(STRUCTURED_SEPARATOR.as_bytes());
+        data.extend_from_slice(NEWLINE.as_bytes());
+        data.extend_from_slice(&text.as_bytes()[split..]);
+        Self::parse(data.into())
+    }
+
+    /// Check whether the given plaintext is a structured secret.
+    pub fn is_structured(plaintext: &Plaintext) -> bool {
+        plaintext
+            .unsecure_to_str()
+            .ok()
+            .and_then(|text| text.lines().nth(1))
+            .is_some_and(|line| line.trim_end() == STRUCTURED_SEPARATOR)
+    }
+
+    /// Get the password, the first line.
+    pub fn password(&self) -> Result<Plaintext> {
+        self.plaintext.first_line()
+    }
+
+    /// Iterate over all field keys and their kind, in order.
+    pub fn fields(&self) -> impl Iterator<Item = (&str, FieldKind)> {
+        self.fields
+            .iter()
+            .map(|field| (field.key.as_str(), FieldKind::from_key(&field.key)))
+    }
+
+    /// Get the value of a field.
+    ///
+    /// The field is matched by exact key first, then case-insensitively. If the given name is
+    /// a well known field kind, such as `username`, the first field of the same kind is used as
+    /// fallback, matching `user` or `login` as well.
+    pub fn field(&self, name: &str) -> Result<Plaintext> {
+        let field = self
+            .find(name)
+            .ok_or_else(|| Err::Field(name.trim().into()))?;
+        let text = self.text()?;
+        Ok(decode_value(&text[field.range.clone()]).into())
+    }
+
+    /// Set the value of a field.
+    ///
+    /// An existing field is replaced in place keeping its key, otherwise the field is appended.
+    pub fn set_field(&mut self, name: &str, value: Plaintext) -> Result<()> {
+        let value = value.unsecure_to_str().map_err(Err::Utf8)?;
+        let (key, range) = match self.find(name) {
+            Some(field) => (field.key.clone(), field.range.clone()),
+            None => {
+                let key = name.trim();
+                if !is_valid_key(key) {
+                    return Err(Err::InvalidKey(key.into()).into());
+                }
+                let end = self.plaintext.unsecure_ref().len();
+                (key.into(), end..end)
+            }
+        };
+
+        let text = self.text()?;
+        let mut data = Vec::with_capacity(text.len() + value.len() + key.len() + 16);
+        data.extend_from_slice(&text.as_bytes()[..range.start]);
+        if range.is_empty() && !data.is_empty() && !data.ends_with(b"\n") {
+            data.extend_from_slice(NEWLINE.as_bytes());
+        }
+        let mut entry = encode_field(&key, value);
+        data.extend_from_slice(entry.as_bytes());
+        entry.zeroize();
+        data.extend_from_slice(&text.as_bytes()[range.end..]);
+
+        *self = Self::parse(data.into())?;
+        Ok(())
+    }
+
+    /// Remove a field.
+    ///
+    /// Returns an error if the field does not exist.
+    pub fn remove_field(&mut self, name: &str) -> Result<()> {
+        let range = self
+            .find(name)
+            .map(|field| field.range.clone())
+            .ok_or_else(|| Err::Field(name.trim().into()))?;
+
+        let text = self.text()?;
+        let mut data = Vec::with_capacity(text.len());
+        data.extend_from_slice(&text.as_bytes()[..range.start]);
+        data.extend_from_slice(&text.as_bytes()[range.end..]);
+
+        *self = Self::parse(data.into())?;
+        Ok(())
+    }
+
+    /// Get the YAML body, everything after the separator line.
+    pub fn body(&self) -> Result<Plaintext> {
+        Ok(self.text()?[self.body..].into())
+    }
+
+    /// Transform into plaintext.
+    pub fn into_plaintext(self) -> Plaintext {
+        self.plaintext
+    }
+
+    /// Find a field by name.
+    fn find(&self, name: &str) -> Option<&FieldSpan> {
+        let name = name.trim();
+        let kind = FieldKind::from_key(name);
+        self.fields
+            .iter()
+            .find(|field| field.key == name)
+            .or_else(|| {
+                self.fields
+                    .iter()
+                    .find(|field| field.key.eq_ignore_ascii_case(name))
+            })
+            .or_else(|| {
+                self.fields.iter().find(|field| {
+                    kind != FieldKind::Custom && FieldKind::from_key(&field.key) == kind
+                })
+            })
+    }
+
+    /// Get the plaintext as string.
+    fn text(&self) -> Result<&str> {
+        Ok(self.plaintext.unsecure_to_str().map_err(Err::Utf8)?)
+    }
+}
+
+impl TryFrom<Plaintext> for Structured {
+    type Error = anyhow::Error;
+
+    fn try_from(plaintext: Plaintext) -> Result<Self> {
+        Self::parse(plaintext)
+    }
+}
+
+/// Kind of a structured secret field, derived from its key.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FieldKind {
+    /// Website URL.
+    Url,
+
+    /// Username, login or email address.
+    Username,
+
+    /// One-time password, such as an `otpauth://` URL.
+    Otp,
+
+    /// Free form notes.
+    Notes,
+
+    /// Any other field.
+    Custom,
+}
+
+impl FieldKind {
+    /// Determine the field kind for the given key.
+    pub fn from_key(key: &str) -> Self {
+        match key.trim().to_lowercase().as_str() {
+            "url" | "uri" | "website" | "site" | "link" => Self::Url,
+            "username" | "user" | "login" | "email" | "mail" => Self::Username,
+            "otp" | "totp" | "hotp" | "2fa" | "otpauth" => Self::Otp,
+            "notes" | "note" | "comment" | "comments" => Self::Notes,
+            _ => Self::Custom,
+        }
+    }
+
+    /// Get the kind name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Url => "url",
+            Self::Username => "username",
+            Self::Otp => "otp",
+            Self::Notes => "notes",
+            Self::Custom => "custom",
+        }
+    }
+}
+
+/// A line in plaintext.
+struct Line<'a> {
+    /// Line text, without line terminator.
+    text: &'a str,
+
+    /// Byte offset of the line start.
+    start: usize,
+
+    /// Byte offset of the line end, including line terminator.
+    end: usize,
+}
+
+/// Split text into lines, keeping track of their byte offsets.
+fn lines(text: &str) -> Vec<Line<'_>> {
+    let mut start = 0;
+    text.split_inclusive('\n')
+        .map(|raw| {
+            let line = Line {
+                text: raw.trim_end_matches('\n').trim_end_matches('\r'),
+                start,
+                end: start + raw.len(),
+            };
+            start = line.end;
+            line
+        })
+        .collect()
+}
+
+/// Parse fields from the lines of a YAML body.
+fn parse_fields(lines: &[Line]) -> Result<Vec<FieldSpan>> {
+    let mut fields = vec![];
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i].text;
+
+        // Skip blank lines, comments and document markers
+        if line.trim().is_empty()
+            || line.starts_with('#')
+            || line == "..."
+            || line == STRUCTURED_SEPARATOR
+        {
+            i += 1;
+            continue;
+        }
+
+        let key = parse_key(line).ok_or_else(|| Err::InvalidLine(line.trim().into()))?;
+
+        // Field continues on indented lines, blank lines and unindented sequence items
+        let mut end = i + 1;
+        let mut j = i + 1;
+        while j < lines.len() {
+            let next = lines[j].text;
+            if next.trim().is_empty() {
+                j += 1;
+            } else if next.starts_with([' ', '\t']) || next == "-" || next.starts_with("- ") {
+                j += 1;
+                end = j;
+            } else {
+                break;
+            }
+        }
+
+        fields.push(FieldSpan {
+            key: key.into(),
+            range: lines[i].start..lines[end - 1].end,
+        });
+        i = end;
+    }
+    Ok(fields)
+}
+
+/// Parse the key of a field line.
+///
+/// Returns `None` if this line does not start a field.
+fn parse_key(line: &str) -> Option<&str> {
+    if line.starts_with([' ', '\t', '-', '#', '[', '{']) {
+        return None;
+    }
+
+    // Quoted key
+    if let Some(quote) = line.chars().next().filter(|c| *c == '"' || *c == '\'') {
+        let close = line[1..].find(quote)? + 1;
+        let rest = &line[close + 1..];
+        return (rest.starts_with(": ") || rest == ":" || rest.starts_with(":\t"))
+            .then(|| &line[1..close]);
+    }
+
+    line.match_indices(':')
+        .find(|(i, _)| matches!(line[i + 1..].chars().next(), None | Some(' ') | Some('\t')))
+        .map(|(i, _)| line[..i].trim_end())
+        .filter(|key| !key.is_empty())
+}
+
+/// Check whether the given key can be written as plain key.
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && !key.starts_with(['-', '#', '"', '\''])
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ' ' | '/'))
+}
+
+/// Decode the value of a field from its raw text.
+fn decode_value(raw: &str) -> String {
+    let lines: Vec<&str> = raw
+        .split_inclusive('\n')
+        .map(|l| l.trim_end_matches('\n').trim_end_matches('\r'))
+        .collect();
+    let first = lines[0];
+    let head = match first.chars().next() {
+        Some(quote @ ('"' | '\'')) => first[1..]
+            .find(quote)
+            .map(|i| &first[i + 3..])
+            .unwrap_or(""),
+        _ => &first[first.find(':').map(|i| i + 1).unwrap_or(first.len())..],
+    }
+    .trim();
+    let rest = &lines[1..];
+
+    match head.chars().next() {
+        Some('|') | Some('>') => decode_block(head, rest),
+        Some('"') | Some('\'') => {
+            let start = head.as_ptr() as usize - raw.as_ptr() as usize;
+            decode_quoted(&raw[start..])
+        }
+        None => dedent(rest),
+        _ => {
+            let mut value = strip_comment(head).to_string();
+            for line in rest {
+                let line = line.trim();
+                if line.is_empty() {
+                    value.push('\n');
+                } else {
+                    if !value.ends_with('\n') {
+                        value.push(' ');
+                    }
+                    value.push_str(strip_comment(line));
+                }
+            }
+            value
+        }
+    }
+}
+
+/// Strip a trailing comment from a plain value.
+fn strip_comment(value: &str) -> &str {
+    value
+        .find(" #")
+        .or_else(|| value.find("\t#"))
+        .map(|i| &value[..i])
+        .unwrap_or(value)
+        .trim_end()
+}
+
+/// Decode a block scalar, with the given header such as `|-`.
+fn decode_block(header: &str, lines: &[&str]) -> String {
+    let header = strip_comment(header);
+    let folded = header.starts_with('>');
+    let chomp = header.chars().find(|c| *c == '-' || *c == '+');
+    let indent = header
+        .chars()
+        .find_map(|c| c.to_digit(10))
+        .map(|i| i as usize)
+        .unwrap_or_else(|| {
+            lines
+                .iter()
+                .find(|l| !l.trim().is_empty())
+                .map(|l| l.len() - l.trim_start_matches(' ').len())
+                .unwrap_or(0)
+        });
+
+    let content: Vec<&str> = lines
+        .iter()
+        .map(|l| l.get(indent..).unwrap_or(""))
+        .collect();
+    let last = content.iter().rposition(|l| !l.is_empty());
+    let (content, trailing) = match last {
+        Some(last) => (&content[..=last], content.len() - last - 1),
+        None => (&content[..0], content.len()),
+    };
+
+    let mut value = if folded {
+        let mut value = String::new();
+        for (i, line) in content.iter().enumerate() {
+            if i > 0 {
+                let prev = content[i - 1];
+                if line.is_empty() || prev.is_empty() || line.starts_with(' ') {
+                    value.push('\n');
+                } else {
+                    value.push(' ');
+                }
+            }
+            value.push_str(line);
+        }
+        value
+    } else {
+        content.join("\n")
+    };
+
+    match chomp {
+        Some('-') => {}
+        Some('+') => {
+            value.push('\n');
+            value.push_str(&"\n".repeat(trailing));
+        }
+        _ if !content.is_empty() => value.push('\n'),
+        _ => {}
+    }
+    value
+}
+
+/// Remove common indentation from nested block lines.
+fn dedent(lines: &[&str]) -> String {
+    let indent = lines
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    lines
+        .iter()
+        .map(|l| l.get(indent..).unwrap_or(""))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim_end()
+        .to_string()
+}
+
+/// Decode a single or double quoted value, starting at the opening quote.
+fn decode_quoted(raw: &str) -> String {
+    let mut chars = raw.chars().peekable();
+    let quote = chars.next().unwrap();
+    let mut value = String::new();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c == quote => {
+                // Single quotes are escaped by doubling them
+                if quote == '\'' && chars.peek() == Some(&'\'') {
+                    chars.next();
+                    value.push('\'');
+                } else {
+                    break;
+                }
+            }
+            '\\' if quote == '"' => match chars.next() {
+                Some('0') => value.push('\0'),
+                Some('a') => value.push('\x07'),
+                Some('b') => value.push('\x08'),
+                Some('t') | Some('\t') => value.push('\t'),
+                Some('n') => value.push('\n'),
+                Some('v') => value.push('\x0b'),
+                Some('f') => value.push('\x0c'),
+                Some('r') => value.push('\r'),
+                Some('e') => value.push('\x1b'),
+                Some('N') => value.push('\u{85}'),
+                Some('_') => value.push('\u{a0}'),
+                Some('L') => value.push('\u{2028}'),
+                Some('P') => value.push('\u{2029}'),
+                Some(c @ ('x' | 'u' | 'U')) => {
+                    let len = match c {
+                        'x' => 2,
+                        'u' => 4,
+                        _ => 8,
+                    };
+                    let hex: String = (0..len).filter_map(|_| chars.next()).collect();
+                    if let Some(c) = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                        value.push(c);
+                    }
+                }
+                // Escaped line break, continue on next line without folding
+                Some('\n') => {
+                    while chars.peek().is_some_and(|c| *c == ' ' || *c == '\t') {
+                        chars.next();
+                    }
+                }
+                Some('\r') => {}
+                Some(c) => value.push(c),
+                None => break,
+            },
+            '\r' => {}
+            // Fold line breaks into a space, empty lines into newlines
+            '\n' => {
+                value.truncate(value.trim_end_matches([' ', '\t']).len());
+                let mut empty = 0;
+                loop {
+                    while chars.peek().is_some_and(|c| *c == ' ' || *c == '\t') {
+                        chars.next();
+                    }
+                    match chars.peek() {
+                        Some('\r') => {
+                            chars.next();
+                        }
+                        Some('\n') => {
+                            chars.next();
+                            empty += 1;
+                        }
+                        _ => break,
+                    }
+                }
+                if empty == 0 {
+                    value.push(' ');
+                } else {
+                    value.push_str(&"\n".repeat(empty));
+                }
+            }
+            c => value.push(c),
+        }
+    }
+    value
+}
+
+/// Encode a field with the given key and value, including line terminator.
+fn encode_field(key: &str, value: &str) -> String {
+    // Multi-line values as literal block, if representable
+    if value.contains('\n') {
+        let body = value.strip_suffix('\n').unwrap_or(value);
+        let representable = !body.ends_with('\n')
+            && !body.trim().is_empty()
+            && body
+                .split('\n')
+                .all(|l| !(l.is_empty() ^ l.trim().is_empty()))
+            && !body
+                .chars()
+                .any(|c| c.is_control() && c != '\n' && c != '\t');
+        if representable {
+            let indicator = match body.split('\n').find(|l| !l.is_empty()) {
+                Some(l) if l.starts_with(' ') => STRUCTURED_INDENT.len().to_string(),
+                _ => String::new(),
+            };
+            let chomp = if value.ends_with('\n') { "" } else { "-" };
+            let mut entry = format!("{key}: |{indicator}{chomp}{NEWLINE}");
+            for line in body.split('\n') {
+                if !line.is_empty() {
+                    entry.push_str(STRUCTURED_INDENT);
+                    entry.push_str(line);
+                }
+                entry.push_str(NEWLINE);
+            }
+            return entry;
+        }
+    }
+
+    if is_plain_safe(value) {
+        format!("{key}: {value}{NEWLINE}")
+    } else {
+        format!("{key}: {}{NEWLINE}", encode_quoted(value))
+    }
+}
+
+/// Check whether a value can be written as plain scalar and is read back as the same string.
+fn is_plain_safe(value: &str) -> bool {
+    let lower = value.to_lowercase();
+    !value.is_empty()
+        && value.trim() == value
+        && !value.starts_with([
+            '-', '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '"', '%',
+            '@', '`',
+        ])
+        && !value.ends_with(':')
+        && !value.contains(": ")
+        && !value.contains(":\t")
+        && !value.contains(" #")
+        && !value.contains("\t#")
+        && !value.chars().any(|c| c.is_control())
+        && !matches!(
+            lower.as_str(),
+            "~" | "null" | "true" | "false" | "yes" | "no" | "on" | "off" | "y" | "n"
+        )
+        && value.parse::<f64>().is_err()
+        && !value.starts_with("0x")
+        && !value.starts_with("0o")
+}
+
+/// Encode a value as double quoted scalar.
+fn encode_quoted(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() || matches!(c, '\u{2028}' | '\u{2029}' | '\u{feff}') => {
+                quoted.push_str(&format!("\\u{:04x}", c as u32));
+            }
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
 /// A plaintext or ciphertext handling error.
 #[derive(Debug, Error)]
 pub enum Err {
@@ -196,6 +987,32 @@ pub enum Err {
 
     #[error("property '{}' does not exist in plaintext", _0)]
     Property(String),
+
+    #[error("cannot edit binary plaintext as text")]
+    Binary,
+
+    #[error("invalid property name: '{}'", _0)]
+    PropertyName(String),
+
+    #[error("property value must be a single line")]
+    PropertyValue,
+
+    #[error("plaintext is not a structured secret, it has no '---' separator on the second line")]
+    NotStructured,
+
+    #[error(
+        "cannot convert plaintext into structured secret, lines after the first are not fields"
+    )]
+    Convert,
+
+    #[error("invalid line in structured secret: '{}'", _0)]
+    InvalidLine(String),
+
+    #[error("invalid field name for structured secret: '{}'", _0)]
+    InvalidKey(String),
+
+    #[error("field '{}' does not exist in structured secret", _0)]
+    Field(String),
 }
 
 #[cfg(test)]
@@ -227,6 +1044,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn plaintext_is_binary() {
+        assert!(!Plaintext::empty().is_binary());
+        assert!(!Plaintext::from("abc\ndef").is_binary());
+        assert!(!Plaintext::from("abc\0def").is_binary());
+        assert!(!Plaintext::from("prs-binary\nabc").is_binary());
+        assert!(Plaintext::from(vec![0xff, 0xfe, 0x61]).is_binary());
+
+        let mut data = BINARY_MARKER.to_vec();
+        data.extend(b"abc");
+        let plaintext = Plaintext::from(data);
+        assert!(plaintext.is_binary());
+        assert_eq!(plaintext.binary_size(), 3);
+        assert!(Plaintext::from(BINARY_MARKER.to_vec()).is_binary());
+    }
+
+    #[test]
+    fn plaintext_binary_not_editable() {
+        let mut data = BINARY_MARKER.to_vec();
+        data.extend(b"-----BEGIN CERTIFICATE-----\nabc\n");
+        let mut plaintext = Plaintext::from(data.clone());
+
+        assert!(
+            plaintext
+                .set_property("owner", Plaintext::from("ops"))
+                .is_err()
+        );
+        assert!(plaintext.remove_property("owner").is_err());
+        assert_eq!(plaintext.unsecure_ref(), data.as_slice());
+        assert!(Structured::parse(plaintext.clone()).is_err());
+        assert!(Structured::parse_or_convert(plaintext).is_err());
+    }
+
     #[test]
     fn plaintext_first_line() {
         // (input, output)
@@ -389,6 +1239,83 @@ mod tests {
         }
     }
 
+    #[test]
+    fn plaintext_set_property() {
+        // (input, property, value, output)
+        #[rustfmt::skip]
+        let set = vec![
+            ("", "Name", "abc", "\nName: abc"),
+            ("pass", "Name", "abc", "pass\nName: abc"),
+            ("pass\n", "Name", "abc", "pass\nName: abc"),
+            ("Name: x", "Name", "abc", "Name: x\nName: abc"),
+            ("pass\nName: x\nName: y", "name", "abc", "pass\nName: abc\nName: y"),
+            ("pass\r\n  NAME  :  x  \r\nMail: y", "name", "", "pass\r\n  NAME: \r\nMail: y"),
+            ("pass\nMail: y\n", "Name", "abc", "pass\nMail: y\nName: abc\n"),
+            ("pass\nStraße: x", "STRASSE", "abc", "pass\nStraße: abc"),
+        ];
+
+        for (input, property, value, output) in set {
+            let mut plaintext = Plaintext::from(input);
+            plaintext
+                .set_property(property, Plaintext::from(value))
+                .unwrap();
+            assert_eq!(
+                plaintext.unsecure_to_str().unwrap().replace("\r\n", "\n"),
+                output.replace("\r\n", "\n"),
+                "incorrect plaintext after setting property",
+            );
+            assert_eq!(
+                plaintext
+                    .property(property)
+                    .unwrap()
+                    .unsecure_to_str()
+                    .unwrap(),
+                value,
+                "incorrect property value after setting property",
+            );
+        }
+
+        let mut plaintext = Plaintext::from("pass");
+        assert!(plaintext.set_property("a:b", Plaintext::from("x")).is_err());
+        assert!(
+            plaintext
+                .set_property("a", Plaintext::from("x\ny"))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn plaintext_remove_property() {
+        // (input, property, output)
+        #[rustfmt::skip]
+        let set = vec![
+            ("pass\nName: abc", "name", Some("pass")),
+            ("pass\nName: abc\n", "name", Some("pass\n")),
+            ("pass\nName: abc\r\nMail: x", "NAME", Some("pass\nMail: x")),
+            ("pass\nName: a\nMail: x\nname: b\n", "name", Some("pass\nMail: x\n")),
+            ("Name: abc", "name", None),
+            ("pass\nMail: x", "name", None),
+            ("pass\nÄrger: x", "ärger", Some("pass")),
+        ];
+
+        for (input, property, output) in set {
+            let mut plaintext = Plaintext::from(input);
+            let result = plaintext.remove_property(property);
+            match output {
+                Some(output) => {
+                    result.unwrap();
+                    assert_eq!(
+                        plaintext.unsecure_to_str().unwrap(),
+                        output,
+                        "incorrect plaintext after removing property",
+                    );
+                    assert!(plaintext.property(property).is_err());
+                }
+                None => assert!(result.is_err(), "no property should be removed"),
+            }
+        }
+    }
+
     #[quickcheck]
     fn plaintext_must_zero_on_drop(plaintext: String) -> bool {
         // Skip all-zero/empty because we cannot reliably test
@@ -411,6 +1338,119 @@ mod tests {
         slice != must_not_match
     }
 
+    #[test]
+    fn structured_parse() {
+        assert!(Structured::parse(Plaintext::from("abc")).is_err());
+        assert!(Structured::parse(Plaintext::from("abc\nurl: x")).is_err());
+        assert!(Structured::parse(Plaintext::from("abc\n---\n  bad")).is_err());
+        assert!(!Structured::is_structured(&Plaintext::from("abc\nurl: x")));
+        assert!(Structured::is_structured(&Plaintext::from("abc\n---")));
+
+        let secret = Structured::parse(Plaintext::from(
+            "pass\r\n---\r\n# comment\r\nurl: https://example.com # home\r\nUser: alice\r\nnotes: |\r\n  line 1\r\n\r\n  line 2\r\nempty:\r\nquoted: \"a\\tb \\\"c\\\"\"\r\nsingle: 'it''s'\r\nfolded: >-\r\n  a\r\n  b\r\nlist:\r\n- a\r\n- b\r\n",
+        ))
+        .unwrap();
+
+        // (field, output)
+        #[rustfmt::skip]
+        let set = vec![
+            ("url", "https://example.com"),
+            ("website", "https://example.com"),
+            ("user", "alice"),
+            ("username", "alice"),
+            ("notes", "line 1\n\nline 2\n"),
+            ("empty", ""),
+            ("quoted", "a\tb \"c\""),
+            ("single", "it's"),
+            ("folded", "a b"),
+            ("list", "- a\n- b"),
+        ];
+        for (field, output) in set {
+            assert_eq!(
+                secret.field(field).unwrap().unsecure_to_str().unwrap(),
+                output,
+                "incorrect field value",
+            );
+        }
+
+        assert_eq!(
+            secret.password().unwrap().unsecure_to_str().unwrap(),
+            "pass"
+        );
+        assert!(secret.field("missing").is_err());
+        assert_eq!(
+            secret.fields().map(|(key, _)| key).collect::<Vec<_>>(),
+            [
+                "url", "User", "notes", "empty", "quoted", "single", "folded", "list"
+            ],
+        );
+    }
+
+    #[test]
+    fn structured_set_field() {
+        let input = "pass\n---\n# comment\nurl: a\nnotes: |\n  x\n  y\nuser: b\n";
+        let mut secret = Structured::parse(Plaintext::from(input)).unwrap();
+
+        // Replace in place, keep everything else untouched
+        secret.set_field("notes", Plaintext::from("z")).unwrap();
+        secret.set_field("URL", Plaintext::from("c: d")).unwrap();
+        secret.set_field("extra", Plaintext::from("1\n 2")).unwrap();
+        assert_eq!(
+            secret.field("extra").unwrap().unsecure_to_str().unwrap(),
+            "1\n 2"
+        );
+        assert_eq!(
+            secret
+                .clone()
+                .into_plaintext()
+                .unsecure_to_str()
+                .unwrap()
+                .replace("\r\n", "\n"),
+            "pass\n---\n# comment\nurl: \"c: d\"\nnotes: z\nuser: b\nextra: |-\n  1\n   2\n",
+        );
+
+        secret.remove_field("notes").unwrap();
+        assert!(secret.remove_field("notes").is_err());
+        assert!(secret.set_field("- bad", Plaintext::from("x")).is_err());
+        assert_eq!(
+            secret
+                .into_plaintext()
+                .unsecure_to_str()
+                .unwrap()
+                .replace("\r\n", "\n"),
+            "pass\n---\n# comment\nurl: \"c: d\"\nuser: b\nextra: |-\n  1\n   2\n",
+        );
+    }
+
+    #[test]
+    fn structured_convert() {
+        let secret = Structured::parse_or_convert(Plaintext::from("pass\nuser: a")).unwrap();
+        assert_eq!(
+            secret
+                .into_plaintext()
+                .unsecure_to_str()
+                .unwrap()
+                .replace("\r\n", "\n"),
+            "pass\n---\nuser: a"
+        );
+        let secret = Structured::parse_or_convert(Plaintext::from("pass")).unwrap();
+        assert!(secret.field("user").is_err());
+        assert!(Structured::parse_or_convert(Plaintext::from("pass\nfree text")).is_err());
+    }
+
+    #[quickcheck]
+    fn structured_field_round_trip(value: String) -> bool {
+        let mut secret = Structured::parse(Plaintext::from("pass\n---\na: b\n")).unwrap();
+        secret
+            .set_field("field", Plaintext::from(value.as_str()))
+            .unwrap();
+        secret.set_field("z", Plaintext::from("c")).unwrap();
+        let secret = Structured::parse(secret.into_plaintext()).unwrap();
+        secret.field("field").unwrap().unsecure_to_str().unwrap() == value
+            && secret.field("a").unwrap().unsecure_to_str().unwrap() == "b"
+            && secret.field("z").unwrap().unsecure_to_str().unwrap() == "c"
+    }
+
     #[test]
     fn ciphertext_empty() {
         let empty = Ciphertext::empty();